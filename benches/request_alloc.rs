@@ -0,0 +1,82 @@
+//! allocations and time per request, owned vs borrowed decoding  
+//! run with `cargo bench --bench request_alloc`
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use ocsp::request::{OcspRequest, OcspRequestRef};
+
+/// system allocator counting every allocation
+struct Counting;
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const ROUNDS: usize = 100_000;
+
+// two certs with nonce
+const REQUEST: &str = "3081b53081b230818a30433041300906052b0e03021a05000414694d18a9be42\
+    f7802614d4844f23601478b788200414397be002a2f571fd80dceb52a17a7f8b\
+    632be755020841300983331f9d4f30433041300906052b0e03021a0500041469\
+    4d18a9be42f7802614d4844f23601478b788200414397be002a2f571fd80dceb\
+    52a17a7f8b632be75502086378e51d448ff46da2233021301f06092b06010505\
+    07300102041204105e7a74e51c861a3f79454658bb090244";
+
+/// run f ROUNDS times, return allocations per call and nanoseconds per call
+fn measure<F: FnMut()>(mut f: F) -> (f64, f64) {
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    let elapsed = start.elapsed().as_nanos() as f64;
+    let allocs = ALLOCS.load(Ordering::Relaxed) - allocs;
+    (allocs as f64 / ROUNDS as f64, elapsed / ROUNDS as f64)
+}
+
+fn main() {
+    let req = hex::decode(REQUEST).unwrap();
+
+    // certids and nonce only, as on a responder hot path
+    let (owned_allocs, owned_ns) = measure(|| {
+        let r = OcspRequest::parse(&req).unwrap();
+        std::hint::black_box((r.extract_certid().len(), r.extract_ext().is_some()));
+    });
+    let (borrowed_allocs, borrowed_ns) = measure(|| {
+        let r = OcspRequestRef::parse(&req).unwrap();
+        std::hint::black_box((r.request_list.len(), r.nonce().unwrap()));
+    });
+
+    println!(
+        "{:<20}{:>16}{:>16}",
+        "decoding", "allocs/request", "ns/request"
+    );
+    println!(
+        "{:<20}{:>16.1}{:>16.0}",
+        "OcspRequest", owned_allocs, owned_ns
+    );
+    println!(
+        "{:<20}{:>16.1}{:>16.0}",
+        "OcspRequestRef", borrowed_allocs, borrowed_ns
+    );
+    assert!(borrowed_allocs < owned_allocs);
+}
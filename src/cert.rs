@@ -0,0 +1,726 @@
+//! X.509 certificate, RFC 5280
+//! only what OCSP needs is decoded: names, serial, validity, public key and a few extensions
+
+use asn1_der::DerObject;
+use tracing::{error, trace};
+
+use crate::common::{
+    asn1::{
+        asn1_field, asn1_offset, asn1_tbs_certificate, AlgorithmIdentifier, Bytes, CertId,
+        CertIdHash, GeneralizedTime, Oid, SequenceRef, SerialNumber, ASN1_BIT_STRING, ASN1_BOOLEAN,
+        ASN1_EXPLICIT_3, ASN1_GENERALIZED_TIME, ASN1_OCTET, ASN1_OID, ASN1_UTC_TIME,
+    },
+    der::{DerDecode, DerEncode, DerWriter},
+    name::DistinguishedName,
+};
+use crate::err::{OcspError, Result};
+use crate::oid::{
+    AD_OCSP_HEX, CERT_EXT_AIA_HEX, CERT_EXT_EXT_KEY_USAGE_HEX, CERT_EXT_KEY_USAGE_HEX,
+    CERT_EXT_OCSP_NOCHECK_HEX, KP_OCSP_SIGNING_HEX,
+};
+use crate::response::{ResponderId, ResponderType};
+use crate::verify::PublicKey;
+
+/// GeneralName uniformResourceIdentifier, implicit 6
+const ASN1_GENERAL_NAME_URI: u8 = 0x86;
+
+/// RFC 5280 KeyUsage bits, bit n of the BIT STRING is `1 << n`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct KeyUsage(pub u16);
+
+impl KeyUsage {
+    /// digitalSignature (0)
+    pub const DIGITAL_SIGNATURE: u16 = 1;
+    /// nonRepudiation or contentCommitment (1)
+    pub const NON_REPUDIATION: u16 = 1 << 1;
+    /// keyEncipherment (2)
+    pub const KEY_ENCIPHERMENT: u16 = 1 << 2;
+    /// dataEncipherment (3)
+    pub const DATA_ENCIPHERMENT: u16 = 1 << 3;
+    /// keyAgreement (4)
+    pub const KEY_AGREEMENT: u16 = 1 << 4;
+    /// keyCertSign (5)
+    pub const KEY_CERT_SIGN: u16 = 1 << 5;
+    /// cRLSign (6)
+    pub const CRL_SIGN: u16 = 1 << 6;
+    /// encipherOnly (7)
+    pub const ENCIPHER_ONLY: u16 = 1 << 7;
+    /// decipherOnly (8)
+    pub const DECIPHER_ONLY: u16 = 1 << 8;
+
+    /// get key usage from raw BIT STRING
+    pub fn parse(ku: &[u8]) -> Result<Self> {
+        let bits = DerObject::decode(ku).map_err(OcspError::Asn1DecodingError)?;
+        let val = bits.value();
+        if bits.tag() != ASN1_BIT_STRING || val.is_empty() || val[0] > 7 {
+            error!("Provided key usage is not a BIT STRING");
+            return Err(OcspError::Asn1MismatchError("KeyUsage"));
+        }
+        let mut r = 0u16;
+        // named bits beyond decipherOnly are not defined
+        for (i, b) in val[1..].iter().take(2).enumerate() {
+            for j in 0..8 {
+                if b & (0x80 >> j) != 0 {
+                    r |= 1 << (i * 8 + j);
+                }
+            }
+        }
+        Ok(KeyUsage(r))
+    }
+
+    /// whether all given bits are set
+    pub fn contains(&self, bits: u16) -> bool {
+        self.0 & bits == bits
+    }
+}
+
+/// RFC 5280 Certificate
+/// extensions other than key usage, extended key usage, authority information access
+/// and id-pkix-ocsp-nocheck are skipped, critical ones are kept in `unrecognized_critical`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Certificate {
+    /// certificate serial number
+    pub serial_num: SerialNumber,
+    /// issuer name
+    pub issuer: DistinguishedName,
+    /// validity notBefore, UTCTime is converted
+    pub not_before: GeneralizedTime,
+    /// validity notAfter, UTCTime is converted
+    pub not_after: GeneralizedTime,
+    /// subject name
+    pub subject: DistinguishedName,
+    /// subject public key info
+    pub public_key: PublicKey,
+    /// key usage extension, None if absent
+    pub key_usage: Option<KeyUsage>,
+    /// extended key usage purposes, None if absent
+    pub ext_key_usage: Option<Vec<Oid>>,
+    /// OCSP responder URLs in authority information access
+    pub ocsp_urls: Vec<String>,
+    /// whether id-pkix-ocsp-nocheck extension is present
+    pub ocsp_nocheck: bool,
+    /// critical extensions not understood, certificate must not be relied on if any
+    pub unrecognized_critical: Vec<Oid>,
+    /// signature algorithm of issuer
+    pub signature_algo: AlgorithmIdentifier,
+    /// issuer signature, without unused bits byte
+    pub signature: Bytes,
+    /// DER of tbsCertificate, signature is verified over it
+    pub tbs_raw: Bytes,
+    /// DER of certificate
+    pub raw: Bytes,
+}
+
+impl Certificate {
+    /// get certificate from raw bytes
+    pub fn parse(cert: &[u8]) -> Result<Self> {
+        trace!("Parsing certificate {}", hex::encode(cert));
+        let obj = DerObject::decode(cert).map_err(OcspError::Asn1DecodingError)?;
+        let s = SequenceRef::decode(obj.raw())?;
+        if s.len() != 3 {
+            error!(
+                "Provided certificate contains {} items, expecting 3",
+                s.len()
+            );
+            return Err(OcspError::Asn1LengthError("Certificate"));
+        }
+        let mut items = s.iter();
+        let (tbs, algo, sign) = match (items.next(), items.next(), items.next()) {
+            (Some(t), Some(a), Some(s)) => (t, a, s),
+            _ => return Err(OcspError::Asn1Truncated),
+        };
+
+        let signature_algo = AlgorithmIdentifier::parse(algo.raw()).map_err(asn1_field(
+            cert,
+            algo.raw(),
+            "signatureAlgorithm",
+        ))?;
+        // leading byte is the number of unused bits
+        if sign.tag() != ASN1_BIT_STRING || sign.value().first() != Some(&0x00) {
+            error!("Provided signature is not a BIT STRING of whole bytes");
+            let e = OcspError::Asn1MismatchError("Certificate signature");
+            return Err(asn1_field(cert, sign.raw(), "signatureValue")(e));
+        }
+
+        let fields = asn1_tbs_certificate(cert)?;
+        let sn = fields[0].raw();
+        let serial_num =
+            SerialNumber::parse(sn).map_err(asn1_field(cert, sn, "tbsCertificate.serialNumber"))?;
+        let issuer = fields[2].raw();
+        let issuer = DistinguishedName::parse(issuer).map_err(asn1_field(
+            cert,
+            issuer,
+            "tbsCertificate.issuer",
+        ))?;
+        let validity = fields[3].raw();
+        let (not_before, not_after) = parse_validity(validity).map_err(asn1_field(
+            cert,
+            validity,
+            "tbsCertificate.validity",
+        ))?;
+        let subject = fields[4].raw();
+        let subject = DistinguishedName::parse(subject).map_err(asn1_field(
+            cert,
+            subject,
+            "tbsCertificate.subject",
+        ))?;
+        let spki = fields[5].raw();
+        let public_key = PublicKey::from_spki_der(spki).map_err(asn1_field(
+            cert,
+            spki,
+            "tbsCertificate.subjectPublicKeyInfo",
+        ))?;
+
+        let mut ext = Extensions::default();
+        // issuerUniqueID and subjectUniqueID may come first
+        if let Some(list) = fields[6..].iter().find(|f| f.tag() == ASN1_EXPLICIT_3) {
+            let field = asn1_field(cert, list.raw(), "tbsCertificate.extensions");
+            let list = SequenceRef::decode(list.value()).map_err(field)?;
+            for (i, e) in list.iter().enumerate() {
+                let offset = asn1_offset(cert, e.raw());
+                ext.parse_one(e.raw())
+                    .map_err(|err| err.at(&format!("tbsCertificate.extensions[{}]", i), offset))?;
+            }
+        }
+
+        trace!("Certificate with sn {} successfully decoded", serial_num);
+        Ok(Certificate {
+            serial_num,
+            issuer,
+            not_before,
+            not_after,
+            subject,
+            public_key,
+            key_usage: ext.key_usage,
+            ext_key_usage: ext.ext_key_usage,
+            ocsp_urls: ext.ocsp_urls,
+            ocsp_nocheck: ext.ocsp_nocheck,
+            unrecognized_critical: ext.unrecognized_critical,
+            signature_algo,
+            signature: sign.value()[1..].to_vec(),
+            tbs_raw: tbs.raw().to_vec(),
+            raw: obj.raw().to_vec(),
+        })
+    }
+
+    /// get certificate from PEM `CERTIFICATE`
+    pub fn from_pem(pem: &str) -> Result<Self> {
+        let (label, der) = pem_rfc7468::decode_vec(pem.as_bytes()).map_err(|e| {
+            error!("Unable to decode PEM: {}", e);
+            OcspError::Asn1MismatchError("PEM")
+        })?;
+        if label != "CERTIFICATE" {
+            error!("Provided PEM label is {}, expecting certificate", label);
+            return Err(OcspError::Asn1MismatchError("PEM label"));
+        }
+        Certificate::parse(&der)
+    }
+
+    /// encode to ASN.1 DER, certificate is kept as received
+    pub fn to_der(&self) -> Result<Bytes> {
+        Ok(self.raw.clone())
+    }
+
+    /// cid of this certificate issued by issuer, see [CertId::from_issuer_and_serial]
+    pub fn cert_id(&self, issuer: &Certificate, hash: CertIdHash) -> Result<CertId> {
+        CertId::from_issuer_and_serial(&issuer.raw, self.serial_num.clone(), hash)
+    }
+
+    /// responder id by SHA-1 hash of subject public key, as responder certificate
+    pub fn responder_id_by_key(&self) -> ResponderId {
+        ResponderId::new_key_hash(&CertIdHash::Sha1.digest(self.public_key.key()))
+    }
+
+    /// responder id by subject name, as responder certificate  
+    /// subject is kept as received, re-encoding may reorder multi-valued RDNs
+    pub fn responder_id_by_name(&self) -> Result<ResponderId> {
+        let tbs = asn1_tbs_certificate(&self.raw)?;
+        Ok(ResponderId {
+            id_by: ResponderType::BY_NAME,
+            id: tbs[4].raw().to_vec(),
+        })
+    }
+
+    /// whether certificate is allowed to sign OCSP responses on behalf of its issuer
+    /// - extended key usage has id-kp-OCSPSigning
+    /// - key usage, if present, has digitalSignature or nonRepudiation
+    /// - no unrecognized critical extension, see RFC 5280 4.2
+    pub fn is_ocsp_signer(&self) -> bool {
+        if !self.unrecognized_critical.is_empty() {
+            return false;
+        }
+        let eku = self
+            .ext_key_usage
+            .as_ref()
+            .map(|l| l.iter().any(|o| o.as_bytes() == KP_OCSP_SIGNING_HEX))
+            .unwrap_or(false);
+        let ku = self
+            .key_usage
+            .map(|k| {
+                k.contains(KeyUsage::DIGITAL_SIGNATURE) || k.contains(KeyUsage::NON_REPUDIATION)
+            })
+            .unwrap_or(true);
+        eku && ku
+    }
+
+    /// whether time is within validity
+    pub fn is_valid_at(&self, time: &GeneralizedTime) -> bool {
+        self.not_before <= *time && *time <= self.not_after
+    }
+
+    /// verify certificate signature with issuer public key, see [PublicKey::verify]
+    pub fn verify_issued_by(&self, issuer: &PublicKey) -> Result<()> {
+        issuer.verify(&self.signature_algo, &self.tbs_raw, &self.signature)
+    }
+}
+
+impl DerEncode for Certificate {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
+        w.write_raw(&self.raw);
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for Certificate {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        Certificate::parse(raw)
+    }
+}
+
+/// extensions of interest
+#[derive(Default)]
+struct Extensions {
+    key_usage: Option<KeyUsage>,
+    ext_key_usage: Option<Vec<Oid>>,
+    ocsp_urls: Vec<String>,
+    ocsp_nocheck: bool,
+    unrecognized_critical: Vec<Oid>,
+}
+
+impl Extensions {
+    /// parse one Extension, skipping unknown ones and recording them if critical
+    fn parse_one(&mut self, ext: &[u8]) -> Result<()> {
+        let s = SequenceRef::decode(ext)?;
+        let items = s.iter().collect::<Vec<_>>();
+        let (id, critical, value) = match &items[..] {
+            [id, value] => (id, false, value),
+            // critical BOOLEAN DEFAULT FALSE sits in between, any non zero is true
+            [id, critical, value] if critical.tag() == ASN1_BOOLEAN => {
+                (id, critical.value().iter().any(|b| *b != 0), value)
+            }
+            _ => {
+                error!("Provided extension contains {} items", items.len());
+                return Err(OcspError::Asn1MismatchError("Extension"));
+            }
+        };
+        if id.tag() != ASN1_OID || value.tag() != ASN1_OCTET {
+            return Err(OcspError::Asn1MismatchError("Extension"));
+        }
+
+        let field = asn1_field(ext, value.raw(), "extnValue");
+        let val = value.value();
+        match id.value() {
+            v if v == CERT_EXT_KEY_USAGE_HEX => {
+                trace!("Found key usage extension");
+                self.key_usage = Some(KeyUsage::parse(val).map_err(field)?);
+            }
+            v if v == CERT_EXT_EXT_KEY_USAGE_HEX => {
+                trace!("Found extended key usage extension");
+                let list = SequenceRef::decode(val).map_err(field)?;
+                let list = list
+                    .iter()
+                    .map(|o| Oid::parse_raw(o.raw()))
+                    .collect::<Result<Vec<_>>>()
+                    .map_err(field)?;
+                self.ext_key_usage = Some(list);
+            }
+            v if v == CERT_EXT_AIA_HEX => {
+                trace!("Found authority information access extension");
+                self.ocsp_urls = parse_aia_ocsp(val).map_err(field)?;
+            }
+            v if v == CERT_EXT_OCSP_NOCHECK_HEX => {
+                trace!("Found ocsp nocheck extension");
+                self.ocsp_nocheck = true;
+            }
+            v if critical => {
+                trace!(
+                    "Unrecognized critical certificate extension {}",
+                    hex::encode(v)
+                );
+                self.unrecognized_critical.push(Oid::parse_raw(id.raw())?);
+            }
+            v => {
+                trace!("Skipping certificate extension {}", hex::encode(v));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// OCSP URLs of AuthorityInfoAccessSyntax, other access methods and locations are skipped
+fn parse_aia_ocsp(aia: &[u8]) -> Result<Vec<String>> {
+    let list = SequenceRef::decode(aia)?;
+    let mut urls = vec![];
+    for ad in list.iter() {
+        let ad = SequenceRef::decode(ad.raw())?;
+        let mut items = ad.iter();
+        let (method, location) = match (items.next(), items.next(), items.next()) {
+            (Some(m), Some(l), None) if m.tag() == ASN1_OID => (m, l),
+            _ => return Err(OcspError::Asn1MismatchError("AccessDescription")),
+        };
+        if method.value() == AD_OCSP_HEX && location.tag() == ASN1_GENERAL_NAME_URI {
+            let url = std::str::from_utf8(location.value())?;
+            trace!("Found ocsp url {}", url);
+            urls.push(url.to_string());
+        }
+    }
+    Ok(urls)
+}
+
+/// notBefore and notAfter of Validity
+fn parse_validity(validity: &[u8]) -> Result<(GeneralizedTime, GeneralizedTime)> {
+    let s = SequenceRef::decode(validity)?;
+    let mut items = s.iter();
+    match (items.next(), items.next(), items.next()) {
+        (Some(b), Some(a), None) => {
+            let before = parse_time(b.raw()).map_err(asn1_field(validity, b.raw(), "notBefore"))?;
+            let after = parse_time(a.raw()).map_err(asn1_field(validity, a.raw(), "notAfter"))?;
+            Ok((before, after))
+        }
+        _ => Err(OcspError::Asn1LengthError("Validity")),
+    }
+}
+
+/// UTCTime or GeneralizedTime
+/// UTCTime years below 50 are 20YY, as RFC 5280 4.1.2.5.1
+fn parse_time(time: &[u8]) -> Result<GeneralizedTime> {
+    let obj = DerObject::decode(time).map_err(OcspError::Asn1DecodingError)?;
+    match obj.tag() {
+        ASN1_GENERALIZED_TIME => GeneralizedTime::parse(time),
+        ASN1_UTC_TIME => {
+            let val = obj.value();
+            if val.len() != 13 || val[12] != b'Z' || !val[..12].iter().all(u8::is_ascii_digit) {
+                error!("UTC time {:?} is not in YYMMDDHHMMSSZ format", val);
+                return Err(OcspError::Asn1MismatchError("UTCTime"));
+            }
+            // all digits, safe to convert
+            let num = |v: &[u8]| v.iter().fold(0u32, |acc, d| acc * 10 + (d - b'0') as u32);
+            let year = match num(&val[0..2]) as i32 {
+                y if y >= 50 => 1900 + y,
+                y => 2000 + y,
+            };
+            GeneralizedTime::new(
+                year,
+                num(&val[2..4]),
+                num(&val[4..6]),
+                num(&val[6..8]),
+                num(&val[8..10]),
+                num(&val[10..12]),
+            )
+        }
+        t => {
+            error!("Provided time tag is {}, expecting 0x17 or 0x18", t);
+            Err(OcspError::Asn1MismatchError("Time"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common::asn1::{
+        asn1_tbs_certificate, CertIdHash, GeneralizedTime, Oid, SequenceRef, SerialNumber,
+    };
+    use crate::common::der::{DerDecode, DerEncode, DerWriter};
+    use crate::err::OcspError;
+    use crate::oid::KP_OCSP_SIGNING_DOT;
+    use crate::response::ResponderType;
+    use crate::verify::test::{CA_CERT, LEAF_CERT};
+
+    use super::{parse_time, Certificate, KeyUsage};
+
+    /// delegated responder issued by [CA_CERT] with serial 0xff01  
+    /// critical key usage, OCSPSigning, two ocsp urls and a caIssuers url, ocsp nocheck
+    const RESP_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIICPDCCAeOgAwIBAgIDAP8BMAoGCCqGSM49BAMCMBIxEDAOBgNVBAMMB1Rlc3Qg
+Q0EwHhcNMjYxMDE4MDczMDUxWhcNMzYxMDE1MDczMDUxWjA4MQswCQYDVQQGEwJB
+VTEQMA4GA1UECgwHRXhhbXBsZTEXMBUGA1UEAwwOT0NTUCBSZXNwb25kZXIwWTAT
+BgcqhkjOPQIBBggqhkjOPQMBBwNCAARql448m3ZncW1LiKY9gi4ApImXzNi9wQUu
+Oo/orp1LuIm6SsKEhIiNyqgkyrQ/NNsM2LMnsYA6hgMELVWsD9Zdo4IBADCB/TAO
+BgNVHQ8BAf8EBAMCBsAwEwYDVR0lBAwwCgYIKwYBBQUHAwkwgYQGCCsGAQUFBwEB
+BHgwdjAjBggrBgEFBQcwAYYXaHR0cDovL29jc3AuZXhhbXBsZS5jb20wKAYIKwYB
+BQUHMAKGHGh0dHA6Ly9jYS5leGFtcGxlLmNvbS9jYS5jcnQwJQYIKwYBBQUHMAGG
+GWh0dHA6Ly9vY3NwMi5leGFtcGxlLmNvbS8wDwYJKwYBBQUHMAEFBAIFADAdBgNV
+HQ4EFgQUnnhTfOiRL1bl3Bv1LlAIKO1Jm+UwHwYDVR0jBBgwFoAUDDFrw2+/YM5h
+COu9/9JhRzeuDg0wCgYIKoZIzj0EAwIDRwAwRAIgGdWpFt57hQd217+OsefSLhvA
+15Ijcf+yuTbeKWe/vaECIDB9D7Ji1qwMdlpxID3mYCTAae9n9T/cQ92dnbHcufSH
+-----END CERTIFICATE-----";
+
+    fn der(pem: &str) -> Vec<u8> {
+        pem_rfc7468::decode_vec(pem.as_bytes()).unwrap().1
+    }
+
+    #[test]
+    fn certificate_responder() {
+        let cert = Certificate::from_pem(RESP_CERT).unwrap();
+        assert_eq!(SerialNumber::from_hex("ff01").unwrap(), cert.serial_num);
+        assert_eq!("CN=Test CA", cert.issuer.to_string());
+        assert_eq!("CN=OCSP Responder,O=Example,C=AU", cert.subject.to_string());
+        assert_eq!(
+            GeneralizedTime::new(2026, 10, 18, 7, 30, 51).unwrap(),
+            cert.not_before
+        );
+        assert_eq!(
+            GeneralizedTime::new(2036, 10, 15, 7, 30, 51).unwrap(),
+            cert.not_after
+        );
+        assert_eq!(
+            Some(KeyUsage(
+                KeyUsage::DIGITAL_SIGNATURE | KeyUsage::NON_REPUDIATION
+            )),
+            cert.key_usage
+        );
+        let eku = cert.ext_key_usage.as_ref().unwrap();
+        assert_eq!(1, eku.len());
+        assert_eq!(KP_OCSP_SIGNING_DOT, eku[0].to_dot());
+        // caIssuers skipped
+        assert_eq!(
+            vec!["http://ocsp.example.com", "http://ocsp2.example.com/"],
+            cert.ocsp_urls
+        );
+        assert!(cert.ocsp_nocheck);
+        assert!(cert.is_ocsp_signer());
+        assert_eq!("1.2.840.10045.4.3.2", cert.signature_algo.oid.to_dot());
+
+        let raw = der(RESP_CERT);
+        assert_eq!(raw, cert.to_der().unwrap());
+        assert_eq!(raw, DerEncode::to_der(&cert).unwrap());
+        assert_eq!(cert, Certificate::from_der(&raw).unwrap());
+        assert_eq!(&raw[4..4 + cert.tbs_raw.len()], &cert.tbs_raw[..]);
+    }
+
+    #[test]
+    fn certificate_ca_and_leaf() {
+        let ca = Certificate::from_pem(CA_CERT).unwrap();
+        assert_eq!(ca.issuer, ca.subject);
+        // GeneralizedTime from 2050
+        assert_eq!(
+            GeneralizedTime::new(2126, 9, 24, 7, 13, 55).unwrap(),
+            ca.not_after
+        );
+        assert_eq!(None, ca.key_usage);
+        assert_eq!(None, ca.ext_key_usage);
+        assert!(ca.ocsp_urls.is_empty());
+        assert!(!ca.ocsp_nocheck);
+        assert!(!ca.is_ocsp_signer());
+
+        // subject key identifier of CA
+        let rid = ca.responder_id_by_key();
+        assert!(matches!(rid.id_by, ResponderType::BY_KEY_HASH));
+        assert_eq!(
+            "0c316bc36fbf60ce6108ebbdffd2614737ae0e0d",
+            hex::encode(&rid.id)
+        );
+        let rid = ca.responder_id_by_name().unwrap();
+        assert_eq!(ca.subject, rid.name().unwrap());
+
+        let leaf = Certificate::from_pem(LEAF_CERT).unwrap();
+        assert_eq!(SerialNumber::from_hex("1234").unwrap(), leaf.serial_num);
+        assert_eq!(ca.subject, leaf.issuer);
+        for hash in CertIdHash::ALL.iter() {
+            assert_eq!(
+                crate::common::asn1::CertId::from_certificates(&ca.raw, &leaf.raw, *hash).unwrap(),
+                leaf.cert_id(&ca, *hash).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn certificate_validity() {
+        let cert = Certificate::from_pem(RESP_CERT).unwrap();
+        assert!(cert.is_valid_at(&cert.not_before));
+        assert!(cert.is_valid_at(&cert.not_after));
+        assert!(cert.is_valid_at(&GeneralizedTime::new(2030, 1, 1, 0, 0, 0).unwrap()));
+        assert!(!cert.is_valid_at(&GeneralizedTime::new(2026, 10, 18, 7, 30, 50).unwrap()));
+        assert!(!cert.is_valid_at(&GeneralizedTime::new(2036, 10, 15, 7, 30, 52).unwrap()));
+    }
+
+    #[test]
+    fn certificate_time() {
+        // 49 and 50 are the UTCTime century boundary
+        let utc = hex::decode("170d3439313233313233353935395a").unwrap();
+        assert_eq!(
+            GeneralizedTime::new(2049, 12, 31, 23, 59, 59).unwrap(),
+            parse_time(&utc).unwrap()
+        );
+        let utc = hex::decode("170d3530303130313030303030305a").unwrap();
+        assert_eq!(
+            GeneralizedTime::new(1950, 1, 1, 0, 0, 0).unwrap(),
+            parse_time(&utc).unwrap()
+        );
+        let gt = hex::decode("180f32303530303130313030303030305a").unwrap();
+        assert_eq!(
+            GeneralizedTime::new(2050, 1, 1, 0, 0, 0).unwrap(),
+            parse_time(&gt).unwrap()
+        );
+
+        // without seconds, with offset, or not a time
+        for t in &[
+            "170b343931323331323335395a",
+            "17113439313233313233353935392b30313030",
+            "040d3439313233313233353935395a",
+        ] {
+            assert!(parse_time(&hex::decode(t).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn certificate_key_usage() {
+        // keyCertSign and cRLSign
+        let ku = KeyUsage::parse(&[0x03, 0x02, 0x01, 0x06]).unwrap();
+        assert_eq!(KeyUsage(KeyUsage::KEY_CERT_SIGN | KeyUsage::CRL_SIGN), ku);
+        assert!(ku.contains(KeyUsage::CRL_SIGN));
+        assert!(!ku.contains(KeyUsage::CRL_SIGN | KeyUsage::DIGITAL_SIGNATURE));
+        // decipherOnly in second byte
+        let ku = KeyUsage::parse(&[0x03, 0x03, 0x07, 0x80, 0x80]).unwrap();
+        assert_eq!(
+            KeyUsage(KeyUsage::DIGITAL_SIGNATURE | KeyUsage::DECIPHER_ONLY),
+            ku
+        );
+        assert!(KeyUsage::parse(&[0x04, 0x02, 0x01, 0x06]).is_err());
+        assert!(KeyUsage::parse(&[0x03, 0x00]).is_err());
+
+        // key usage without digitalSignature nor nonRepudiation
+        let mut cert = Certificate::from_pem(RESP_CERT).unwrap();
+        cert.key_usage = Some(KeyUsage(KeyUsage::KEY_CERT_SIGN));
+        assert!(!cert.is_ocsp_signer());
+        cert.key_usage = None;
+        assert!(cert.is_ocsp_signer());
+        cert.ext_key_usage = Some(vec![]);
+        assert!(!cert.is_ocsp_signer());
+    }
+
+    #[test]
+    fn certificate_malformed() {
+        let raw = der(RESP_CERT);
+
+        // notBefore tag changed to OCTET STRING
+        let mut bad = raw.clone();
+        let pos = 52;
+        assert_eq!(0x17, bad[pos]);
+        bad[pos] = 0x04;
+        let err = Certificate::parse(&bad).unwrap_err();
+        assert_eq!(Some("tbsCertificate.validity.notBefore"), err.path());
+        assert_eq!(Some(pos), err.offset());
+        assert!(matches!(err.root(), OcspError::Asn1MismatchError("Time")));
+
+        // key usage BIT STRING changed to OCTET STRING
+        let mut bad = raw.clone();
+        let ku = raw
+            .windows(5)
+            .position(|w| w == [0x55, 0x1d, 0x0f, 0x01, 0x01])
+            .unwrap();
+        assert_eq!(0x03, bad[ku + 8]);
+        bad[ku + 8] = 0x04;
+        let err = Certificate::parse(&bad).unwrap_err();
+        assert_eq!(Some("tbsCertificate.extensions[0].extnValue"), err.path());
+        assert!(matches!(
+            err.root(),
+            OcspError::Asn1MismatchError("KeyUsage")
+        ));
+
+        // signature without unused bits byte
+        let mut bad = raw.clone();
+        let sign = raw.len() - 73;
+        assert_eq!([0x03, 0x47, 0x00], bad[sign..sign + 3]);
+        bad[sign + 2] = 0x01;
+        let err = Certificate::parse(&bad).unwrap_err();
+        assert_eq!(Some("signatureValue"), err.path());
+        assert_eq!(Some(sign), err.offset());
+
+        // not a certificate
+        assert!(Certificate::parse(&raw[4..]).is_err());
+        assert!(
+            Certificate::from_pem("-----BEGIN PUBLIC KEY-----\n-----END PUBLIC KEY-----").is_err()
+        );
+    }
+
+    #[test]
+    fn certificate_responder_id_unsorted_subject() {
+        let raw = der(RESP_CERT);
+        let subject = asn1_tbs_certificate(&raw).unwrap()[4].raw().to_vec();
+
+        // CN=b+CN=a, SET OF not in DER order
+        let unsorted = hex::decode("301631143008060355040313016230080603550403130161").unwrap();
+        let cert = SequenceRef::decode(&raw).unwrap();
+        let mut cert = cert.iter();
+        let tbs = cert.next().unwrap();
+        let tbs = SequenceRef::decode(tbs.raw()).unwrap();
+        let tbs = DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                tbs.iter().for_each(|f| match f.raw() {
+                    f if f == &subject[..] => w.write_raw(&unsorted),
+                    f => w.write_raw(f),
+                });
+                Ok(())
+            })
+        })
+        .unwrap();
+        let rest = cert.map(|f| f.raw()).collect::<Vec<_>>();
+        let bad = DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                w.write_raw(&tbs);
+                rest.iter().for_each(|f| w.write_raw(f));
+                Ok(())
+            })
+        })
+        .unwrap();
+
+        let cert = Certificate::parse(&bad).unwrap();
+        let rid = cert.responder_id_by_name().unwrap();
+        assert_eq!(unsorted, rid.id);
+        assert_ne!(unsorted, cert.subject.to_der().unwrap());
+        assert_eq!(cert.subject, rid.name().unwrap());
+    }
+
+    #[test]
+    fn certificate_unrecognized_critical() {
+        let raw = der(RESP_CERT);
+        assert!(Certificate::parse(&raw)
+            .unwrap()
+            .unrecognized_critical
+            .is_empty());
+
+        // critical key usage turned into unknown id-ce 127
+        let mut bad = raw.clone();
+        let ku = raw
+            .windows(5)
+            .position(|w| w == [0x55, 0x1d, 0x0f, 0x01, 0x01])
+            .unwrap();
+        assert_eq!(0xff, bad[ku + 5]);
+        bad[ku + 2] = 0x7f;
+        let cert = Certificate::parse(&bad).unwrap();
+        assert_eq!(None, cert.key_usage);
+        assert_eq!(
+            vec![Oid::new_from_dot("2.5.29.127").unwrap()],
+            cert.unrecognized_critical
+        );
+        assert!(!cert.is_ocsp_signer());
+
+        // not critical, skipped
+        bad[ku + 5] = 0x00;
+        let cert = Certificate::parse(&bad).unwrap();
+        assert!(cert.unrecognized_critical.is_empty());
+        assert!(cert.is_ocsp_signer());
+    }
+
+    #[cfg(feature = "ecdsa")]
+    #[test]
+    fn certificate_verify_issued_by() {
+        let ca = Certificate::from_pem(CA_CERT).unwrap();
+        let cert = Certificate::from_pem(RESP_CERT).unwrap();
+        cert.verify_issued_by(&ca.public_key).unwrap();
+        ca.verify_issued_by(&ca.public_key).unwrap();
+        let err = cert.verify_issued_by(&cert.public_key).unwrap_err();
+        assert!(matches!(err, OcspError::OcspBadSignature));
+    }
+}
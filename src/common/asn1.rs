@@ -1,15 +1,23 @@
 //! Common components in ASN.1  
 //! For ASN.1 universal tags list, see [here](https://www.obj-sys.com/asn1tutorial/node124.html)
 
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
 use asn1_der::{
     typed::{DerDecodable, Sequence},
     DerObject,
 };
-use chrono::{Datelike, Timelike};
+use chrono::{DateTime, Datelike, Timelike, Utc};
 use tracing::{error, trace};
 
+use crate::common::der::{DerDecode, DerEncode, DerWriter};
 use crate::err::OcspError;
-use crate::oid::{b2i_oid, d2i_oid, i2b_oid};
+use crate::oid::*;
 
 /// Aliasing `Vec<u8>` with Bytes
 pub type Bytes = Vec<u8>;
@@ -20,6 +28,10 @@ pub(crate) const ASN1_EXPLICIT_0: u8 = 0xa0;
 pub(crate) const ASN1_EXPLICIT_1: u8 = 0xa1;
 /// ASN.1 explicit tag 1
 pub(crate) const ASN1_EXPLICIT_2: u8 = 0xa2;
+/// ASN.1 explicit tag 3
+pub(crate) const ASN1_EXPLICIT_3: u8 = 0xa3;
+/// ASN.1 explicit tag 4
+pub(crate) const ASN1_EXPLICIT_4: u8 = 0xa4;
 /// ASN.1 null
 pub(crate) const ASN1_NULL: u8 = 0x05;
 /// ASN.1 oid
@@ -34,12 +46,16 @@ pub(crate) const ASN1_OCTET: u8 = 0x04;
 pub(crate) const ASN1_INTEGER: u8 = 0x02;
 /// ASN.1 ia5string
 pub(crate) const ASN1_IA5STRING: u8 = 0x16;
+/// ASN.1 utc time
+pub(crate) const ASN1_UTC_TIME: u8 = 0x17;
 /// ASN.1 generalized time
 pub(crate) const ASN1_GENERALIZED_TIME: u8 = 0x18;
 /// ASN.1 enumerated
 pub(crate) const ASN1_ENUMERATED: u8 = 0x0a;
 /// ASN.1 bit string
 pub(crate) const ASN1_BIT_STRING: u8 = 0x03;
+/// ASN.1 boolean
+pub(crate) const ASN1_BOOLEAN: u8 = 0x01;
 
 /// Allowing byte data to be converted to [Sequence](https://docs.rs/asn1_der/0.7.2/asn1_der/typed/struct.Sequence.html)
 pub trait TryIntoSequence<'d> {
@@ -86,41 +102,285 @@ pub(crate) fn asn1_encode_length(len: usize) -> Result<Bytes, OcspError> {
             }
             let l = 0x80 + v.len() as u8;
             let l = vec![l];
-            Ok(l.into_iter().chain(v.into_iter()).collect())
+            Ok(l.into_iter().chain(v).collect())
+        }
+    }
+}
+
+/// Offset of part in input  
+/// part is expected to be a subslice of input, 0 otherwise
+pub(crate) fn asn1_offset(input: &[u8], part: &[u8]) -> usize {
+    let off = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    if off <= input.len() {
+        off
+    } else {
+        0
+    }
+}
+
+/// Attaching field name and position to a decoding error, see [OcspError::path]  
+/// part is the field TLV within input
+pub(crate) fn asn1_field(
+    input: &[u8],
+    part: &[u8],
+    field: &'static str,
+) -> impl Fn(OcspError) -> OcspError + Copy {
+    let offset = asn1_offset(input, part);
+    move |e| e.at(field, offset)
+}
+
+/// Splitting the value of a constructed TLV into its child TLVs  
+/// Used where the constructed type is implicitly tagged and cannot be loaded as Sequence
+pub(crate) fn asn1_decode_children(value: &[u8]) -> Result<Vec<DerObject<'_>>, OcspError> {
+    let mut r = vec![];
+    let mut pos = 0;
+    while pos < value.len() {
+        let obj = DerObject::decode_at(value, pos).map_err(OcspError::Asn1DecodingError)?;
+        pos += obj.raw().len();
+        r.push(obj);
+    }
+    Ok(r)
+}
+
+/// Child TLVs of a SEQUENCE borrowed from raw bytes  
+/// children are checked once when decoding, counting and iterating never allocate,  
+/// unlike [Sequence] which allocates an error at the end of every walk
+#[derive(Clone, Copy)]
+pub(crate) struct SequenceRef<'a> {
+    value: &'a [u8],
+    len: usize,
+}
+
+impl<'a> SequenceRef<'a> {
+    /// load a SEQUENCE, data after the TLV is ignored
+    pub(crate) fn decode(raw: &'a [u8]) -> Result<Self, OcspError> {
+        let obj = DerObject::decode(raw).map_err(OcspError::Asn1DecodingError)?;
+        if obj.tag() != ASN1_SEQUENCE {
+            error!("Provided tag is {}, expecting SEQUENCE", obj.tag());
+            return Err(OcspError::Asn1MismatchError("SEQUENCE"));
+        }
+        SequenceRef::from_value(obj.value())
+    }
+
+    /// load children from the value of a constructed TLV
+    pub(crate) fn from_value(value: &'a [u8]) -> Result<Self, OcspError> {
+        let mut pos = 0;
+        let mut len = 0;
+        while pos < value.len() {
+            let obj = DerObject::decode_at(value, pos).map_err(OcspError::Asn1DecodingError)?;
+            pos += obj.raw().len();
+            len += 1;
         }
+        Ok(SequenceRef { value, len })
+    }
+
+    /// number of children
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// iterate children in order
+    pub(crate) fn iter(&self) -> impl Iterator<Item = DerObject<'a>> {
+        let value = self.value;
+        let mut pos = 0;
+        std::iter::from_fn(move || {
+            if pos >= value.len() {
+                return None;
+            }
+            // children were checked in from_value
+            let obj = DerObject::decode_at(value, pos).ok()?;
+            pos += obj.raw().len();
+            Some(obj)
+        })
     }
 }
 
-/// Packing octet into ASN.1 DER
-pub(crate) fn asn1_encode_octet(data: &[u8]) -> Result<Bytes, OcspError> {
-    let mut tlv = vec![ASN1_OCTET];
-    let len = asn1_encode_length(data.len())?;
-    tlv.extend(len);
-    tlv.extend(data);
-    Ok(tlv)
+/// Fields of a DER X.509 certificate's tbsCertificate, optional version skipped  
+/// serialNumber, signature, issuer, validity, subject, subjectPublicKeyInfo, then optional fields
+pub(crate) fn asn1_tbs_certificate(cert: &[u8]) -> Result<Vec<DerObject<'_>>, OcspError> {
+    trace!("Extracting tbs certificate");
+    let s = SequenceRef::decode(cert)?;
+    let tbs = s.iter().next().ok_or(OcspError::Asn1Truncated)?;
+    let field = asn1_field(cert, tbs.raw(), "tbsCertificate");
+    let items = SequenceRef::decode(tbs.raw()).map_err(field)?;
+    let mut items = items.iter().peekable();
+    if items.peek().map(|i| i.tag()) == Some(ASN1_EXPLICIT_0) {
+        items.next();
+    }
+    let items = items.collect::<Vec<_>>();
+    if items.len() < 6 {
+        error!(
+            "Provided tbs certificate contains {} items after version, expecting at least 6",
+            items.len()
+        );
+        return Err(field(OcspError::Asn1LengthError("TBSCertificate")));
+    }
+    Ok(items)
 }
 
-/// Packing integer into ASN.1 DER
-pub(crate) fn asn1_encode_integer(data: &[u8]) -> Result<Bytes, OcspError> {
-    let mut tlv = vec![ASN1_INTEGER];
-    let len = asn1_encode_length(data.len())?;
-    tlv.extend(len);
-    tlv.extend(data);
-    Ok(tlv)
+/// Subject Name in DER and subjectPublicKey without unused bits byte of a DER X.509 certificate  
+/// what [CertId] issuer name and key hashes are computed over
+pub(crate) fn asn1_issuer_name_key(cert: &[u8]) -> Result<(&[u8], &[u8]), OcspError> {
+    let tbs = asn1_tbs_certificate(cert)?;
+    let name = tbs[4].raw();
+    let spki = tbs[5].raw();
+    let field = asn1_field(cert, spki, "tbsCertificate.subjectPublicKeyInfo");
+    let s = SequenceRef::decode(spki).map_err(field)?;
+    match s.iter().nth(1) {
+        // leading byte is the number of unused bits
+        Some(k) if k.tag() == ASN1_BIT_STRING && k.value().first() == Some(&0x00) => {
+            Ok((name, &k.value()[1..]))
+        }
+        _ => {
+            error!("Provided subject public key is not a BIT STRING of whole bytes");
+            let e = OcspError::Asn1MismatchError("SubjectPublicKeyInfo subjectPublicKey");
+            Err(field(e))
+        }
+    }
 }
 
-/// Packing bit string into ASN.1 DER
-pub(crate) fn asn1_encode_bit_string(data: &[u8]) -> Result<Bytes, OcspError> {
-    let mut tlv = vec![ASN1_BIT_STRING];
-    let len = asn1_encode_length(data.len())?;
-    tlv.extend(len);
-    tlv.extend(data);
-    Ok(tlv)
+/// Decoding strictness  
+/// see [asn1_validate_der] for strict rules
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum DecodeMode {
+    /// accept what asn1_der accepts, data after outermost TLV is ignored
+    #[default]
+    Lenient,
+    /// accept DER only
+    Strict,
+}
+
+impl DecodeMode {
+    /// whether mode is strict
+    pub fn is_strict(&self) -> bool {
+        *self == DecodeMode::Strict
+    }
+}
+
+/// Validating raw bytes are exactly one TLV in DER  
+/// - no trailing bytes after the TLV  
+/// - no indefinite length  
+/// - minimal length encoding  
+/// - BOOLEAN is either 0x00 or 0xff  
+///
+/// constructed values are checked at any depth, OCTET STRING content is not
+pub(crate) fn asn1_validate_der(raw: &[u8]) -> Result<(), OcspError> {
+    let end = asn1_validate_tlv(raw)?;
+    if end != raw.len() {
+        error!("Found {} bytes after TLV", raw.len() - end);
+        return Err(OcspError::Asn1TrailingData(raw.len() - end));
+    }
+    Ok(())
+}
+
+/// validate one TLV at the start of raw, return position after it  
+/// nesting is walked with an explicit stack, untrusted input cannot overflow the call stack
+fn asn1_validate_tlv(raw: &[u8]) -> Result<usize, OcspError> {
+    // end of every enclosing constructed value
+    let mut stack = vec![];
+    let mut limit = raw.len();
+    let mut pos = 0;
+    loop {
+        let (tag, content, end) = asn1_validate_header(&raw[..limit], pos)?;
+        if tag & 0x20 != 0 {
+            // constructed, children must fill content exactly
+            stack.push(limit);
+            limit = end;
+            pos = content;
+        } else {
+            if tag == ASN1_BOOLEAN {
+                if end - content != 1 {
+                    return Err(OcspError::Asn1LengthError("BOOLEAN"));
+                }
+                if raw[content] != 0x00 && raw[content] != 0xff {
+                    error!("Found non canonical boolean {:02x}", raw[content]);
+                    return Err(OcspError::Asn1NonCanonicalBoolean(raw[content]));
+                }
+            }
+            pos = end;
+        }
+
+        // leave every constructed value whose children are complete
+        while pos == limit {
+            match stack.pop() {
+                Some(outer) => limit = outer,
+                None => break,
+            }
+        }
+        if stack.is_empty() {
+            return Ok(pos);
+        }
+    }
+}
+
+/// validate tag and length of the TLV at pos, return tag, content start and end
+fn asn1_validate_header(raw: &[u8], pos: usize) -> Result<(u8, usize, usize), OcspError> {
+    let byte = |i: usize| raw.get(i).copied().ok_or(OcspError::Asn1Truncated);
+
+    let tag = byte(pos)?;
+    let mut i = pos + 1;
+    // high tag number form
+    if tag & 0x1f == 0x1f {
+        while byte(i)? & 0x80 != 0 {
+            i += 1;
+        }
+        i += 1;
+    }
+
+    let first = byte(i)?;
+    i += 1;
+    let len = match first {
+        0x80 => {
+            error!("Found indefinite length at {}", pos);
+            return Err(OcspError::Asn1IndefiniteLength);
+        }
+        l if l < 0x80 => l as usize,
+        l => {
+            let n = (l & 0x7f) as usize;
+            if n > std::mem::size_of::<usize>() {
+                return Err(OcspError::Asn1LengthOverflow(n));
+            }
+            let mut len = 0usize;
+            for j in 0..n {
+                len = (len << 8) | byte(i + j)? as usize;
+            }
+            // leading zero or short form length
+            if byte(i)? == 0 || len < 0x80 {
+                error!("Found non minimal length at {}", pos);
+                return Err(OcspError::Asn1NonMinimalLength);
+            }
+            i += n;
+            len
+        }
+    };
+
+    let end = i.checked_add(len).ok_or(OcspError::Asn1Truncated)?;
+    if end > raw.len() {
+        return Err(OcspError::Asn1Truncated);
+    }
+    Ok((tag, i, end))
+}
+
+/// Unpacking unsigned integer from ASN.1 DER
+pub(crate) fn asn1_decode_u32(data: &[u8]) -> Result<u32, OcspError> {
+    let obj = DerObject::decode(data).map_err(OcspError::Asn1DecodingError)?;
+    let v = obj.value();
+    if obj.tag() != ASN1_INTEGER || v.is_empty() || v[0] & 0x80 != 0 {
+        return Err(OcspError::Asn1MismatchError("INTEGER"));
+    }
+    let v = match v {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        _ => v,
+    };
+    if v.len() > 4 {
+        return Err(OcspError::Asn1LengthError("INTEGER"));
+    }
+    Ok(v.iter().fold(0u32, |acc, b| (acc << 8) | *b as u32))
 }
 
 /// Represents a ASN.1 GeneralizedTime  
-/// Only support UTC
-#[derive(Debug, Copy, Clone)]
+/// Only support UTC, with optional fractional seconds
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct GeneralizedTime {
     year: i32,
     month: u32,
@@ -128,7 +388,7 @@ pub struct GeneralizedTime {
     hour: u32,
     min: u32,
     sec: u32,
-    //millis: u32,
+    nanos: u32,
 }
 
 impl GeneralizedTime {
@@ -141,12 +401,33 @@ impl GeneralizedTime {
         min: u32,
         sec: u32,
     ) -> Result<Self, OcspError> {
+        GeneralizedTime::new_with_nanos(year, month, day, hour, min, sec, 0)
+    }
+
+    /// Create a generalized time at specified time, with fractional seconds in nanoseconds
+    pub fn new_with_nanos(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        min: u32,
+        sec: u32,
+        nanos: u32,
+    ) -> Result<Self, OcspError> {
+        // YYYY in DER
+        if !(0..=9999).contains(&year) {
+            return Err(OcspError::GenInvalidDate(year, month, day));
+        }
         // lazy check if date time is valid
         // turn it into chrono
         let dt = chrono::NaiveDate::from_ymd_opt(year, month, day)
             .ok_or(OcspError::GenInvalidDate(year, month, day))?;
+        // leap second is not allowed
+        if nanos >= 1_000_000_000 {
+            return Err(OcspError::GenInvalidTime(hour, min, sec));
+        }
         let _ = dt
-            .and_hms_opt(hour, min, sec)
+            .and_hms_nano_opt(hour, min, sec, nanos)
             .ok_or(OcspError::GenInvalidTime(hour, min, sec))?;
 
         Ok(GeneralizedTime {
@@ -156,10 +437,12 @@ impl GeneralizedTime {
             hour,
             min,
             sec,
+            nanos,
         })
     }
 
-    /// Create a generalized time **now** in UTC
+    /// Create a generalized time **now** in UTC  
+    /// fractional seconds are dropped
     pub fn now() -> Self {
         let now = chrono::offset::Utc::now();
 
@@ -170,33 +453,195 @@ impl GeneralizedTime {
             hour: now.hour(),
             min: now.minute(),
             sec: now.second(),
-            //millis: mi,
+            nanos: 0,
+        }
+    }
+
+    /// fractional seconds in nanoseconds
+    pub fn nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    /// get generalized time from raw bytes  
+    /// expecting format YYYYMMDDHHMMSS\[.f\]Z  
+    /// fraction must not have trailing zeros, as required by DER
+    pub fn parse(gt: &[u8]) -> Result<Self, OcspError> {
+        trace!("Parsing generalized time {}", hex::encode(gt));
+        let obj = DerObject::decode(gt).map_err(OcspError::Asn1DecodingError)?;
+        if obj.tag() != ASN1_GENERALIZED_TIME {
+            error!(
+                "Provided generalized time tag is {}, expecting 0x18",
+                obj.tag()
+            );
+            return Err(OcspError::Asn1MismatchError("GeneralizedTime"));
+        }
+
+        let val = obj.value();
+        let len = val.len();
+        if len < 15 || val[len - 1] != b'Z' || !val[..14].iter().all(u8::is_ascii_digit) {
+            error!("Generalized time {:?} is not in UTC format", val);
+            return Err(OcspError::Asn1MismatchError("GeneralizedTime"));
         }
+        // all digits, safe to convert
+        let num = |v: &[u8]| v.iter().fold(0u32, |acc, d| acc * 10 + (d - b'0') as u32);
+
+        let mut nanos = 0;
+        if len > 15 {
+            let frac = &val[15..len - 1];
+            if val[14] != b'.'
+                || frac.is_empty()
+                || frac.len() > 9
+                || !frac.iter().all(u8::is_ascii_digit)
+                || frac[frac.len() - 1] == b'0'
+            {
+                error!("Generalized time {:?} has invalid fractional seconds", val);
+                return Err(OcspError::Asn1MismatchError("GeneralizedTime"));
+            }
+            nanos = num(frac) * 10u32.pow(9 - frac.len() as u32);
+        }
+
+        GeneralizedTime::new_with_nanos(
+            num(&val[0..4]) as i32,
+            num(&val[4..6]),
+            num(&val[6..8]),
+            num(&val[8..10]),
+            num(&val[10..12]),
+            num(&val[12..14]),
+            nanos,
+        )
     }
 
     /// Serialize to DER encoding  
     /// see [html](https://www.obj-sys.com/asn1tutorial/node14.html)
     pub fn to_der_utc(&self) -> Result<Bytes, OcspError> {
-        let v = format!(
-            "{}{:02}{:02}{:02}{:02}{:02}Z",
+        DerWriter::encode(|w| self.write_der(w))
+    }
+
+    /// add duration, return None on overflow or year beyond 9999
+    pub fn checked_add(&self, rhs: Duration) -> Option<Self> {
+        let rhs = chrono::Duration::from_std(rhs).ok()?;
+        let dt = DateTime::<Utc>::from(*self).checked_add_signed(rhs)?;
+        GeneralizedTime::try_from(dt).ok()
+    }
+
+    /// subtract duration, return None on overflow or year before 0
+    pub fn checked_sub(&self, rhs: Duration) -> Option<Self> {
+        let rhs = chrono::Duration::from_std(rhs).ok()?;
+        let dt = DateTime::<Utc>::from(*self).checked_sub_signed(rhs)?;
+        GeneralizedTime::try_from(dt).ok()
+    }
+
+    /// duration elapsed since an earlier time, None if earlier is later than self
+    pub fn duration_since(&self, earlier: GeneralizedTime) -> Option<Duration> {
+        let d = DateTime::<Utc>::from(*self) - DateTime::<Utc>::from(earlier);
+        d.to_std().ok()
+    }
+}
+
+impl DerEncode for GeneralizedTime {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        let mut v = format!(
+            "{:04}{:02}{:02}{:02}{:02}{:02}",
             self.year, self.month, self.day, self.hour, self.min, self.sec
+        );
+        if self.nanos > 0 {
+            let frac = format!("{:09}", self.nanos);
+            v.push('.');
+            v.push_str(frac.trim_end_matches('0'));
+        }
+        v.push('Z');
+        w.write_tlv(ASN1_GENERALIZED_TIME, v.as_bytes())
+    }
+}
+
+impl<'a> DerDecode<'a> for GeneralizedTime {
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError> {
+        GeneralizedTime::parse(raw)
+    }
+}
+
+impl From<GeneralizedTime> for DateTime<Utc> {
+    fn from(gt: GeneralizedTime) -> Self {
+        // fields are validated on creation
+        let dt = chrono::NaiveDate::from_ymd_opt(gt.year, gt.month, gt.day)
+            .and_then(|d| d.and_hms_nano_opt(gt.hour, gt.min, gt.sec, gt.nanos))
+            .unwrap_or_default();
+        DateTime::from_naive_utc_and_offset(dt, Utc)
+    }
+}
+
+impl TryFrom<DateTime<Utc>> for GeneralizedTime {
+    type Error = OcspError;
+
+    fn try_from(dt: DateTime<Utc>) -> Result<Self, Self::Error> {
+        GeneralizedTime::new_with_nanos(
+            dt.year(),
+            dt.month(),
+            dt.day(),
+            dt.hour(),
+            dt.minute(),
+            dt.second(),
+            dt.nanosecond(),
         )
-        .as_bytes()
-        .to_vec();
-        let l = asn1_encode_length(v.len())?;
-        let mut t = vec![ASN1_GENERALIZED_TIME];
-        t.extend(l);
-        t.extend(v);
-        Ok(t)
+    }
+}
+
+impl From<GeneralizedTime> for SystemTime {
+    fn from(gt: GeneralizedTime) -> Self {
+        DateTime::<Utc>::from(gt).into()
+    }
+}
+
+impl TryFrom<SystemTime> for GeneralizedTime {
+    type Error = OcspError;
+
+    fn try_from(st: SystemTime) -> Result<Self, Self::Error> {
+        GeneralizedTime::try_from(DateTime::<Utc>::from(st))
+    }
+}
+
+impl Add<Duration> for GeneralizedTime {
+    type Output = GeneralizedTime;
+
+    /// # Panics
+    /// panics on overflow, see [GeneralizedTime::checked_add]
+    fn add(self, rhs: Duration) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("overflow when adding duration to generalized time")
+    }
+}
+
+impl Sub<Duration> for GeneralizedTime {
+    type Output = GeneralizedTime;
+
+    /// # Panics
+    /// panics on overflow, see [GeneralizedTime::checked_sub]
+    fn sub(self, rhs: Duration) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting duration from generalized time")
+    }
+}
+
+impl AddAssign<Duration> for GeneralizedTime {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Duration> for GeneralizedTime {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
     }
 }
 
 /// Oid represents a 0x06 OID type in ASN.1  
+/// any object identifier is allowed, well-known ones are listed in [crate::oid]  
 /// In OpenSSL ocsp request, OID is followed by NULL 0x05  
 /// REVIEW 0x05
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Oid {
-    pub(crate) index: usize,
+    /// oid value in DER, without tag and length
+    pub(crate) id: Bytes,
 }
 
 impl Oid {
@@ -225,69 +670,614 @@ impl Oid {
             return Err(OcspError::Asn1MismatchError("OID"));
         }
 
-        let u = match b2i_oid(id.value()) {
-            None => return Err(OcspError::Asn1OidUnknown),
-            Some(u) => u,
-        };
+        let u = Oid::from_bytes(id.value())?;
+        trace!("Oid {} successfully decoded to {}", oid_hex, u);
+        Ok(u)
+    }
+
+    /// get oid from raw bytes
+    /// - without sequence header
+    /// - without tailing NULL
+    pub fn parse_raw(oid: &[u8]) -> Result<Self, OcspError> {
+        trace!("Parsing oid without sequence {}", hex::encode(oid));
+        let id = DerObject::decode(oid).map_err(OcspError::Asn1DecodingError)?;
+        if id.tag() != ASN1_OID {
+            error!("Provided oid tag is {}, expecting 0x06", id.tag());
+            return Err(OcspError::Asn1MismatchError("OID"));
+        }
+
+        let u = Oid::from_bytes(id.value())?;
+        trace!("Oid successfully decoded to {}", u);
+        Ok(u)
+    }
 
-        trace!("Oid {} successfully decoded to internal {}", oid_hex, u);
-        Ok(Oid { index: u })
+    /// get oid from its value bytes
+    /// - without tag and length
+    pub fn from_bytes(oid: &[u8]) -> Result<Self, OcspError> {
+        if !is_oid(oid) {
+            error!("Provided oid {} is not valid", hex::encode(oid));
+            return Err(OcspError::Asn1OidInvalid);
+        }
+        Ok(Oid { id: oid.to_vec() })
     }
 
     /// return new oid from dot notation
     pub fn new_from_dot(name_dot_notation: &str) -> Result<Self, OcspError> {
         // ignoring logging here, trace if logged in d2i_oid
-        d2i_oid(name_dot_notation).ok_or(OcspError::Asn1OidUnknown)
+        d2i_oid(name_dot_notation).ok_or(OcspError::Asn1OidInvalid)
+    }
+
+    /// oid value bytes, without tag and length
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.id
+    }
+
+    /// oid in dot notation  
+    /// value bytes in hex if an arc is too wide to convert, see [crate::oid]
+    pub fn to_dot(&self) -> String {
+        b2d_oid(&self.id).unwrap_or_else(|| hex::encode(&self.id))
+    }
+
+    /// internal id if oid is well-known, see const in [crate::oid]
+    pub fn index(&self) -> Option<usize> {
+        b2i_oid(&self.id)
+    }
+
+    /// asn1 notation if oid is well-known
+    pub fn name(&self) -> Option<&'static str> {
+        self.index().map(|i| OCSP_OID_NAME_LIST[i])
     }
 
     /// encode to ASN.1 DER with tailing NULL
     pub fn to_der_with_null(&self) -> Result<Bytes, OcspError> {
-        trace!("Encoding oid index {}", self.index);
-        let val_oid = i2b_oid(self)?;
-        let len_oid = asn1_encode_length(val_oid.len())?;
-        let mut tlv_oid = vec![ASN1_OID];
-        tlv_oid.extend(len_oid);
-        tlv_oid.extend(val_oid);
-        tlv_oid.extend(ASN1_OID_PADDING);
-        let len_seq = asn1_encode_length(tlv_oid.len())?;
-        let mut tlv_seq_oid = vec![ASN1_SEQUENCE];
-        tlv_seq_oid.extend(len_seq);
-        tlv_seq_oid.extend(tlv_oid);
-
-        trace!("Internal oid {} successfully encoded", self.index);
-        Ok(tlv_seq_oid)
+        trace!("Encoding oid {}", self);
+        DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                self.write_der(w)?;
+                w.write_raw(&ASN1_OID_PADDING);
+                Ok(())
+            })
+        })
     }
 
     /// encode to ASN.1 DER
     /// - without sequence header
     /// - without tailing NULL
     pub fn to_der_raw(&self) -> Result<Bytes, OcspError> {
-        trace!("Encoding oid without sequence index {}", self.index);
-        let val_oid = i2b_oid(self)?;
-        let len_oid = asn1_encode_length(val_oid.len())?;
-        let mut tlv_oid = vec![ASN1_OID];
-        tlv_oid.extend(len_oid);
-        tlv_oid.extend(val_oid);
-
-        trace!(
-            "Internal oid {} successfully encoded without padding",
-            self.index
-        );
-        Ok(tlv_oid)
+        trace!("Encoding oid without sequence {}", self);
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+/// plain OID TLV, see [Oid::to_der_raw] and [Oid::parse_raw]
+impl DerEncode for Oid {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        w.write_tlv(ASN1_OID, i2b_oid(self)?)
+    }
+}
+
+impl<'a> DerDecode<'a> for Oid {
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError> {
+        Oid::parse_raw(raw)
+    }
+}
+
+impl fmt::Display for Oid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_dot())
+    }
+}
+
+/// AlgorithmIdentifier parameters, see RFC 5280 4.1.1.2
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AlgorithmParameters {
+    /// parameters field is omitted, eg. ECDSA and Ed25519
+    Absent,
+    /// parameters field is NULL, eg. RSA PKCS#1 v1.5 and hash algorithms
+    Null,
+    /// RSASSA-PSS-params, see RFC 4055 3.1
+    RsaPss(Box<RsaPssParameters>),
+    /// any other parameters, in DER
+    Other(Bytes),
+}
+
+/// RFC 4055 RSASSA-PSS-params
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RsaPssParameters {
+    /// hashAlgorithm, DEFAULT sha1
+    pub hash_algo: AlgorithmIdentifier,
+    /// hash algorithm of maskGenAlgorithm, which is always MGF1, DEFAULT sha1
+    pub mgf_hash_algo: AlgorithmIdentifier,
+    /// saltLength, DEFAULT 20
+    pub salt_len: u32,
+    /// trailerField, DEFAULT 1
+    pub trailer_field: u32,
+}
+
+impl RsaPssParameters {
+    /// return new RSASSA-PSS parameters  
+    /// MGF1 uses the same hash, salt length is the hash length
+    pub fn new(hash: Oid, salt_len: u32) -> Self {
+        let hash_algo = AlgorithmIdentifier::new(hash);
+        RsaPssParameters {
+            hash_algo: hash_algo.clone(),
+            mgf_hash_algo: hash_algo,
+            salt_len,
+            trailer_field: 1,
+        }
+    }
+
+    /// get RSASSA-PSS parameters from raw bytes
+    pub fn parse(params: &[u8]) -> Result<Self, OcspError> {
+        trace!("Parsing RSASSA-PSS parameters {}", hex::encode(params));
+        let s = DerObject::decode(params).map_err(OcspError::Asn1DecodingError)?;
+        if s.tag() != ASN1_SEQUENCE {
+            return Err(OcspError::Asn1MismatchError("RSASSA-PSS-params"));
+        }
+
+        let mut r = RsaPssParameters::default();
+        let mut last = None;
+        for item in asn1_decode_children(s.value())? {
+            // fields are tagged in order, each at most once
+            if last.is_some_and(|t| item.tag() <= t) {
+                return Err(OcspError::Asn1MismatchError("RSASSA-PSS-params"));
+            }
+            last = Some(item.tag());
+            match item.tag() {
+                ASN1_EXPLICIT_0 => r.hash_algo = AlgorithmIdentifier::parse_hash(item.value())?,
+                ASN1_EXPLICIT_1 => {
+                    let mgf = AlgorithmIdentifier::parse(item.value())?;
+                    if mgf.oid.index() != Some(ALGO_MGF1_ID) {
+                        error!("Unsupported mask generation function {}", mgf.oid);
+                        return Err(OcspError::Asn1MismatchError("RSASSA-PSS-params MGF"));
+                    }
+                    r.mgf_hash_algo = match mgf.parameters {
+                        AlgorithmParameters::Other(p) => AlgorithmIdentifier::parse_hash(&p)?,
+                        _ => return Err(OcspError::Asn1MismatchError("RSASSA-PSS-params MGF")),
+                    };
+                }
+                ASN1_EXPLICIT_2 => r.salt_len = asn1_decode_u32(item.value())?,
+                ASN1_EXPLICIT_3 => r.trailer_field = asn1_decode_u32(item.value())?,
+                _ => return Err(OcspError::Asn1MismatchError("RSASSA-PSS-params")),
+            }
+        }
+
+        trace!("RSASSA-PSS parameters successfully decoded");
+        Ok(r)
+    }
+
+    /// encode to ASN.1 DER, fields equal to DEFAULT are omitted
+    pub fn to_der(&self) -> Result<Bytes, OcspError> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for RsaPssParameters {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        let default = RsaPssParameters::default();
+        w.write_sequence(|w| {
+            if self.hash_algo != default.hash_algo {
+                w.write_tagged(ASN1_EXPLICIT_0, |w| self.hash_algo.write_der(w))?;
+            }
+            if self.mgf_hash_algo != default.mgf_hash_algo {
+                w.write_tagged(ASN1_EXPLICIT_1, |w| {
+                    w.write_sequence(|w| {
+                        Oid::new_from_dot(ALGO_MGF1_DOT)?.write_der(w)?;
+                        self.mgf_hash_algo.write_der(w)
+                    })
+                })?;
+            }
+            if self.salt_len != default.salt_len {
+                w.write_tagged(ASN1_EXPLICIT_2, |w| w.write_u32(self.salt_len))?;
+            }
+            if self.trailer_field != default.trailer_field {
+                w.write_tagged(ASN1_EXPLICIT_3, |w| w.write_u32(self.trailer_field))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<'a> DerDecode<'a> for RsaPssParameters {
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError> {
+        RsaPssParameters::parse(raw)
+    }
+}
+
+impl Default for RsaPssParameters {
+    fn default() -> Self {
+        let sha1 = AlgorithmIdentifier {
+            oid: Oid {
+                id: ALGO_SHA1_HEX.to_vec(),
+            },
+            parameters: AlgorithmParameters::Null,
+        };
+        RsaPssParameters {
+            hash_algo: sha1.clone(),
+            mgf_hash_algo: sha1,
+            salt_len: 20,
+            trailer_field: 1,
+        }
+    }
+}
+
+/// RFC 5280 AlgorithmIdentifier
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AlgorithmIdentifier {
+    /// algorithm oid
+    pub oid: Oid,
+    /// parameters
+    pub parameters: AlgorithmParameters,
+}
+
+impl AlgorithmIdentifier {
+    /// return new algorithm identifier  
+    /// parameters are chosen by algorithm:
+    /// - ECDSA and Ed25519 signatures have absent parameters
+    /// - RSASSA-PSS, MGF1 and id-ecPublicKey cannot go without parameters, encoding fails until they are set
+    /// - others have NULL parameters  
+    ///
+    /// use [AlgorithmIdentifier::new_rsa_pss] for RSASSA-PSS
+    pub fn new(oid: Oid) -> Self {
+        let parameters = match oid.index() {
+            Some(ALGO_SHA256_WITH_ECDSA_ENCRYPTION_ID)
+            | Some(ALGO_SHA384_WITH_ECDSA_ENCRYPTION_ID)
+            | Some(ALGO_SHA512_WITH_ECDSA_ENCRYPTION_ID)
+            | Some(ALGO_ED25519_ID) => AlgorithmParameters::Absent,
+            _ if AlgorithmIdentifier::requires_parameters(&oid) => AlgorithmParameters::Absent,
+            _ => AlgorithmParameters::Null,
+        };
+        AlgorithmIdentifier { oid, parameters }
+    }
+
+    /// algorithms whose parameters are a required structure, not absent nor NULL
+    fn requires_parameters(oid: &Oid) -> bool {
+        matches!(
+            oid.index(),
+            Some(ALGO_RSASSA_PSS_ID) | Some(ALGO_MGF1_ID) | Some(ALGO_EC_PUBLIC_KEY_ID)
+        )
+    }
+
+    /// return new RSASSA-PSS algorithm identifier
+    pub fn new_rsa_pss(params: RsaPssParameters) -> Result<Self, OcspError> {
+        Ok(AlgorithmIdentifier {
+            oid: Oid::new_from_dot(ALGO_RSASSA_PSS_DOT)?,
+            parameters: AlgorithmParameters::RsaPss(Box::new(params)),
+        })
+    }
+
+    /// get algorithm identifier from raw bytes
+    pub fn parse(algo: &[u8]) -> Result<Self, OcspError> {
+        trace!("Parsing algorithm identifier {}", hex::encode(algo));
+        let s = algo.try_into()?;
+        if !(1..=2).contains(&s.len()) {
+            error!(
+                "Provided algorithm identifier contains {} items, expecting 1 or 2",
+                s.len()
+            );
+            return Err(OcspError::Asn1LengthError("AlgorithmIdentifier"));
+        }
+
+        let oid = Oid::parse_raw(s.get(0).map_err(OcspError::Asn1DecodingError)?.raw())?;
+        let parameters = if s.len() == 1 {
+            AlgorithmParameters::Absent
+        } else {
+            let p = s.get(1).map_err(OcspError::Asn1DecodingError)?;
+            match (p.tag(), oid.index()) {
+                (ASN1_NULL, _) if p.value().is_empty() => AlgorithmParameters::Null,
+                (ASN1_SEQUENCE, Some(ALGO_RSASSA_PSS_ID)) => {
+                    AlgorithmParameters::RsaPss(Box::new(RsaPssParameters::parse(p.raw())?))
+                }
+                _ => AlgorithmParameters::Other(p.raw().to_vec()),
+            }
+        };
+
+        trace!("Algorithm identifier {} successfully decoded", oid);
+        Ok(AlgorithmIdentifier { oid, parameters })
+    }
+
+    /// get digest algorithm identifier from raw bytes  
+    /// parameters must be absent or NULL, so RSASSA-PSS hash fields cannot nest further
+    fn parse_hash(algo: &[u8]) -> Result<Self, OcspError> {
+        let s = SequenceRef::decode(algo)?;
+        let mut items = s.iter();
+        let oid = match items.next() {
+            Some(o) => Oid::parse_raw(o.raw())?,
+            None => return Err(OcspError::Asn1LengthError("AlgorithmIdentifier")),
+        };
+        let parameters = match (items.next(), items.next()) {
+            (None, _) => AlgorithmParameters::Absent,
+            (Some(p), None) if p.tag() == ASN1_NULL && p.value().is_empty() => {
+                AlgorithmParameters::Null
+            }
+            _ => {
+                error!("Provided digest algorithm {} has parameters", oid);
+                return Err(OcspError::Asn1MismatchError("Digest AlgorithmIdentifier"));
+            }
+        };
+        Ok(AlgorithmIdentifier { oid, parameters })
+    }
+
+    /// encode to ASN.1 DER
+    pub fn to_der(&self) -> Result<Bytes, OcspError> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for AlgorithmIdentifier {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        trace!("Encoding algorithm identifier {}", self.oid);
+        if AlgorithmIdentifier::requires_parameters(&self.oid)
+            && matches!(
+                self.parameters,
+                AlgorithmParameters::Absent | AlgorithmParameters::Null
+            )
+        {
+            error!("Algorithm {} requires parameters", self.oid);
+            return Err(OcspError::GenMissingField("AlgorithmIdentifier parameters"));
+        }
+        w.write_sequence(|w| {
+            self.oid.write_der(w)?;
+            match &self.parameters {
+                AlgorithmParameters::Absent => {}
+                AlgorithmParameters::Null => w.write_raw(&ASN1_OID_PADDING),
+                AlgorithmParameters::RsaPss(p) => p.write_der(w)?,
+                AlgorithmParameters::Other(p) => w.write_raw(p),
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<'a> DerDecode<'a> for AlgorithmIdentifier {
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError> {
+        AlgorithmIdentifier::parse(raw)
+    }
+}
+
+impl From<Oid> for AlgorithmIdentifier {
+    fn from(oid: Oid) -> Self {
+        AlgorithmIdentifier::new(oid)
+    }
+}
+
+/// Certificate serial number, a positive ASN.1 INTEGER  
+/// kept as minimal big-endian unsigned bytes, compared by numeric value
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SerialNumber {
+    // no leading zero, except zero itself
+    num: Bytes,
+}
+
+impl SerialNumber {
+    /// return serial number from big-endian unsigned bytes  
+    /// leading zeros are removed
+    pub fn from_bytes(be: &[u8]) -> Self {
+        let start = be.iter().position(|b| *b != 0).unwrap_or(be.len());
+        let mut num = be[start..].to_vec();
+        if num.is_empty() {
+            num.push(0);
+        }
+        SerialNumber { num }
+    }
+
+    /// get serial number from raw bytes  
+    /// - with INTEGER tag  
+    /// - negative INTEGER is rejected, it would be echoed back as a different serial
+    pub fn parse(sn: &[u8]) -> Result<Self, OcspError> {
+        trace!("Parsing serial number {}", hex::encode(sn));
+        let obj = DerObject::decode(sn).map_err(OcspError::Asn1DecodingError)?;
+        if obj.tag() != ASN1_INTEGER || obj.value().is_empty() {
+            error!("Provided serial number is not an INTEGER");
+            return Err(OcspError::Asn1MismatchError("SerialNumber"));
+        }
+        SerialNumber::check_positive(obj.value())?;
+        Ok(SerialNumber::from_bytes(obj.value()))
+    }
+
+    /// INTEGER content with high bit set is negative
+    pub(crate) fn check_positive(value: &[u8]) -> Result<(), OcspError> {
+        if value.first().is_some_and(|b| b & 0x80 != 0) {
+            error!("Provided serial number {} is negative", hex::encode(value));
+            return Err(OcspError::GenInvalidSerialNumber(hex::encode(value)));
+        }
+        Ok(())
+    }
+
+    /// return serial number from hex string  
+    /// colons between bytes are allowed, eg. 41:30:09
+    pub fn from_hex(sn: &str) -> Result<Self, OcspError> {
+        let mut digits: String = sn.chars().filter(|c| *c != ':').collect();
+        if digits.len() % 2 == 1 {
+            digits.insert(0, '0');
+        }
+        let v = hex::decode(&digits).map_err(|_| OcspError::GenInvalidSerialNumber(sn.into()))?;
+        if v.is_empty() {
+            return Err(OcspError::GenInvalidSerialNumber(sn.into()));
+        }
+        Ok(SerialNumber::from_bytes(&v))
+    }
+
+    /// minimal big-endian unsigned bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.num
+    }
+
+    /// lowercase hex string of minimal bytes
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.num)
+    }
+
+    /// encode to ASN.1 DER INTEGER  
+    /// a leading 0x00 is added if high bit is set
+    pub fn to_der(&self) -> Result<Bytes, OcspError> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+
+    fn to_u128_checked(&self, max_len: usize) -> Option<u128> {
+        if self.num.len() > max_len {
+            return None;
+        }
+        Some(
+            self.num
+                .iter()
+                .fold(0u128, |acc, b| (acc << 8) | *b as u128),
+        )
+    }
+}
+
+impl DerEncode for SerialNumber {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        w.write_tagged(ASN1_INTEGER, |w| {
+            if self.num[0] & 0x80 != 0 {
+                w.write_raw(&[0x00]);
+            }
+            w.write_raw(&self.num);
+            Ok(())
+        })
+    }
+}
+
+impl<'a> DerDecode<'a> for SerialNumber {
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError> {
+        SerialNumber::parse(raw)
+    }
+}
+
+impl Ord for SerialNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // both minimal, longer is larger
+        self.num
+            .len()
+            .cmp(&other.num.len())
+            .then_with(|| self.num.cmp(&other.num))
+    }
+}
+
+impl PartialOrd for SerialNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for SerialNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl FromStr for SerialNumber {
+    type Err = OcspError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SerialNumber::from_hex(s)
+    }
+}
+
+impl From<&[u8]> for SerialNumber {
+    fn from(be: &[u8]) -> Self {
+        SerialNumber::from_bytes(be)
+    }
+}
+
+impl From<u64> for SerialNumber {
+    fn from(num: u64) -> Self {
+        SerialNumber::from_bytes(&num.to_be_bytes())
+    }
+}
+
+impl From<u128> for SerialNumber {
+    fn from(num: u128) -> Self {
+        SerialNumber::from_bytes(&num.to_be_bytes())
+    }
+}
+
+impl TryFrom<&SerialNumber> for u64 {
+    type Error = OcspError;
+
+    fn try_from(sn: &SerialNumber) -> Result<Self, Self::Error> {
+        sn.to_u128_checked(8)
+            .map(|n| n as u64)
+            .ok_or_else(|| OcspError::GenInvalidSerialNumber(sn.to_hex()))
+    }
+}
+
+impl TryFrom<&SerialNumber> for u128 {
+    type Error = OcspError;
+
+    fn try_from(sn: &SerialNumber) -> Result<Self, Self::Error> {
+        sn.to_u128_checked(16)
+            .ok_or_else(|| OcspError::GenInvalidSerialNumber(sn.to_hex()))
+    }
+}
+
+/// hash algorithm computing [CertId] from certificates
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CertIdHash {
+    /// SHA-1, used by most clients
+    Sha1,
+    /// SHA-256
+    Sha256,
+    /// SHA-384
+    Sha384,
+    /// SHA-512
+    Sha512,
+}
+
+impl CertIdHash {
+    /// every supported hash algorithm
+    pub const ALL: [CertIdHash; 4] = [
+        CertIdHash::Sha1,
+        CertIdHash::Sha256,
+        CertIdHash::Sha384,
+        CertIdHash::Sha512,
+    ];
+
+    /// get hash algorithm from its oid, None if not supported
+    pub fn from_oid(oid: &Oid) -> Option<Self> {
+        match oid.index() {
+            Some(ALGO_SHA1_ID) => Some(CertIdHash::Sha1),
+            Some(ALGO_SHA256_ID) => Some(CertIdHash::Sha256),
+            Some(ALGO_SHA384_ID) => Some(CertIdHash::Sha384),
+            Some(ALGO_SHA512_ID) => Some(CertIdHash::Sha512),
+            _ => None,
+        }
+    }
+
+    /// oid of hash algorithm
+    pub fn oid(&self) -> Oid {
+        let id = match self {
+            CertIdHash::Sha1 => &ALGO_SHA1_HEX[..],
+            CertIdHash::Sha256 => &ALGO_SHA256_HEX[..],
+            CertIdHash::Sha384 => &ALGO_SHA384_HEX[..],
+            CertIdHash::Sha512 => &ALGO_SHA512_HEX[..],
+        };
+        Oid { id: id.to_vec() }
+    }
+
+    /// hash data
+    pub fn digest(&self, data: &[u8]) -> Bytes {
+        use sha2::Digest;
+        match self {
+            CertIdHash::Sha1 => sha1::Sha1::digest(data).to_vec(),
+            CertIdHash::Sha256 => sha2::Sha256::digest(data).to_vec(),
+            CertIdHash::Sha384 => sha2::Sha384::digest(data).to_vec(),
+            CertIdHash::Sha512 => sha2::Sha512::digest(data).to_vec(),
+        }
     }
 }
 
 /// RFC 6960 CertID or cid
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct CertId {
-    /// hash algo oid
-    pub hash_algo: Oid,
+    /// hash algorithm
+    pub hash_algo: AlgorithmIdentifier,
     /// issuer name hash in byte
     pub issuer_name_hash: Bytes,
     /// issuer key hash in byte
     pub issuer_key_hash: Bytes,
-    /// certificate serial number in byte
-    pub serial_num: Bytes,
+    /// certificate serial number
+    pub serial_num: SerialNumber,
 }
 
 impl CertId {
@@ -325,10 +1315,15 @@ impl CertId {
             return Err(OcspError::Asn1MismatchError("CID"));
         }
 
-        let oid = Oid::parse(oid.raw())?;
+        let oid = AlgorithmIdentifier::parse(oid.raw()).map_err(asn1_field(
+            cid,
+            oid.raw(),
+            "hashAlgorithm",
+        ))?;
         let name_hash = name_hash.value().to_vec();
         let key_hash = key_hash.value().to_vec();
-        let sn = sn.value().to_vec();
+        let sn =
+            SerialNumber::parse(sn.raw()).map_err(asn1_field(cid, sn.raw(), "serialNumber"))?;
 
         trace!("Cid {} successfully decoded", cid_hex);
         Ok(CertId {
@@ -339,44 +1334,223 @@ impl CertId {
         })
     }
 
-    /// create new cid
-    pub fn new(oid: Oid, name_hash: &[u8], key_hash: &[u8], sn: &[u8]) -> Self {
+    /// create new cid  
+    /// hash algorithm can be an [Oid], with NULL parameters  
+    /// serial number is big-endian unsigned bytes
+    pub fn new(
+        hash_algo: impl Into<AlgorithmIdentifier>,
+        name_hash: &[u8],
+        key_hash: &[u8],
+        sn: &[u8],
+    ) -> Self {
         CertId {
-            hash_algo: oid,
+            hash_algo: hash_algo.into(),
             issuer_name_hash: name_hash.to_vec(),
             issuer_key_hash: key_hash.to_vec(),
-            serial_num: sn.to_vec(),
+            serial_num: SerialNumber::from_bytes(sn),
         }
     }
 
+    /// create cid of subject certificate issued by issuer certificate, both in DER  
+    /// see [CertId::from_issuer_and_serial]
+    pub fn from_certificates(
+        issuer: &[u8],
+        subject: &[u8],
+        hash: CertIdHash,
+    ) -> Result<Self, OcspError> {
+        let tbs = asn1_tbs_certificate(subject)?;
+        let sn = tbs[0].raw();
+        let sn = SerialNumber::parse(sn).map_err(asn1_field(
+            subject,
+            sn,
+            "tbsCertificate.serialNumber",
+        ))?;
+        CertId::from_issuer_and_serial(issuer, sn, hash)
+    }
+
+    /// create cid of serial number issued by issuer certificate in DER  
+    /// - name hash is over issuer's subject Name in DER  
+    /// - key hash is over issuer's subjectPublicKey, without BIT STRING tag, length and unused bits
+    pub fn from_issuer_and_serial(
+        issuer: &[u8],
+        sn: SerialNumber,
+        hash: CertIdHash,
+    ) -> Result<Self, OcspError> {
+        let (name, key) = asn1_issuer_name_key(issuer)?;
+
+        trace!("Cid with sn {} created from issuer certificate", sn);
+        Ok(CertId {
+            hash_algo: hash.oid().into(),
+            issuer_name_hash: hash.digest(name),
+            issuer_key_hash: hash.digest(key),
+            serial_num: sn,
+        })
+    }
+
     /// encode cid to ASN.1 DER
     pub fn to_der(&self) -> Result<Bytes, OcspError> {
-        trace!("Encoding cid with sn {}", hex::encode(&self.serial_num));
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for CertId {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        trace!("Encoding cid with sn {}", self.serial_num);
+        w.write_sequence(|w| {
+            self.hash_algo.write_der(w)?;
+            w.write_octet(&self.issuer_name_hash)?;
+            w.write_octet(&self.issuer_key_hash)?;
+            self.serial_num.write_der(w)
+        })
+    }
+}
+
+impl<'a> DerDecode<'a> for CertId {
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError> {
+        CertId::parse(raw)
+    }
+}
+
+/// RFC 6960 CertID borrowed from raw bytes  
+/// fields are slices of the input, see [CertId] for the owned type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CertIdRef<'a> {
+    /// hash algorithm, AlgorithmIdentifier in DER
+    pub hash_algo: &'a [u8],
+    /// issuer name hash in byte
+    pub issuer_name_hash: &'a [u8],
+    /// issuer key hash in byte
+    pub issuer_key_hash: &'a [u8],
+    /// certificate serial number  
+    /// minimal big-endian unsigned bytes, same as [SerialNumber::as_bytes]
+    pub serial_num: &'a [u8],
+}
+
+impl<'a> CertIdRef<'a> {
+    /// get borrowed cid from raw bytes  
+    /// hash algorithm is only checked to start with an oid
+    pub fn parse(cid: &'a [u8]) -> Result<Self, OcspError> {
+        trace!("Parsing borrowed cid {}", hex::encode(cid));
+        let s = SequenceRef::decode(cid)?;
 
-        let mut oid = self.hash_algo.to_der_with_null()?;
-        let name = asn1_encode_octet(&self.issuer_name_hash)?;
-        let key = asn1_encode_octet(&self.issuer_key_hash)?;
-        let sn = asn1_encode_integer(&self.serial_num)?;
-        oid.extend(name);
-        oid.extend(key);
-        oid.extend(sn);
-        let len = asn1_encode_length(oid.len())?;
-        let mut tlv = vec![ASN1_SEQUENCE];
-        tlv.extend(len);
-        tlv.extend(oid);
+        let mut items = s.iter();
+        let (oid, name_hash, key_hash, sn) =
+            match (items.next(), items.next(), items.next(), items.next()) {
+                (Some(o), Some(n), Some(k), Some(sn)) if s.len() == 4 => (o, n, k, sn),
+                _ => {
+                    error!(
+                        "Provided cid contains {} items in sequence, expecting 4",
+                        s.len()
+                    );
+                    return Err(OcspError::Asn1LengthError("CID"));
+                }
+            };
+
+        if oid.tag() != ASN1_SEQUENCE
+            || name_hash.tag() != ASN1_OCTET
+            || key_hash.tag() != ASN1_OCTET
+            || sn.tag() != ASN1_INTEGER
+            || sn.value().is_empty()
+        {
+            error!(
+                "Provided cid sequence tags are {}, {}, {} and {}, expecting 0x30, 0x04, 0x04, 0x02",
+                oid.tag(),
+                name_hash.tag(),
+                key_hash.tag(),
+                sn.tag()
+            );
+            return Err(OcspError::Asn1MismatchError("CID"));
+        }
+        let field = asn1_field(cid, oid.raw(), "hashAlgorithm");
+        let algo = DerObject::decode(oid.value())
+            .map_err(OcspError::Asn1DecodingError)
+            .map_err(field)?;
+        if algo.tag() != ASN1_OID {
+            return Err(field(OcspError::Asn1MismatchError("AlgorithmIdentifier")));
+        }
+
+        SerialNumber::check_positive(sn.value()).map_err(asn1_field(
+            cid,
+            sn.raw(),
+            "serialNumber",
+        ))?;
+        let mut sn = sn.value();
+        while sn.len() > 1 && sn[0] == 0 {
+            sn = &sn[1..];
+        }
+
+        Ok(CertIdRef {
+            hash_algo: oid.raw(),
+            issuer_name_hash: name_hash.value(),
+            issuer_key_hash: key_hash.value(),
+            serial_num: sn,
+        })
+    }
+
+    /// copy into an owned [CertId]  
+    /// hash algorithm is fully decoded here
+    pub fn to_owned(&self) -> Result<CertId, OcspError> {
+        Ok(CertId {
+            hash_algo: AlgorithmIdentifier::parse(self.hash_algo)?,
+            issuer_name_hash: self.issuer_name_hash.to_vec(),
+            issuer_key_hash: self.issuer_key_hash.to_vec(),
+            serial_num: SerialNumber::from_bytes(self.serial_num),
+        })
+    }
+}
 
-        trace!("Cid {:?} successfully encoded", self);
-        Ok(tlv)
+impl<'a> DerDecode<'a> for CertIdRef<'a> {
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError> {
+        CertIdRef::parse(raw)
     }
 }
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use hex::FromHex;
 
     use crate::oid::{ALGO_SHA1_DOT, OCSP_EXT_CRL_REASON_DOT, OCSP_EXT_CRL_REASON_ID};
 
     use super::*;
 
+    /// inner wrapped depth times in tag, outermost first
+    pub(crate) fn nested(tag: u8, depth: usize, inner: &[u8]) -> Vec<u8> {
+        let mut headers = vec![];
+        let mut len = inner.len();
+        for _ in 0..depth {
+            let mut h = vec![tag];
+            h.extend(asn1_encode_length(len).unwrap());
+            len += h.len();
+            headers.push(h);
+        }
+        let mut r = Vec::with_capacity(len);
+        for h in headers.iter().rev() {
+            r.extend_from_slice(h);
+        }
+        r.extend_from_slice(inner);
+        r
+    }
+
+    /// RSASSA-PSS whose hashAlgorithm is RSASSA-PSS again, depth times, around SHA-1
+    pub(crate) fn nested_rsa_pss(depth: usize) -> Vec<u8> {
+        let pss = Oid::new_from_dot(ALGO_RSASSA_PSS_DOT).unwrap();
+        let mut v = hex::decode("300906052b0e03021a0500").unwrap();
+        for _ in 0..depth {
+            v = DerWriter::encode(|w| {
+                w.write_sequence(|w| {
+                    pss.write_der(w)?;
+                    w.write_sequence(|w| {
+                        w.write_tagged(ASN1_EXPLICIT_0, |w| {
+                            w.write_raw(&v);
+                            Ok(())
+                        })
+                    })
+                })
+            })
+            .unwrap();
+        }
+        v
+    }
+
     // test encoding length
     #[test]
     fn encoding_length_over128() {
@@ -426,7 +1600,7 @@ mod test {
             0x57, 0x40, 0x69, 0xb4,
         ];
 
-        let bit = asn1_encode_bit_string(&sign).unwrap();
+        let bit = DerWriter::encode(|w| w.write_tlv(ASN1_BIT_STRING, &sign)).unwrap();
         let c = vec![
             0x03, 0x82, 0x01, 0x00, 0x6d, 0xdb, 0x51, 0x4f, 0x2c, 0x6a, 0x35, 0x49, 0x80, 0x1e,
             0x40, 0x1e, 0x31, 0x45, 0xdd, 0x88, 0x4a, 0x6a, 0x47, 0x2c, 0x8a, 0x09, 0xa6, 0xf9,
@@ -493,8 +1667,315 @@ mod test {
     #[test]
     fn oid_dot_new() {
         let dot = OCSP_EXT_CRL_REASON_DOT;
-        let d = Oid::new_from_dot(dot).unwrap().index;
-        assert_eq!(d, OCSP_EXT_CRL_REASON_ID);
+        let d = Oid::new_from_dot(dot).unwrap().index();
+        assert_eq!(d, Some(OCSP_EXT_CRL_REASON_ID));
+    }
+
+    /// oid not in the well-known list
+    #[test]
+    fn oid_arbitrary() {
+        let oid = Oid::new_from_dot("1.3.6.1.4.1.11129.2.4.2").unwrap();
+        assert_eq!(
+            vec![0x06, 0x0a, 0x2b, 0x06, 0x01, 0x04, 0x01, 0xd6, 0x79, 0x02, 0x04, 0x02],
+            oid.to_der_raw().unwrap()
+        );
+        assert_eq!(None, oid.index());
+        assert_eq!("1.3.6.1.4.1.11129.2.4.2", oid.to_string());
+
+        let raw = Oid::parse_raw(&oid.to_der_raw().unwrap()).unwrap();
+        assert_eq!(oid, raw);
+        let known = Oid::new_from_dot(OCSP_EXT_CRL_REASON_DOT).unwrap();
+        assert_eq!(Some("id-ce 21"), known.name());
+    }
+
+    /// malformed oid is an error
+    #[test]
+    fn oid_invalid() {
+        assert!(Oid::new_from_dot("3.1").is_err());
+        assert!(Oid::new_from_dot("1.2.").is_err());
+        assert!(Oid::parse_raw(&[0x06, 0x02, 0x2b, 0x80]).is_err());
+        assert!(Oid::parse_raw(&[0x06, 0x00]).is_err());
+    }
+
+    /// parameters chosen per algorithm
+    #[test]
+    fn algorithm_identifier_new() {
+        let ecdsa = Oid::new_from_dot(ALGO_SHA256_WITH_ECDSA_ENCRYPTION_DOT).unwrap();
+        let v = AlgorithmIdentifier::new(ecdsa).to_der().unwrap();
+        assert_eq!(hex::decode("300a06082a8648ce3d040302").unwrap(), v);
+
+        let ed25519 = Oid::new_from_dot(ALGO_ED25519_DOT).unwrap();
+        let v = AlgorithmIdentifier::new(ed25519).to_der().unwrap();
+        assert_eq!(hex::decode("300506032b6570").unwrap(), v);
+
+        let rsa = Oid::new_from_dot(ALGO_SHA256_WITH_RSA_ENCRYPTION_DOT).unwrap();
+        let v = AlgorithmIdentifier::new(rsa).to_der().unwrap();
+        assert_eq!(hex::decode("300d06092a864886f70d01010b0500").unwrap(), v);
+
+        // parameters are a required structure
+        for dot in [ALGO_RSASSA_PSS_DOT, ALGO_MGF1_DOT, "1.2.840.10045.2.1"] {
+            let algo = AlgorithmIdentifier::new(Oid::new_from_dot(dot).unwrap());
+            assert!(matches!(
+                algo.to_der(),
+                Err(OcspError::GenMissingField("AlgorithmIdentifier parameters"))
+            ));
+        }
+        let pss = AlgorithmIdentifier::new_rsa_pss(RsaPssParameters::default()).unwrap();
+        assert_eq!(
+            hex::decode("300d06092a864886f70d01010a3000").unwrap(),
+            pss.to_der().unwrap()
+        );
+        // RSASSA-PSS with NULL as received
+        let c = hex::decode("300d06092a864886f70d01010a0500").unwrap();
+        assert!(AlgorithmIdentifier::parse(&c).unwrap().to_der().is_err());
+    }
+
+    /// absent, NULL and unknown parameters are kept
+    #[test]
+    fn algorithm_identifier_parse() {
+        let c = hex::decode("300a06082a8648ce3d040302").unwrap();
+        let algo = AlgorithmIdentifier::parse(&c).unwrap();
+        assert_eq!(AlgorithmParameters::Absent, algo.parameters);
+        assert_eq!(c, algo.to_der().unwrap());
+
+        let c = hex::decode("300906052b0e03021a0500").unwrap();
+        let algo = AlgorithmIdentifier::parse(&c).unwrap();
+        assert_eq!(AlgorithmParameters::Null, algo.parameters);
+        assert_eq!(c, algo.to_der().unwrap());
+
+        let c = hex::decode("301306072a8648ce3d020106082a8648ce3d030107").unwrap();
+        let algo = AlgorithmIdentifier::parse(&c).unwrap();
+        assert_eq!(
+            AlgorithmParameters::Other(hex::decode("06082a8648ce3d030107").unwrap()),
+            algo.parameters
+        );
+        assert_eq!(c, algo.to_der().unwrap());
+
+        let c = hex::decode("300b06082a8648ce3d04030201").unwrap();
+        assert!(AlgorithmIdentifier::parse(&c).is_err());
+    }
+
+    /// RSASSA-PSS with sha256, as generated by OpenSSL
+    #[test]
+    fn algorithm_identifier_rsa_pss() {
+        let c = hex::decode(
+            "304106092a864886f70d01010a3034a00f300d0609608648016503040201\
+            0500a11c301a06092a864886f70d010108300d06096086480165030402010500a2\
+            03020120",
+        )
+        .unwrap();
+        let algo = AlgorithmIdentifier::parse(&c).unwrap();
+        let sha256 = Oid::new_from_dot(ALGO_SHA256_DOT).unwrap();
+        let params = RsaPssParameters::new(sha256, 32);
+        assert_eq!(
+            AlgorithmParameters::RsaPss(Box::new(params.clone())),
+            algo.parameters
+        );
+        assert_eq!(c, algo.to_der().unwrap());
+
+        let v = AlgorithmIdentifier::new_rsa_pss(params)
+            .unwrap()
+            .to_der()
+            .unwrap();
+        assert_eq!(c, v);
+
+        // all DEFAULT
+        let c = hex::decode("300d06092a864886f70d01010a3000").unwrap();
+        let algo = AlgorithmIdentifier::parse(&c).unwrap();
+        assert_eq!(AlgorithmParameters::RsaPss(Box::default()), algo.parameters);
+        assert_eq!(c, algo.to_der().unwrap());
+    }
+
+    /// hash of RSASSA-PSS is a plain digest identifier
+    #[test]
+    fn algorithm_identifier_rsa_pss_nested() {
+        AlgorithmIdentifier::parse(&nested_rsa_pss(1)).unwrap();
+        for depth in [2, 4_000] {
+            let err = AlgorithmIdentifier::parse(&nested_rsa_pss(depth)).unwrap_err();
+            assert!(matches!(
+                err,
+                OcspError::Asn1MismatchError("Digest AlgorithmIdentifier")
+            ));
+        }
+
+        // MGF1 hash with parameters other than NULL
+        let c = hex::decode(
+            "303006092a864886f70d01010a3023a121301f06092a864886f70d0101083012\
+            060960864801650304020106052b0e03021a",
+        )
+        .unwrap();
+        assert!(AlgorithmIdentifier::parse(&c).is_err());
+    }
+
+    /// serial number with high bit set is padded
+    #[test]
+    fn serial_number_to_der() {
+        let sn = SerialNumber::from_bytes(&[0x00, 0x00, 0x80, 0x01]);
+        assert_eq!(vec![0x80, 0x01], sn.as_bytes());
+        assert_eq!(vec![0x02, 0x03, 0x00, 0x80, 0x01], sn.to_der().unwrap());
+
+        let sn = SerialNumber::from(0u64);
+        assert_eq!(vec![0x02, 0x01, 0x00], sn.to_der().unwrap());
+        let sn = SerialNumber::from(0x7fu64);
+        assert_eq!(vec![0x02, 0x01, 0x7f], sn.to_der().unwrap());
+    }
+
+    /// padded serial numbers are equal, negative ones are rejected
+    #[test]
+    fn serial_number_parse() {
+        let padded = SerialNumber::parse(&[0x02, 0x03, 0x00, 0x80, 0x01]).unwrap();
+        let overpadded = SerialNumber::parse(&[0x02, 0x04, 0x00, 0x00, 0x80, 0x01]).unwrap();
+        assert_eq!(padded, overpadded);
+        assert_eq!(SerialNumber::from(0x8001u64), padded);
+        // -255 and -1
+        for neg in [&[0x02, 0x02, 0xff, 0x01][..], &[0x02, 0x01, 0xff][..]] {
+            assert!(matches!(
+                SerialNumber::parse(neg),
+                Err(OcspError::GenInvalidSerialNumber(_))
+            ));
+        }
+        assert!(SerialNumber::parse(&[0x02, 0x00]).is_err());
+        assert!(SerialNumber::parse(&[0x04, 0x01, 0x01]).is_err());
+    }
+
+    /// serial numbers compare by value
+    #[test]
+    fn serial_number_ord() {
+        let a = SerialNumber::from(0xffu64);
+        let b = SerialNumber::from(0x0100u64);
+        let c = SerialNumber::from_bytes(&[0x00, 0x01, 0x00]);
+        assert!(a < b);
+        assert_eq!(b, c);
+        assert!(SerialNumber::from(u128::MAX) > SerialNumber::from(u64::MAX));
+    }
+
+    /// serial number conversions
+    #[test]
+    fn serial_number_convert() {
+        let sn: SerialNumber = "41:30:09:83:33:1F:9D:4F".parse().unwrap();
+        assert_eq!(0x41300983331f9d4f_u128, u128::try_from(&sn).unwrap());
+        assert_eq!(0x41300983331f9d4f, u64::try_from(&sn).unwrap());
+        assert_eq!("41300983331f9d4f", sn.to_string());
+        assert_eq!(
+            SerialNumber::from_hex("abc").unwrap(),
+            SerialNumber::from(0xabcu64)
+        );
+
+        let big = SerialNumber::from_bytes(&[0x01; 17]);
+        assert!(u128::try_from(&big).is_err());
+        assert!(u64::try_from(&SerialNumber::from(u128::MAX)).is_err());
+        assert!(SerialNumber::from_hex("xyz").is_err());
+        assert!(SerialNumber::from_hex("").is_err());
+    }
+
+    /// certid with sha256 hash algorithm
+    #[test]
+    fn certid_sha256_parse() {
+        let c = hex::decode(
+            "3056300d060960864801650304020105000420\
+            0000000000000000000000000000000000000000000000000000000000000000\
+            04200101010101010101010101010101010101010101010101010101010101010101\
+            020101",
+        )
+        .unwrap();
+        let certid = CertId::parse(&c).unwrap();
+        assert_eq!("2.16.840.1.101.3.4.2.1", certid.hash_algo.oid.to_string());
+        assert_eq!(c, certid.to_der().unwrap());
+    }
+
+    // borrowed cid points into input
+    #[test]
+    fn certid_ref_parse() {
+        let c = hex::decode(
+            "3057300d060960864801650304020105000420\
+            0000000000000000000000000000000000000000000000000000000000000000\
+            04200101010101010101010101010101010101010101010101010101010101010101\
+            020200ff",
+        )
+        .unwrap();
+        let cid = CertIdRef::parse(&c).unwrap();
+        assert_eq!(&c[2..17], cid.hash_algo);
+        assert_eq!(&[0x01; 32], cid.issuer_key_hash);
+        assert_eq!(&[0xff], cid.serial_num);
+
+        let owned = cid.to_owned().unwrap();
+        assert_eq!(CertId::parse(&c).unwrap(), owned);
+        assert_eq!(owned.serial_num.as_bytes(), cid.serial_num);
+        assert_eq!(c, owned.to_der().unwrap());
+
+        // negative serial number
+        let mut neg = c.clone();
+        let last = neg.len() - 2;
+        neg[last] = 0xff;
+        let err = CertIdRef::parse(&neg).unwrap_err();
+        assert_eq!(Some("serialNumber"), err.path());
+        let err = CertId::parse(&neg).unwrap_err();
+        assert_eq!(Some("serialNumber"), err.path());
+        assert!(matches!(err.root(), OcspError::GenInvalidSerialNumber(_)));
+
+        // not an algorithm identifier
+        let mut c = c;
+        c[4] = 0x04;
+        assert!(CertIdRef::parse(&c).is_err());
+    }
+
+    // nesting depth is bounded by input size only, not by the call stack
+    #[test]
+    fn validate_der_deep() {
+        let deep = nested(ASN1_SEQUENCE, 100_000, &[0x01, 0x01, 0xff]);
+        asn1_validate_der(&deep).unwrap();
+        let deep = nested(ASN1_SEQUENCE, 100_000, &[0x01, 0x01, 0x01]);
+        assert!(matches!(
+            asn1_validate_der(&deep),
+            Err(OcspError::Asn1NonCanonicalBoolean(0x01))
+        ));
+        let mut deep = nested(ASN1_SEQUENCE, 100_000, &[]);
+        deep.push(0x00);
+        assert!(matches!(
+            asn1_validate_der(&deep),
+            Err(OcspError::Asn1TrailingData(1))
+        ));
+        // sibling after a deep value
+        let mut inner = nested(ASN1_EXPLICIT_0, 1_000, &[]);
+        inner.extend_from_slice(&[0x02, 0x01, 0x01]);
+        asn1_validate_der(&nested(ASN1_SEQUENCE, 1, &inner)).unwrap();
+    }
+
+    // BER constructs rejected by strict validation
+    #[test]
+    fn validate_der() {
+        asn1_validate_der(&[0x30, 0x03, 0x01, 0x01, 0xff]).unwrap();
+        asn1_validate_der(&[0x30, 0x00]).unwrap();
+        let cases: Vec<(&[u8], OcspError)> = vec![
+            (
+                &[0x30, 0x81, 0x03, 0x01, 0x01, 0xff],
+                OcspError::Asn1NonMinimalLength,
+            ),
+            (
+                &[0x30, 0x80, 0x01, 0x01, 0xff, 0x00, 0x00],
+                OcspError::Asn1IndefiniteLength,
+            ),
+            (
+                &[0x30, 0x03, 0x01, 0x01, 0xff, 0x00],
+                OcspError::Asn1TrailingData(1),
+            ),
+            (
+                &[0x30, 0x03, 0x01, 0x01, 0x01],
+                OcspError::Asn1NonCanonicalBoolean(0x01),
+            ),
+            (&[0x30, 0x05, 0x01, 0x01, 0xff], OcspError::Asn1Truncated),
+            (
+                &[0x30, 0x04, 0x01, 0x01, 0xff, 0x05],
+                OcspError::Asn1Truncated,
+            ),
+            (&[], OcspError::Asn1Truncated),
+        ];
+        for (raw, err) in cases {
+            assert_eq!(
+                format!("{:?}", err),
+                format!("{:?}", asn1_validate_der(raw).unwrap_err())
+            );
+        }
     }
 
     /// test generalized time to der
@@ -507,6 +1988,7 @@ mod test {
             hour: 3,
             min: 9,
             sec: 25,
+            nanos: 0,
         };
 
         let der = gt.to_der_utc().unwrap();
@@ -519,6 +2001,94 @@ mod test {
         );
     }
 
+    /// test generalized time from der
+    #[test]
+    fn generalized_time_parse() {
+        let der = vec![
+            0x18, 0x0f, 0x32, 0x30, 0x32, 0x31, 0x30, 0x31, 0x31, 0x33, 0x30, 0x33, 0x30, 0x39,
+            0x32, 0x35, 0x5a,
+        ];
+        let gt = GeneralizedTime::parse(&der).unwrap();
+        assert_eq!(der, gt.to_der_utc().unwrap());
+
+        // 2021-02-30 does not exist
+        let der = b"\x18\x0f20210230030925Z";
+        assert!(GeneralizedTime::parse(der).is_err());
+    }
+
+    /// test generalized time with fractional seconds
+    #[test]
+    fn generalized_time_parse_fraction() {
+        let der = b"\x18\x1320210113030925.125Z";
+        let gt = GeneralizedTime::parse(der).unwrap();
+        assert_eq!(125_000_000, gt.nanos());
+        assert_eq!(der.to_vec(), gt.to_der_utc().unwrap());
+
+        let gt = GeneralizedTime::new_with_nanos(2021, 1, 13, 3, 9, 25, 1).unwrap();
+        let der = b"\x18\x1920210113030925.000000001Z";
+        assert_eq!(der.to_vec(), gt.to_der_utc().unwrap());
+
+        // trailing zero, empty fraction, comma and local time are not DER
+        for der in [
+            &b"\x18\x1320210113030925.120Z"[..],
+            &b"\x18\x1020210113030925.Z"[..],
+            &b"\x18\x1220210113030925,12Z"[..],
+            &b"\x18\x0e20210113030925"[..],
+        ] {
+            assert!(GeneralizedTime::parse(der).is_err());
+        }
+    }
+
+    /// test generalized time ordering
+    #[test]
+    fn generalized_time_ord() {
+        let a = GeneralizedTime::new(2021, 1, 13, 3, 9, 25).unwrap();
+        let b = GeneralizedTime::new_with_nanos(2021, 1, 13, 3, 9, 25, 500).unwrap();
+        let c = GeneralizedTime::new(2021, 2, 1, 0, 0, 0).unwrap();
+        assert!(a < b && b < c);
+        assert_eq!(a, GeneralizedTime::new(2021, 1, 13, 3, 9, 25).unwrap());
+    }
+
+    /// test generalized time conversions
+    #[test]
+    fn generalized_time_convert() {
+        let gt = GeneralizedTime::new_with_nanos(2021, 1, 13, 3, 9, 25, 500).unwrap();
+        let dt = DateTime::<Utc>::from(gt);
+        assert_eq!("2021-01-13T03:09:25.000000500+00:00", dt.to_rfc3339());
+        assert_eq!(gt, GeneralizedTime::try_from(dt).unwrap());
+
+        let st = SystemTime::from(gt);
+        assert_eq!(gt, GeneralizedTime::try_from(st).unwrap());
+        assert_eq!(
+            Duration::new(1610507365, 500),
+            st.duration_since(SystemTime::UNIX_EPOCH).unwrap()
+        );
+    }
+
+    /// test generalized time duration arithmetic
+    #[test]
+    fn generalized_time_duration() {
+        let this = GeneralizedTime::new(2021, 2, 26, 12, 0, 0).unwrap();
+        let next = this + Duration::from_secs(4 * 24 * 3600);
+        assert_eq!(GeneralizedTime::new(2021, 3, 2, 12, 0, 0).unwrap(), next);
+        assert_eq!(this, next - Duration::from_secs(4 * 24 * 3600));
+        assert_eq!(
+            Some(Duration::from_secs(4 * 24 * 3600)),
+            next.duration_since(this)
+        );
+        assert_eq!(None, this.duration_since(next));
+
+        let mut t = this;
+        t += Duration::from_millis(1500);
+        assert_eq!(
+            b"\x18\x1120210226120001.5Z".to_vec(),
+            t.to_der_utc().unwrap()
+        );
+
+        let end = GeneralizedTime::new(9999, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(None, end.checked_add(Duration::from_secs(1)));
+    }
+
     /// test asn1 encoding with length requires more than one byte
     #[test]
     fn asn1_length_4934() {
@@ -550,4 +2120,88 @@ mod test {
         let time = std::str::from_utf8(&hex).unwrap();
         assert_eq!("20210113030925Z", time);
     }
+
+    // cid matching requests from `openssl ocsp -sha256 -issuer ca.pem -cert leaf.pem`
+    #[test]
+    fn certid_from_certificates() {
+        use crate::request::OcspRequest;
+        use crate::verify::test::{CA_CERT, LEAF_CERT};
+
+        let sha1 = "30433041303f303d303b300906052b0e\
+    03021a05000414bf7052c8b9c0f760c8\
+    9123e099815eb2c039422604140c316b\
+    c36fbf60ce6108ebbdffd2614737ae0e\
+    0d02021234";
+        let sha256 = "305f305d305b30593057300d06096086\
+    48016503040201050004208048479c84\
+    625e7fde383cf9d59c450e415dae47be\
+    9d15c3f75bb6dc57aeb56204208cefb0\
+    387ac8eb403b6f92d73dee501bebab1e\
+    619ab5281489ecd33d29b381d8020212\
+    34";
+        let sha384 = "307f307d307b30793077300d06096086\
+    4801650304020205000430acd2eddece\
+    539456c76a6a5c2844690cfd6ee219d0\
+    c79dd4bee247053897f36eaedcd622c5\
+    1385076db9c8edf76376150430bd5dae\
+    612512655f932d4d863e41ac32104e91\
+    37fe624cb9e5c61b219429fde53f5478\
+    5362ce8a9de9dc61b7c7d57fe1020212\
+    34";
+        let sha512 = "3081a33081a030819d30819a30819730\
+    0d060960864801650304020305000440\
+    635f262918c5cf3580b6d1311e037ed9\
+    ade1eef6b99a41bd762033907abc62a4\
+    cd7a2a6cf148f23cd232480b1dc678ca\
+    cbc78326a3f12bfdbffd9f0702332390\
+    04400457e7ec3d28d64dc2d524b49779\
+    7ddecd147db1837835372b4220fabe54\
+    c460a39b0293b60712f57cb9154732f6\
+    d4b28af58b25301ce8e441babdc0b58b\
+    bdbd02021234";
+        let ca = pem_rfc7468::decode_vec(CA_CERT.as_bytes()).unwrap().1;
+        let leaf = pem_rfc7468::decode_vec(LEAF_CERT.as_bytes()).unwrap().1;
+        for (hash, req) in [
+            (CertIdHash::Sha1, sha1),
+            (CertIdHash::Sha256, sha256),
+            (CertIdHash::Sha384, sha384),
+            (CertIdHash::Sha512, sha512),
+        ] {
+            let req = OcspRequest::parse(&hex::decode(req).unwrap()).unwrap();
+            let cid = CertId::from_certificates(&ca, &leaf, hash).unwrap();
+            assert_eq!(req.extract_certid()[0], &cid);
+            assert_eq!(
+                req.tbs_request().request_list[0].certid.to_der().unwrap(),
+                cid.to_der().unwrap()
+            );
+        }
+
+        // `openssl ocsp -sha256 -issuer ca.pem -serial 0x99`
+        let req = "305f305d305b30593057300d06096086\
+    48016503040201050004208048479c84\
+    625e7fde383cf9d59c450e415dae47be\
+    9d15c3f75bb6dc57aeb56204208cefb0\
+    387ac8eb403b6f92d73dee501bebab1e\
+    619ab5281489ecd33d29b381d8020200\
+    99";
+        let req = OcspRequest::parse(&hex::decode(req).unwrap()).unwrap();
+        let sn = SerialNumber::from_hex("99").unwrap();
+        let cid = CertId::from_issuer_and_serial(&ca, sn, CertIdHash::Sha256).unwrap();
+        assert_eq!(req.extract_certid()[0], &cid);
+
+        // tbs certificate with serial number only
+        let short = DerWriter::encode(|w| {
+            w.write_sequence(|w| w.write_sequence(|w| w.write_tlv(ASN1_INTEGER, &[0x01])))
+        })
+        .unwrap();
+        let e = CertId::from_certificates(&ca, &short, CertIdHash::Sha1).unwrap_err();
+        assert_eq!(Some("tbsCertificate"), e.path());
+        assert_eq!(Some(2), e.offset());
+        let e = CertId::from_issuer_and_serial(
+            &leaf[..20],
+            SerialNumber::from_bytes(&[0x01]),
+            CertIdHash::Sha1,
+        );
+        assert!(matches!(e, Err(OcspError::Asn1DecodingError(_))));
+    }
 }
@@ -0,0 +1,399 @@
+//! ASN.1 DER writer and common encoding traits
+
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::common::asn1::{Bytes, SequenceRef, ASN1_INTEGER, ASN1_OCTET, ASN1_SEQUENCE};
+use crate::err::OcspError;
+
+/// Types with an ASN.1 DER encoding
+pub trait DerEncode {
+    /// write DER encoding of self
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError>;
+
+    /// length of DER encoding in bytes  
+    /// counted without allocating the encoding
+    fn encoded_len(&self) -> Result<usize, OcspError> {
+        DerWriter::count(|w| self.write_der(w))
+    }
+
+    /// encode self to ASN.1 DER
+    fn to_der(&self) -> Result<Bytes, OcspError> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+
+    /// append ASN.1 DER of self to buf
+    fn to_der_into(&self, buf: &mut Vec<u8>) -> Result<(), OcspError> {
+        DerWriter::encode_into(buf, |w| self.write_der(w))
+    }
+
+    /// write ASN.1 DER of self to writer, return bytes written
+    fn to_writer(&self, writer: &mut dyn Write) -> Result<usize, OcspError> {
+        DerWriter::encode_to(writer, |w| self.write_der(w))
+    }
+}
+
+/// Types decodable from ASN.1 DER  
+/// 'a allows borrowed types to keep slices of the input
+pub trait DerDecode<'a>: Sized {
+    /// decode from a TLV, bytes following the TLV are ignored
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError>;
+}
+
+/// SEQUENCE OF T
+impl<T: DerEncode> DerEncode for [T] {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        w.write_sequence(|w| self.iter().try_for_each(|t| t.write_der(w)))
+    }
+}
+
+/// SEQUENCE OF T
+impl<T: DerEncode> DerEncode for Vec<T> {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        self.as_slice().write_der(w)
+    }
+}
+
+/// SEQUENCE OF T
+impl<'a, T: DerDecode<'a>> DerDecode<'a> for Vec<T> {
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError> {
+        SequenceRef::decode(raw)?
+            .iter()
+            .map(|t| T::from_der(t.raw()))
+            .collect()
+    }
+}
+
+enum Sink<'a> {
+    Count(usize),
+    Buffer(&'a mut Vec<u8>),
+    Writer {
+        writer: &'a mut dyn Write,
+        written: usize,
+        err: Option<io::Error>,
+    },
+}
+
+impl fmt::Debug for Sink<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sink::Count(n) => write!(f, "Count({})", n),
+            Sink::Buffer(buf) => write!(f, "Buffer({})", buf.len()),
+            Sink::Writer { written, .. } => write!(f, "Writer({})", written),
+        }
+    }
+}
+
+/// ASN.1 DER writer into a caller supplied buffer or [io::Write]  
+/// values are first counted, recording the length of every constructed value,
+/// then written once with lengths known up front, so no byte is moved after being written  
+/// on error, bytes written so far are left in the buffer
+#[derive(Debug)]
+pub struct DerWriter<'a> {
+    sink: Sink<'a>,
+    /// value lengths of constructed TLVs in the order they are opened
+    lens: Vec<usize>,
+    /// next entry of lens when writing
+    next: usize,
+}
+
+impl<'a> DerWriter<'a> {
+    fn with_sink(sink: Sink<'a>, lens: Vec<usize>) -> Self {
+        DerWriter {
+            sink,
+            lens,
+            next: 0,
+        }
+    }
+
+    /// counting pass, return recorded lengths and total length
+    fn measure<F>(f: &mut F) -> Result<(Vec<usize>, usize), OcspError>
+    where
+        F: FnMut(&mut DerWriter<'_>) -> Result<(), OcspError>,
+    {
+        let mut w = DerWriter::with_sink(Sink::Count(0), vec![]);
+        f(&mut w)?;
+        let n = w.len();
+        Ok((w.lens, n))
+    }
+
+    /// encode with f into a new buffer of exact size  
+    /// f is called twice, counting then writing, and must write the same both times
+    pub fn encode<F>(f: F) -> Result<Bytes, OcspError>
+    where
+        F: FnMut(&mut DerWriter<'_>) -> Result<(), OcspError>,
+    {
+        let mut buf = vec![];
+        DerWriter::encode_into(&mut buf, f)?;
+        Ok(buf)
+    }
+
+    /// encode with f, appending to buf, see [DerWriter::encode]
+    pub fn encode_into<F>(buf: &mut Vec<u8>, mut f: F) -> Result<(), OcspError>
+    where
+        F: FnMut(&mut DerWriter<'_>) -> Result<(), OcspError>,
+    {
+        let (lens, n) = DerWriter::measure(&mut f)?;
+        buf.reserve(n);
+        f(&mut DerWriter::with_sink(Sink::Buffer(buf), lens))
+    }
+
+    /// encode with f to writer, return bytes written, see [DerWriter::encode]  
+    /// writer is not flushed
+    pub fn encode_to<F>(writer: &mut dyn Write, mut f: F) -> Result<usize, OcspError>
+    where
+        F: FnMut(&mut DerWriter<'_>) -> Result<(), OcspError>,
+    {
+        let (lens, _) = DerWriter::measure(&mut f)?;
+        let sink = Sink::Writer {
+            writer,
+            written: 0,
+            err: None,
+        };
+        let mut w = DerWriter::with_sink(sink, lens);
+        f(&mut w)?;
+        match w.sink {
+            Sink::Writer { err: Some(e), .. } => Err(OcspError::GenWriteError(e)),
+            Sink::Writer { written, .. } => Ok(written),
+            _ => unreachable!("sink is a writer"),
+        }
+    }
+
+    /// count bytes f would write, nothing is stored
+    pub fn count<F>(mut f: F) -> Result<usize, OcspError>
+    where
+        F: FnMut(&mut DerWriter<'_>) -> Result<(), OcspError>,
+    {
+        DerWriter::measure(&mut f).map(|(_, n)| n)
+    }
+
+    /// append bytes that are already DER encoded  
+    /// writer errors are kept and returned once encoding ends
+    pub fn write_raw(&mut self, der: &[u8]) {
+        match &mut self.sink {
+            Sink::Count(n) => *n += der.len(),
+            Sink::Buffer(buf) => buf.extend_from_slice(der),
+            Sink::Writer {
+                writer,
+                written,
+                err,
+            } => {
+                if err.is_none() {
+                    if let Err(e) = writer.write_all(der) {
+                        *err = Some(e);
+                    }
+                }
+                *written += der.len();
+            }
+        }
+    }
+
+    /// write a TLV with known value
+    pub fn write_tlv(&mut self, tag: u8, value: &[u8]) -> Result<(), OcspError> {
+        self.write_raw(&[tag]);
+        self.write_length(value.len())?;
+        self.write_raw(value);
+        Ok(())
+    }
+
+    /// write a TLV, value is written by f  
+    /// used for constructed types and explicit tagging
+    pub fn write_tagged<F>(&mut self, tag: u8, f: F) -> Result<(), OcspError>
+    where
+        F: FnOnce(&mut DerWriter<'_>) -> Result<(), OcspError>,
+    {
+        self.write_raw(&[tag]);
+        if let Sink::Count(_) = self.sink {
+            let i = self.lens.len();
+            self.lens.push(0);
+            let start = self.len();
+            f(self)?;
+            let len = self.len() - start;
+            self.lens[i] = len;
+            let (_, n) = encode_length(len)?;
+            if let Sink::Count(c) = &mut self.sink {
+                *c += n;
+            }
+            return Ok(());
+        }
+
+        let len = *self
+            .lens
+            .get(self.next)
+            .ok_or(OcspError::GenEncodeMismatch)?;
+        self.next += 1;
+        self.write_length(len)?;
+        let start = self.len();
+        f(self)?;
+        if self.len() - start != len {
+            return Err(OcspError::GenEncodeMismatch);
+        }
+        Ok(())
+    }
+
+    /// write a SEQUENCE, value is written by f
+    pub fn write_sequence<F>(&mut self, f: F) -> Result<(), OcspError>
+    where
+        F: FnOnce(&mut DerWriter<'_>) -> Result<(), OcspError>,
+    {
+        self.write_tagged(ASN1_SEQUENCE, f)
+    }
+
+    /// write an OCTET STRING
+    pub fn write_octet(&mut self, value: &[u8]) -> Result<(), OcspError> {
+        self.write_tlv(ASN1_OCTET, value)
+    }
+
+    /// write an unsigned INTEGER
+    pub fn write_u32(&mut self, num: u32) -> Result<(), OcspError> {
+        let be = num.to_be_bytes();
+        // minimal encoding, keeping a leading zero if high bit is set
+        let start = be.iter().position(|b| *b != 0).unwrap_or(3);
+        if be[start] & 0x80 != 0 {
+            self.write_raw(&[ASN1_INTEGER, (be.len() - start + 1) as u8, 0x00]);
+        } else {
+            self.write_raw(&[ASN1_INTEGER, (be.len() - start) as u8]);
+        }
+        self.write_raw(&be[start..]);
+        Ok(())
+    }
+
+    /// number of bytes in the underlying buffer, written or counted so far
+    pub fn len(&self) -> usize {
+        match &self.sink {
+            Sink::Count(n) => *n,
+            Sink::Buffer(buf) => buf.len(),
+            Sink::Writer { written, .. } => *written,
+        }
+    }
+
+    /// whether the underlying buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn write_length(&mut self, len: usize) -> Result<(), OcspError> {
+        let (l, n) = encode_length(len)?;
+        self.write_raw(&l[..n]);
+        Ok(())
+    }
+}
+
+/// DER length octets of len and how many are used
+fn encode_length(len: usize) -> Result<([u8; 9], usize), OcspError> {
+    let mut l = [0u8; 9];
+    if len < 0x80 {
+        l[0] = len as u8;
+        return Ok((l, 1));
+    }
+    let be = len.to_be_bytes();
+    let start = be.iter().position(|b| *b != 0).unwrap_or(be.len() - 1);
+    let n = be.len() - start;
+    if n > l.len() - 1 {
+        return Err(OcspError::Asn1LengthOverflow(n));
+    }
+    l[0] = 0x80 + n as u8;
+    l[1..=n].copy_from_slice(&be[start..]);
+    Ok((l, n + 1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // lengths counted in a first pass, nested values keep their place
+    #[test]
+    fn write_tagged_nested() {
+        let v = DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                w.write_octet(&[0x01, 0x02])?;
+                w.write_tagged(0xa0, |w| w.write_octet(&[]))
+            })
+        })
+        .unwrap();
+        assert_eq!(
+            vec![0x30, 0x08, 0x04, 0x02, 0x01, 0x02, 0xa0, 0x02, 0x04, 0x00],
+            v
+        );
+    }
+
+    // long form lengths are known before values are written
+    #[test]
+    fn write_tagged_long_form() {
+        let mut buf = vec![0xff];
+        let f = |w: &mut DerWriter<'_>| {
+            w.write_sequence(|w| {
+                w.write_sequence(|w| w.write_octet(&[0x55; 300]))?;
+                w.write_octet(&[0x66; 2])
+            })
+        };
+        DerWriter::encode_into(&mut buf, f).unwrap();
+        assert_eq!(1 + 4 + 4 + 4 + 300 + 4, buf.len());
+        assert_eq!(
+            [0xff, 0x30, 0x82, 0x01, 0x38, 0x30, 0x82, 0x01, 0x30],
+            buf[..9]
+        );
+        assert_eq!([0x04, 0x82, 0x01, 0x2c, 0x55], buf[9..14]);
+        assert_eq!([0x04, 0x02, 0x66, 0x66], buf[buf.len() - 4..]);
+
+        // written once, buffer never grows past its reservation
+        let mut v = vec![];
+        DerWriter::encode_into(&mut v, f).unwrap();
+        assert_eq!(v.len(), v.capacity());
+
+        let mut out = io::Cursor::new(vec![]);
+        assert_eq!(v.len(), DerWriter::encode_to(&mut out, f).unwrap());
+        assert_eq!(v, out.into_inner());
+    }
+
+    // writer errors surface after encoding
+    #[test]
+    fn write_to_failing_writer() {
+        let mut out = [0u8; 4];
+        let mut out = &mut out[..];
+        let r = DerWriter::encode_to(&mut out, |w| w.write_octet(&[0x01; 8]));
+        assert!(matches!(r, Err(OcspError::GenWriteError(_))));
+    }
+
+    // closure writing differently in its second call is caught
+    #[test]
+    fn write_mismatch() {
+        let mut calls = 0;
+        let r = DerWriter::encode(|w| {
+            calls += 1;
+            w.write_sequence(|w| w.write_octet(&vec![0x01; calls]))
+        });
+        assert!(matches!(r, Err(OcspError::GenEncodeMismatch)));
+    }
+
+    // counting mode agrees with written length, long form included
+    #[test]
+    fn count_matches_encode() {
+        let f = |w: &mut DerWriter<'_>| {
+            w.write_sequence(|w| {
+                w.write_sequence(|w| w.write_octet(&[0x55; 300]))?;
+                w.write_u32(0x80)?;
+                w.write_tagged(0xa0, |w| w.write_octet(&[0x66; 0x7f]))
+            })
+        };
+        let v = DerWriter::encode(f).unwrap();
+        assert_eq!(v.len(), DerWriter::count(f).unwrap());
+    }
+
+    // SEQUENCE OF round trip through blanket Vec impls
+    #[test]
+    fn vec_sequence_of() {
+        use crate::common::asn1::Oid;
+        use crate::oid::{ALGO_SHA1_DOT, ALGO_SHA256_DOT};
+
+        let list = vec![
+            Oid::new_from_dot(ALGO_SHA1_DOT).unwrap(),
+            Oid::new_from_dot(ALGO_SHA256_DOT).unwrap(),
+        ];
+        let v = list.to_der().unwrap();
+        assert_eq!(0x30, v[0]);
+        assert_eq!(v.len(), list.encoded_len().unwrap());
+        assert_eq!(list, Vec::<Oid>::from_der(&v).unwrap());
+        assert!(Vec::<Oid>::from_der(&[0x30, 0x00]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+//! Issuer lookup of CertId
+//! Used by responder matching cid of any hash algorithm against known issuers
+
+use std::collections::HashMap;
+
+use tracing::{debug, trace};
+
+use crate::common::asn1::{asn1_issuer_name_key, Bytes, CertId, CertIdHash, SerialNumber};
+use crate::err::Result;
+
+/// Known issuers indexed by their name and key hashes under every [CertIdHash]  
+/// cid from a client maps to the same `(issuer, serial number)` key whichever hash it uses,  
+/// unlike [CertId] equality, which compares hash algorithm and hashes too
+#[derive(Debug, Clone)]
+pub struct IssuerIndex<K> {
+    /// key hash to hash algorithm, name hash and issuer  
+    /// issuers may share a key under different names
+    issuers: HashMap<Bytes, Vec<(CertIdHash, Bytes, K)>>,
+    len: usize,
+}
+
+impl<K> Default for IssuerIndex<K> {
+    fn default() -> Self {
+        IssuerIndex {
+            issuers: HashMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<K: Clone> IssuerIndex<K> {
+    /// return an empty index
+    pub fn new() -> Self {
+        IssuerIndex::default()
+    }
+
+    /// add issuer certificate in DER under key  
+    /// return previous key if the issuer was already known
+    pub fn insert(&mut self, key: K, issuer: &[u8]) -> Result<Option<K>> {
+        let (name, pubkey) = asn1_issuer_name_key(issuer)?;
+        let mut prev = None;
+        for hash in CertIdHash::ALL.iter() {
+            let name_hash = hash.digest(name);
+            let key_hash = hash.digest(pubkey);
+            trace!(
+                "Indexing issuer with {:?} name hash {} and key hash {}",
+                hash,
+                hex::encode(&name_hash),
+                hex::encode(&key_hash)
+            );
+            let list = self.issuers.entry(key_hash).or_default();
+            match list.iter_mut().find(|i| i.0 == *hash && i.1 == name_hash) {
+                Some(i) => prev = Some(std::mem::replace(&mut i.2, key.clone())),
+                None => list.push((*hash, name_hash, key.clone())),
+            }
+        }
+        if prev.is_none() {
+            self.len += 1;
+        }
+        debug!("{} issuers in index", self.len);
+        Ok(prev)
+    }
+
+    /// key of the issuer of cid, None if hash algorithm or issuer is unknown
+    pub fn issuer(&self, cid: &CertId) -> Option<&K> {
+        let hash = CertIdHash::from_oid(&cid.hash_algo.oid)?;
+        let r = self
+            .issuers
+            .get(&cid.issuer_key_hash)?
+            .iter()
+            .find(|i| i.0 == hash && i.1 == cid.issuer_name_hash)
+            .map(|i| &i.2);
+        if r.is_none() {
+            debug!("No known issuer of cid with sn {}", cid.serial_num);
+        }
+        r
+    }
+
+    /// canonical `(issuer, serial number)` key of cid, see [IssuerIndex::issuer]
+    pub fn key(&self, cid: &CertId) -> Option<(K, SerialNumber)> {
+        self.issuer(cid)
+            .map(|k| (k.clone(), cid.serial_num.clone()))
+    }
+
+    /// number of known issuers
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// whether no issuer is known
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::common::asn1::{AlgorithmIdentifier, CertId, CertIdHash, Oid, SerialNumber};
+    use crate::verify::test::{CA_CERT, LEAF_CERT};
+
+    use super::IssuerIndex;
+
+    fn der(pem: &str) -> Vec<u8> {
+        pem_rfc7468::decode_vec(pem.as_bytes()).unwrap().1
+    }
+
+    // cid of any hash maps to the same key
+    #[test]
+    fn issuer_index_key() {
+        let ca = der(CA_CERT);
+        let leaf = der(LEAF_CERT);
+        let mut index = IssuerIndex::new();
+        assert!(index.is_empty());
+        assert_eq!(None, index.insert("ca", &ca).unwrap());
+        assert_eq!(1, index.len());
+
+        let sn = SerialNumber::from_hex("1234").unwrap();
+        for hash in CertIdHash::ALL.iter() {
+            let cid = CertId::from_certificates(&ca, &leaf, *hash).unwrap();
+            assert_eq!(Some(("ca", sn.clone())), index.key(&cid));
+        }
+
+        // responder status keyed by issuer and serial number
+        let mut status = HashMap::new();
+        status.insert(("ca", sn.clone()), "good");
+        let cid = CertId::from_certificates(&ca, &leaf, CertIdHash::Sha384).unwrap();
+        assert_eq!(Some(&"good"), status.get(&index.key(&cid).unwrap()));
+
+        // same serial number from another issuer
+        let cid = CertId::from_certificates(&leaf, &leaf, CertIdHash::Sha1).unwrap();
+        assert_eq!(None, index.key(&cid));
+        assert_eq!(None, index.insert("leaf", &leaf).unwrap());
+        assert_eq!(Some(("leaf", sn)), index.key(&cid));
+        assert_eq!(2, index.len());
+
+        // same issuer again
+        assert_eq!(Some("ca"), index.insert("ca2", &ca).unwrap());
+        assert_eq!(2, index.len());
+    }
+
+    // unsupported hash, unknown name or malformed certificate
+    #[test]
+    fn issuer_index_unknown() {
+        let ca = der(CA_CERT);
+        let mut index = IssuerIndex::new();
+        index.insert(1, &ca).unwrap();
+
+        let sn = SerialNumber::from_hex("1234").unwrap();
+        let cid = CertId::from_issuer_and_serial(&ca, sn, CertIdHash::Sha1).unwrap();
+        assert_eq!(Some(&1), index.issuer(&cid));
+
+        // md5
+        let mut md5 = cid.clone();
+        md5.hash_algo = AlgorithmIdentifier::new(Oid::new_from_dot("1.2.840.113549.2.5").unwrap());
+        assert_eq!(None, index.issuer(&md5));
+
+        // same key under another name
+        let mut renamed = cid;
+        renamed.issuer_name_hash[0] ^= 0x01;
+        assert_eq!(None, index.issuer(&renamed));
+
+        assert!(index.insert(2, &ca[..20]).is_err());
+        assert_eq!(1, index.len());
+    }
+}
@@ -1,3 +1,6 @@
 //! Common components in ASN.1 and OCSP
 pub mod asn1;
+pub mod der;
+pub mod issuer;
+pub mod name;
 pub mod ocsp;
@@ -0,0 +1,425 @@
+//! X.500 Name
+//! Used by responder id by name and requestor name
+
+use std::fmt;
+use std::str::FromStr;
+
+use asn1_der::DerObject;
+use tracing::{error, trace};
+
+use crate::common::asn1::{
+    asn1_decode_children, asn1_encode_length, Bytes, TryIntoSequence, ASN1_IA5STRING, ASN1_OID,
+};
+use crate::common::der::{DerDecode, DerEncode, DerWriter};
+use crate::err::{OcspError, Result};
+use crate::oid::{b2d_oid, d2b_oid};
+
+/// ASN.1 set
+pub(crate) const ASN1_SET: u8 = 0x31;
+/// ASN.1 utf8string
+pub(crate) const ASN1_UTF8STRING: u8 = 0x0c;
+/// ASN.1 printablestring
+pub(crate) const ASN1_PRINTABLESTRING: u8 = 0x13;
+/// ASN.1 teletexstring
+pub(crate) const ASN1_TELETEXSTRING: u8 = 0x14;
+/// ASN.1 bmpstring
+pub(crate) const ASN1_BMPSTRING: u8 = 0x1e;
+
+/// RFC 4514 short names and their dot notation
+const DN_SHORT_NAMES: [(&str, &str); 9] = [
+    ("CN", "2.5.4.3"),
+    ("L", "2.5.4.7"),
+    ("ST", "2.5.4.8"),
+    ("O", "2.5.4.10"),
+    ("OU", "2.5.4.11"),
+    ("C", "2.5.4.6"),
+    ("STREET", "2.5.4.9"),
+    ("DC", "0.9.2342.19200300.100.1.25"),
+    ("UID", "0.9.2342.19200300.100.1.1"),
+];
+
+/// X.500 AttributeTypeAndValue
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AttributeTypeAndValue {
+    /// attribute type, oid bytes without tag and length
+    pub attr_type: Bytes,
+    /// ASN.1 tag of the value, eg. UTF8String or PrintableString
+    pub tag: u8,
+    /// attribute value without tag and length
+    pub value: Bytes,
+}
+
+impl AttributeTypeAndValue {
+    /// get attribute from raw bytes
+    pub fn parse(atv: &[u8]) -> Result<Self> {
+        let s = atv.try_into()?;
+        if s.len() != 2 {
+            error!("Provided attribute contains {} items, expecting 2", s.len());
+            return Err(OcspError::Asn1LengthError("AttributeTypeAndValue"));
+        }
+
+        let oid = s.get(0).map_err(OcspError::Asn1DecodingError)?;
+        if oid.tag() != ASN1_OID {
+            return Err(OcspError::Asn1MismatchError("AttributeTypeAndValue"));
+        }
+        let val = s.get(1).map_err(OcspError::Asn1DecodingError)?;
+
+        Ok(AttributeTypeAndValue {
+            attr_type: oid.value().to_vec(),
+            tag: val.tag(),
+            value: val.value().to_vec(),
+        })
+    }
+
+    /// encode to ASN.1 DER
+    pub fn to_der(&self) -> Result<Bytes> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+
+    /// short name defined in RFC 4514, if any
+    fn short_name(&self) -> Option<&'static str> {
+        DN_SHORT_NAMES
+            .iter()
+            .find(|(_, dot)| d2b_oid(dot).as_deref() == Some(&self.attr_type[..]))
+            .map(|(name, _)| *name)
+    }
+
+    /// value as string if it is a known string type
+    fn value_string(&self) -> Option<String> {
+        match self.tag {
+            ASN1_UTF8STRING | ASN1_PRINTABLESTRING | ASN1_IA5STRING | ASN1_TELETEXSTRING => {
+                String::from_utf8(self.value.clone()).ok()
+            }
+            ASN1_BMPSTRING => {
+                if !self.value.len().is_multiple_of(2) {
+                    return None;
+                }
+                let units: Vec<u16> = self
+                    .value
+                    .chunks(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                    .collect();
+                String::from_utf16(&units).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl DerEncode for AttributeTypeAndValue {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
+        w.write_sequence(|w| {
+            w.write_tlv(ASN1_OID, &self.attr_type)?;
+            w.write_tlv(self.tag, &self.value)
+        })
+    }
+}
+
+impl<'a> DerDecode<'a> for AttributeTypeAndValue {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        AttributeTypeAndValue::parse(raw)
+    }
+}
+
+impl fmt::Display for AttributeTypeAndValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // dotted types and non string values use hex form of BER encoding
+        match (self.short_name(), self.value_string()) {
+            (Some(name), Some(val)) => write!(f, "{}={}", name, dn_escape(&val)),
+            (name, _) => {
+                let name = match name {
+                    Some(n) => n.to_string(),
+                    None => {
+                        b2d_oid(&self.attr_type).unwrap_or_else(|| hex::encode(&self.attr_type))
+                    }
+                };
+                let mut ber = vec![self.tag];
+                ber.extend(asn1_encode_length(self.value.len()).map_err(|_| fmt::Error)?);
+                ber.extend(&self.value);
+                write!(f, "{}=#{}", name, hex::encode(ber))
+            }
+        }
+    }
+}
+
+/// X.500 Name, RDNSequence
+/// each RDN is a SET OF AttributeTypeAndValue
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DistinguishedName {
+    /// RDNs in ASN.1 order, which is the reverse of RFC 4514 string order
+    pub rdns: Vec<Vec<AttributeTypeAndValue>>,
+}
+
+impl DistinguishedName {
+    /// get name from raw bytes
+    pub fn parse(name: &[u8]) -> Result<Self> {
+        trace!("Parsing name {}", hex::encode(name));
+        let s = name.try_into()?;
+
+        let mut rdns = vec![];
+        for i in 0..s.len() {
+            let set = s.get(i).map_err(OcspError::Asn1DecodingError)?;
+            if set.tag() != ASN1_SET {
+                error!("Provided rdn tag is {}, expecting 0x31", set.tag());
+                return Err(OcspError::Asn1MismatchError("RelativeDistinguishedName"));
+            }
+            let mut rdn = vec![];
+            for atv in asn1_decode_children(set.value())? {
+                rdn.push(AttributeTypeAndValue::parse(atv.raw())?);
+            }
+            if rdn.is_empty() {
+                return Err(OcspError::Asn1LengthError("RelativeDistinguishedName"));
+            }
+            rdns.push(rdn);
+        }
+
+        trace!("Name {} successfully decoded", hex::encode(name));
+        Ok(DistinguishedName { rdns })
+    }
+
+    /// encode to ASN.1 DER
+    /// attributes in a multi-valued RDN are sorted as DER requires for SET OF
+    pub fn to_der(&self) -> Result<Bytes> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for DistinguishedName {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
+        w.write_sequence(|w| {
+            for rdn in &self.rdns {
+                w.write_tagged(ASN1_SET, |w| {
+                    if let [atv] = &rdn[..] {
+                        return atv.write_der(w);
+                    }
+                    // SET OF is sorted by encoding
+                    let mut atvs = vec![];
+                    for atv in rdn {
+                        atvs.push(atv.to_der()?);
+                    }
+                    atvs.sort();
+                    atvs.iter().for_each(|a| w.write_raw(a));
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<'a> DerDecode<'a> for DistinguishedName {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        DistinguishedName::parse(raw)
+    }
+}
+
+impl fmt::Display for DistinguishedName {
+    /// RFC 4514 string representation
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, rdn) in self.rdns.iter().rev().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            for (j, atv) in rdn.iter().enumerate() {
+                if j > 0 {
+                    write!(f, "+")?;
+                }
+                write!(f, "{}", atv)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DistinguishedName {
+    type Err = OcspError;
+
+    /// parse RFC 4514 string representation
+    fn from_str(dn: &str) -> Result<Self> {
+        trace!("Parsing name string {}", dn);
+        let mut rdns = vec![];
+        if dn.trim().is_empty() {
+            return Ok(DistinguishedName { rdns });
+        }
+
+        for rdn in dn_split(dn, ',')? {
+            let mut atvs = vec![];
+            for atv in dn_split(rdn, '+')? {
+                atvs.push(dn_parse_atv(atv)?);
+            }
+            rdns.push(atvs);
+        }
+        rdns.reverse();
+
+        Ok(DistinguishedName { rdns })
+    }
+}
+
+/// escape attribute value as RFC 4514 2.4
+fn dn_escape(val: &str) -> String {
+    let mut r = String::new();
+    let last = val.chars().count().saturating_sub(1);
+    for (i, c) in val.chars().enumerate() {
+        match c {
+            '"' | '+' | ',' | ';' | '<' | '>' | '\\' => {
+                r.push('\\');
+                r.push(c);
+            }
+            '#' if i == 0 => r.push_str("\\#"),
+            ' ' if i == 0 || i == last => r.push_str("\\ "),
+            '\0' => r.push_str("\\00"),
+            _ => r.push(c),
+        }
+    }
+    r
+}
+
+/// split on unescaped separator
+fn dn_split(dn: &str, sep: char) -> Result<Vec<&str>> {
+    let mut r = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in dn.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == sep {
+            r.push(&dn[start..i]);
+            start = i + 1;
+        }
+    }
+    if escaped {
+        return Err(OcspError::GenInvalidDn(dn.to_string()));
+    }
+    r.push(&dn[start..]);
+    Ok(r)
+}
+
+/// parse one type=value pair
+fn dn_parse_atv(atv: &str) -> Result<AttributeTypeAndValue> {
+    let invalid = || OcspError::GenInvalidDn(atv.to_string());
+    let (name, val) = atv.split_once('=').ok_or_else(invalid)?;
+    let name = name.trim();
+
+    let (attr_type, short) = match DN_SHORT_NAMES
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+    {
+        Some((n, dot)) => (d2b_oid(dot).ok_or_else(invalid)?, Some(*n)),
+        None => (d2b_oid(name).ok_or_else(invalid)?, None),
+    };
+
+    // hex form of BER encoding
+    if let Some(h) = val.strip_prefix('#') {
+        let ber = hex::decode(h).map_err(|_| invalid())?;
+        let obj = DerObject::decode(&ber).map_err(|_| invalid())?;
+        if obj.raw().len() != ber.len() {
+            return Err(invalid());
+        }
+        return Ok(AttributeTypeAndValue {
+            attr_type,
+            tag: obj.tag(),
+            value: obj.value().to_vec(),
+        });
+    }
+
+    let value = dn_unescape(val).ok_or_else(invalid)?;
+    let tag = match short {
+        Some("C") => ASN1_PRINTABLESTRING,
+        Some("DC") => ASN1_IA5STRING,
+        _ => ASN1_UTF8STRING,
+    };
+    Ok(AttributeTypeAndValue {
+        attr_type,
+        tag,
+        value,
+    })
+}
+
+/// unescape attribute value, both `\c` and `\HH` forms
+fn dn_unescape(val: &str) -> Option<Bytes> {
+    let b = val.as_bytes();
+    let mut r = vec![];
+    let mut i = 0;
+    while i < b.len() {
+        if b[i] != b'\\' {
+            r.push(b[i]);
+            i += 1;
+            continue;
+        }
+        let next = *b.get(i + 1)?;
+        if next.is_ascii_hexdigit() {
+            let pair = std::str::from_utf8(b.get(i + 1..i + 3)?).ok()?;
+            r.push(u8::from_str_radix(pair, 16).ok()?);
+            i += 3;
+        } else {
+            r.push(next);
+            i += 2;
+        }
+    }
+    // `\HH` may produce multi-byte chars, validate afterwards
+    String::from_utf8(r).ok().map(String::into_bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // example name in responder id by name
+    const OPENSSL_NAME_HEX: &str = "3054310b3009060355040613024155\
+    3113301106035504080c0a536f6d652d\
+    53746174653121301f060355040a0c18\
+    496e7465726e65742057696467697473\
+    20507479204c7464310d300b06035504\
+    030c044f435350";
+
+    /// name from der to string
+    #[test]
+    fn name_parse_to_string() {
+        let name_v8 = hex::decode(OPENSSL_NAME_HEX).unwrap();
+        let name = DistinguishedName::parse(&name_v8[..]).unwrap();
+        assert_eq!(
+            "CN=OCSP,O=Internet Widgits Pty Ltd,ST=Some-State,C=AU",
+            name.to_string()
+        );
+        assert_eq!(name_v8, name.to_der().unwrap());
+    }
+
+    /// name from string to der
+    #[test]
+    fn name_from_str_to_der() {
+        let name: DistinguishedName = "CN=OCSP,O=Internet Widgits Pty Ltd,ST=Some-State,C=AU"
+            .parse()
+            .unwrap();
+        let name_v8 = hex::decode(OPENSSL_NAME_HEX).unwrap();
+        assert_eq!(name_v8, name.to_der().unwrap());
+    }
+
+    /// escaping, multi-valued rdn and dotted type
+    #[test]
+    fn name_string_escape() {
+        let dn = "CN=Smith\\, James+UID=jsmith,1.2.3.4=#0c0474657374,O=\\#1\\20";
+        let name: DistinguishedName = dn.parse().unwrap();
+        assert_eq!(name.rdns.len(), 3);
+        assert_eq!(name.rdns[2].len(), 2);
+        assert_eq!(name.rdns[2][0].value, b"Smith, James".to_vec());
+        assert_eq!(name.rdns[0][0].value, b"#1 ".to_vec());
+        assert_eq!(
+            "CN=Smith\\, James+UID=jsmith,1.2.3.4=#0c0474657374,O=\\#1\\ ",
+            name.to_string()
+        );
+
+        let name_v8 = name.to_der().unwrap();
+        assert_eq!(name, DistinguishedName::parse(&name_v8[..]).unwrap());
+    }
+
+    /// malformed strings
+    #[test]
+    fn name_string_invalid() {
+        assert!("CN".parse::<DistinguishedName>().is_err());
+        assert!("XX=1".parse::<DistinguishedName>().is_err());
+        assert!("CN=a\\".parse::<DistinguishedName>().is_err());
+        assert!("1.2.3=#zz".parse::<DistinguishedName>().is_err());
+    }
+}
@@ -1,21 +1,86 @@
 //! Common ocsp components
 
+use std::convert::TryFrom;
+
 use asn1_der::DerObject;
 use tracing::{error, trace};
 
 use crate::common::asn1::{
-    asn1_encode_bit_string, TryIntoSequence, ASN1_BIT_STRING, ASN1_EXPLICIT_0, ASN1_EXPLICIT_1, ASN1_EXPLICIT_2, ASN1_INTEGER, ASN1_NULL, ASN1_OCTET, ASN1_OID
+    asn1_offset, asn1_validate_der, AlgorithmIdentifier, DecodeMode, GeneralizedTime, Oid,
+    TryIntoSequence, ASN1_BOOLEAN, ASN1_ENUMERATED, ASN1_EXPLICIT_0, ASN1_EXPLICIT_1,
+    ASN1_EXPLICIT_2, ASN1_GENERALIZED_TIME, ASN1_IA5STRING, ASN1_INTEGER, ASN1_OCTET, ASN1_OID,
+    ASN1_OID_PADDING,
 };
+use crate::common::name::DistinguishedName;
+use crate::response::CrlReason;
 use crate::{err::OcspError, oid::*};
 
-use super::asn1::{asn1_encode_length, asn1_encode_octet, ASN1_SEQUENCE};
-use crate::common::asn1::Bytes;
+use crate::common::asn1::{Bytes, ASN1_SEQUENCE};
+use crate::common::der::{DerDecode, DerEncode, DerWriter};
+
+/// RFC 6960 Version  
+/// only v1 is defined, which is also the DEFAULT
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Version {
+    /// v1(0)
+    #[default]
+    V1 = 0u8,
+}
+
+impl Version {
+    /// get version from raw bytes  
+    /// - without explicit 0 tagging
+    pub fn parse(ver: &[u8]) -> Result<Self, OcspError> {
+        trace!("Parsing version {}", hex::encode(ver));
+        let int = DerObject::decode(ver).map_err(OcspError::Asn1DecodingError)?;
+        if int.tag() != ASN1_INTEGER {
+            error!("Provided version tag is {}, expecting 0x02", int.tag());
+            return Err(OcspError::Asn1MismatchError("Version"));
+        }
+
+        let val = int.value();
+        if val.is_empty() || val.len() > 8 {
+            error!("Provided version contains {} bytes", val.len());
+            return Err(OcspError::Asn1LengthError("Version"));
+        }
+        // sign extend to i64
+        let init = if val[0] & 0x80 == 0 { 0i64 } else { -1i64 };
+        let num = val.iter().fold(init, |acc, b| (acc << 8) | *b as i64);
+
+        match num {
+            0 => Ok(Version::V1),
+            _ => {
+                error!("Version {} is not supported", num);
+                Err(OcspError::OcspUnsupportedVersion(num))
+            }
+        }
+    }
+
+    /// encode to ASN.1 DER  
+    /// - with explicit 0 tagging  
+    /// - DEFAULT v1 is omitted as required by DER, returning empty bytes
+    pub fn to_der(&self) -> Result<Bytes, OcspError> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for Version {
+    fn write_der(&self, _w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        match self {
+            Version::V1 => Ok(()),
+        }
+    }
+}
 
 /// OCSP extension with internal id
 #[derive(Debug, Clone)]
 pub struct OcspExtI {
-    /// internal id of extension, see const in [crate::oid]
+    /// internal id of extension, see const in [crate::oid]  
+    /// [OCSP_EXT_UNKNOWN_ID] if not recognized
     pub id: usize,
+    /// critical BOOLEAN DEFAULT FALSE
+    pub critical: bool,
     /// extension variant
     pub ext: OcspExt,
 }
@@ -25,6 +90,12 @@ impl OcspExtI {
     /// raw is a sequence of multiple extensions  
     /// remove explicit and implicit tags first
     pub fn parse(raw: &[u8]) -> Result<Vec<Self>, OcspError> {
+        OcspExtI::parse_with_mode(raw, DecodeMode::Lenient)
+    }
+
+    /// parse ocsp extension, see [OcspExtI::parse]  
+    /// in strict mode, explicit FALSE critical and non DER extension value are rejected
+    pub(crate) fn parse_with_mode(raw: &[u8], mode: DecodeMode) -> Result<Vec<Self>, OcspError> {
         trace!("Parsing extension list");
 
         let mut r: Vec<OcspExtI> = Vec::new();
@@ -32,44 +103,100 @@ impl OcspExtI {
         let list = raw.try_into()?;
         for i in 0..list.len() {
             let ext = list.get(i).map_err(OcspError::Asn1DecodingError)?;
-            let (id, ext) = OcspExt::parse_oneext(ext.raw())?;
-            r.push(OcspExtI { id, ext });
+            let (id, critical, ext) = OcspExt::parse_oneext(ext.raw(), mode)
+                .map_err(|e| e.at(&format!("[{}]", i), asn1_offset(raw, ext.raw())))?;
+            r.push(OcspExtI { id, critical, ext });
         }
 
         trace!("{} extensions successfully decoded", r.len());
         Ok(r)
     }
 
-    /// encode a list of extensions, wrapped in explicit tag
-    pub fn list_to_der(ext_list: &[OcspExtI], exp_tag: u8) -> Result<Bytes, OcspError> {
-        trace!("Encoding {} ext, with tag {:02x?}", ext_list.len(), exp_tag);
-        trace!("Ext list: {:?}", ext_list);
+    /// encode one extension with its critical flag
+    pub fn to_der(&self) -> Result<Bytes, OcspError> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+
+    /// whether the extension is critical but not recognized  
+    /// per RFC 6960 4.1.2, such request must be rejected
+    pub fn is_unrecognized_critical(&self) -> bool {
+        self.critical && matches!(self.ext, OcspExt::Unknown { .. })
+    }
+}
 
-        // in req and resp, extensions are labelled either 0, 1, 2
-        match exp_tag {
-            ASN1_EXPLICIT_0 | ASN1_EXPLICIT_1 | ASN1_EXPLICIT_2 => {}
-            _ => {
-                return Err(OcspError::OcspUndefinedTagging);
-            }
+/// one extension with its critical flag  
+/// a list of extensions is `Vec<OcspExtI>`
+impl DerEncode for OcspExtI {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        self.ext.write_der_critical(self.critical, w)
+    }
+}
+
+/// one extension with its critical flag  
+/// a list of extensions is `Vec<OcspExtI>`
+impl<'a> DerDecode<'a> for OcspExtI {
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError> {
+        let (id, critical, ext) = OcspExt::parse_oneext(raw, DecodeMode::Lenient)?;
+        Ok(OcspExtI { id, critical, ext })
+    }
+}
+
+/// RFC 6960 4.4.7.1 PreferredSignatureAlgorithm
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreferredSignatureAlgorithm {
+    /// sigIdentifier
+    pub sig_identifier: AlgorithmIdentifier,
+    /// certIdentifier OPTIONAL, public key algorithm
+    pub cert_identifier: Option<AlgorithmIdentifier>,
+}
+
+impl PreferredSignatureAlgorithm {
+    /// get preferred signature algorithm from raw bytes
+    pub fn parse(pref: &[u8]) -> Result<Self, OcspError> {
+        let s = pref.try_into()?;
+        if !(1..=2).contains(&s.len()) {
+            error!(
+                "Provided preferred signature algorithm contains {} items, expecting 1 or 2",
+                s.len()
+            );
+            return Err(OcspError::Asn1LengthError("PreferredSignatureAlgorithm"));
         }
 
-        let mut v = vec![];
-        for e in ext_list {
-            v.extend(e.ext.to_der()?)
+        let sig = s.get(0).map_err(OcspError::Asn1DecodingError)?;
+        let sig_identifier = AlgorithmIdentifier::parse(sig.raw())?;
+        let mut cert_identifier = None;
+        if s.len() == 2 {
+            let cert = s.get(1).map_err(OcspError::Asn1DecodingError)?;
+            cert_identifier = Some(AlgorithmIdentifier::parse(cert.raw())?);
         }
 
-        let len = asn1_encode_length(v.len())?;
-        let mut r = vec![ASN1_SEQUENCE];
-        r.extend(len);
-        r.extend(v);
+        Ok(PreferredSignatureAlgorithm {
+            sig_identifier,
+            cert_identifier,
+        })
+    }
 
-        let mut exp = vec![exp_tag];
-        let len = asn1_encode_length(r.len())?;
-        exp.extend(len);
-        exp.extend(r);
+    /// encode to ASN.1 DER
+    pub fn to_der(&self) -> Result<Bytes, OcspError> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
 
-        trace!("Ext list successfully encoded");
-        Ok(exp)
+impl DerEncode for PreferredSignatureAlgorithm {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        w.write_sequence(|w| {
+            self.sig_identifier.write_der(w)?;
+            if let Some(c) = &self.cert_identifier {
+                c.write_der(w)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<'a> DerDecode<'a> for PreferredSignatureAlgorithm {
+    fn from_der(raw: &'a [u8]) -> Result<Self, OcspError> {
+        PreferredSignatureAlgorithm::parse(raw)
     }
 }
 
@@ -83,8 +210,7 @@ pub enum OcspExt {
     },
     /// 4.4.8
     ExtendedRevocation,
-    /// 4.4.2  
-    /// REVIEW: untested
+    /// 4.4.2
     CrlRef {
         /// EXPLICIT 0 IA5String OPTIONAL
         url: Option<Bytes>,
@@ -93,34 +219,117 @@ pub enum OcspExt {
         /// EXPLICIT 2 GeneralizedTime OPTIONAL
         time: Option<Bytes>,
     },
+    /// 4.4.3
+    AcceptableResponses {
+        /// SEQUENCE OF OBJECT IDENTIFIER
+        response_types: Vec<Oid>,
+    },
+    /// 4.4.4
+    ArchiveCutoff {
+        /// GeneralizedTime
+        time: GeneralizedTime,
+    },
+    /// RFC 5280 5.3.1 CRL entry extension, used in single response
+    CrlReason {
+        /// ENUMERATED
+        reason: CrlReason,
+    },
+    /// RFC 5280 5.3.2 CRL entry extension, used in single response
+    InvalidityDate {
+        /// GeneralizedTime
+        time: GeneralizedTime,
+    },
+    /// 4.4.6
+    ServiceLocator {
+        /// issuer Name
+        issuer: DistinguishedName,
+        /// AuthorityInfoAccessSyntax OPTIONAL, in DER
+        locator: Option<Bytes>,
+    },
+    /// 4.4.7
+    PreferredSignatureAlgorithms {
+        /// SEQUENCE OF PreferredSignatureAlgorithm
+        algorithms: Vec<PreferredSignatureAlgorithm>,
+    },
+    /// extension not recognized, kept as is
+    Unknown {
+        /// extnID
+        oid: Oid,
+        /// critical BOOLEAN DEFAULT FALSE
+        critical: bool,
+        /// extnValue content
+        value: Bytes,
+    },
 }
 
 impl OcspExt {
-    /// pass in each sequence of extension, return OcspExt
-    fn parse_oneext(oneext: &[u8]) -> Result<(usize, Self), OcspError> {
+    /// pass in each sequence of extension, return internal id, critical and OcspExt
+    fn parse_oneext(oneext: &[u8], mode: DecodeMode) -> Result<(usize, bool, Self), OcspError> {
         trace!("Parsing single extension {}", hex::encode(oneext));
         let oneext = oneext.try_into()?;
 
+        let len = oneext.len();
+        if !(2..=3).contains(&len) {
+            error!(
+                "Provided extension contains {} items, expecting 2 or 3",
+                len
+            );
+            return Err(OcspError::Asn1LengthError("Extension"));
+        }
+
         let oid = oneext.get(0).map_err(OcspError::Asn1DecodingError)?;
         if oid.tag() != ASN1_OID {
             return Err(OcspError::Asn1MismatchError("OID"));
         }
         let val = oid.value();
-        // translate oid
-        let ext_id = match b2i_oid(val) {
-            None => return Err(OcspError::Asn1OidUnknown),
+
+        // critical BOOLEAN DEFAULT FALSE sits in between
+        let mut critical = false;
+        if len == 3 {
+            let c = oneext.get(1).map_err(OcspError::Asn1DecodingError)?;
+            if c.tag() != ASN1_BOOLEAN || c.value().len() != 1 {
+                return Err(OcspError::Asn1MismatchError("Extension critical"));
+            }
+            critical = c.value()[0] != 0;
+            if mode.is_strict() && !critical {
+                error!("Found explicit FALSE critical in extension");
+                return Err(OcspError::Asn1ExplicitDefault("critical"));
+            }
+        }
+        let octet = oneext.get(len - 1).map_err(OcspError::Asn1DecodingError)?;
+        if octet.tag() != ASN1_OCTET {
+            return Err(OcspError::Asn1MismatchError("Extension value"));
+        }
+        let value = octet.value();
+
+        // translate oid, well-known oid other than extensions are unknown here
+        let ext_id = match b2i_oid(val).filter(|id| OCSP_EXT_ID_LIST.contains(id)) {
             Some(v) => v,
+            None => {
+                trace!(
+                    "Found unknown extension {}, critical {}",
+                    hex::encode(val),
+                    critical
+                );
+                let r = OcspExt::Unknown {
+                    oid: Oid::from_bytes(val)?,
+                    critical,
+                    value: value.to_vec(),
+                };
+                return Ok((OCSP_EXT_UNKNOWN_ID, critical, r));
+            }
         };
 
+        // nonce is opaque, some clients send it without inner octet header
+        if mode.is_strict() && ext_id != OCSP_EXT_NONCE_ID {
+            asn1_validate_der(value)?;
+        }
+
         let r = match ext_id {
             OCSP_EXT_NONCE_ID => {
                 trace!("Found nonce extension");
                 OcspExt::Nonce {
-                    nonce: oneext
-                        .get(1)
-                        .map_err(OcspError::Asn1DecodingError)?
-                        .value()
-                        .to_vec(),
+                    nonce: value.to_vec(),
                 }
             }
             OCSP_EXT_EXTENDED_REVOKE_ID => {
@@ -132,27 +341,16 @@ impl OcspExt {
                 let mut url = None;
                 let mut num = None;
                 let mut time = None;
-                for i in 1..oneext.len() {
-                    let tmp = oneext.get(i).map_err(OcspError::Asn1DecodingError)?;
-                    let val = match tmp.tag() {
-                        ASN1_EXPLICIT_0..=ASN1_EXPLICIT_2 => tmp.value(),
-                        _ => return Err(OcspError::Asn1MismatchError("Ext CrlRef")),
-                    };
-                    match tmp.tag() {
-                        ASN1_EXPLICIT_0 => {
-                            let val =
-                                DerObject::decode(val).map_err(OcspError::Asn1DecodingError)?;
-                            url = Some(val.value().to_vec());
-                        }
-                        ASN1_EXPLICIT_1 => {
-                            let val =
-                                DerObject::decode(val).map_err(OcspError::Asn1DecodingError)?;
-                            num = Some(val.value().to_vec());
-                        }
-                        ASN1_EXPLICIT_2 => {
-                            let val =
-                                DerObject::decode(val).map_err(OcspError::Asn1DecodingError)?;
-                            time = Some(val.value().to_vec());
+                let crlid = value.try_into()?;
+                for i in 0..crlid.len() {
+                    let tmp = crlid.get(i).map_err(OcspError::Asn1DecodingError)?;
+                    let val =
+                        DerObject::decode(tmp.value()).map_err(OcspError::Asn1DecodingError)?;
+                    match (tmp.tag(), val.tag()) {
+                        (ASN1_EXPLICIT_0, ASN1_IA5STRING) => url = Some(val.value().to_vec()),
+                        (ASN1_EXPLICIT_1, ASN1_INTEGER) => num = Some(val.value().to_vec()),
+                        (ASN1_EXPLICIT_2, ASN1_GENERALIZED_TIME) => {
+                            time = Some(val.value().to_vec())
                         }
                         _ => return Err(OcspError::Asn1MismatchError("Ext CrlRef EXP tag")),
                     }
@@ -160,58 +358,162 @@ impl OcspExt {
 
                 OcspExt::CrlRef { url, num, time }
             }
-            OCSP_EXT_RESP_TYPE_ID
-            | OCSP_EXT_ARCHIVE_CUTOFF_ID
-            | OCSP_EXT_CRL_REASON_ID
-            | OCSP_EXT_INVALID_DATE_ID
-            | OCSP_EXT_SERVICE_LOCATOR_ID
-            | OCSP_EXT_PREF_SIG_ALGS_ID => {
-                unimplemented!()
+            OCSP_EXT_RESP_TYPE_ID => {
+                trace!("Found acceptable responses extension");
+                let list = value.try_into()?;
+                let mut response_types = vec![];
+                for i in 0..list.len() {
+                    let oid = list.get(i).map_err(OcspError::Asn1DecodingError)?;
+                    response_types.push(Oid::parse_raw(oid.raw())?);
+                }
+                OcspExt::AcceptableResponses { response_types }
+            }
+            OCSP_EXT_ARCHIVE_CUTOFF_ID => {
+                trace!("Found archive cutoff extension");
+                OcspExt::ArchiveCutoff {
+                    time: GeneralizedTime::parse(value)?,
+                }
+            }
+            OCSP_EXT_CRL_REASON_ID => {
+                trace!("Found crl reason extension");
+                let re = DerObject::decode(value).map_err(OcspError::Asn1DecodingError)?;
+                if re.tag() != ASN1_ENUMERATED || re.value().len() != 1 {
+                    return Err(OcspError::Asn1MismatchError("Ext CrlReason"));
+                }
+                OcspExt::CrlReason {
+                    reason: CrlReason::try_from(re.value()[0])?,
+                }
+            }
+            OCSP_EXT_INVALID_DATE_ID => {
+                trace!("Found invalidity date extension");
+                OcspExt::InvalidityDate {
+                    time: GeneralizedTime::parse(value)?,
+                }
+            }
+            OCSP_EXT_SERVICE_LOCATOR_ID => {
+                trace!("Found service locator extension");
+                let locator = value.try_into()?;
+                if !(1..=2).contains(&locator.len()) {
+                    return Err(OcspError::Asn1LengthError("Ext ServiceLocator"));
+                }
+                let issuer = locator.get(0).map_err(OcspError::Asn1DecodingError)?;
+                let issuer = DistinguishedName::parse(issuer.raw())?;
+                let mut aia = None;
+                if locator.len() == 2 {
+                    let tmp = locator.get(1).map_err(OcspError::Asn1DecodingError)?;
+                    if tmp.tag() != ASN1_SEQUENCE {
+                        return Err(OcspError::Asn1MismatchError("Ext ServiceLocator"));
+                    }
+                    aia = Some(tmp.raw().to_vec());
+                }
+                OcspExt::ServiceLocator {
+                    issuer,
+                    locator: aia,
+                }
+            }
+            OCSP_EXT_PREF_SIG_ALGS_ID => {
+                trace!("Found preferred signature algorithms extension");
+                let list = value.try_into()?;
+                let mut algorithms = vec![];
+                for i in 0..list.len() {
+                    let pref = list.get(i).map_err(OcspError::Asn1DecodingError)?;
+                    algorithms.push(PreferredSignatureAlgorithm::parse(pref.raw())?);
+                }
+                OcspExt::PreferredSignatureAlgorithms { algorithms }
             }
             _ => return Err(OcspError::OcspExtUnknown),
         };
 
         trace!("One extension successfully decoded");
-        Ok((ext_id, r))
+        Ok((ext_id, critical, r))
     }
 
-    /// encode one extension to ASN.1 DER
+    /// encode one extension to ASN.1 DER  
+    /// - critical is omitted, except for unknown extension
     pub fn to_der(&self) -> Result<Bytes, OcspError> {
-        let mut v = vec![ASN1_SEQUENCE];
-        match &self {
-            OcspExt::Nonce { nonce } => {
-                trace!("Encoding nonce extension");
-                trace!("Nonce {:?}", self);
-                // == OCSP_EXT_HEX_NONCE
-                let mut id = vec![
-                    0x06, 0x09, 0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01, 0x02,
-                ];
-                let nc = asn1_encode_octet(nonce)?;
-                id.extend(nc);
-                let len = asn1_encode_length(id.len())?;
-                v.extend(len);
-                v.extend(id);
-            }
-            OcspExt::ExtendedRevocation => {
-                trace!("Encoding extended revocation extension");
-                // == OCSP_EXT_EXTENDED_REVOKE_HEX
-                let mut id = vec![
-                    0x06, 0x09, 0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01, 0x09,
-                ];
-                let nc = asn1_encode_octet(&[ASN1_NULL])?;
-                id.extend(nc);
-                let len = asn1_encode_length(id.len())?;
-                v.extend(len);
-                v.extend(id);
-            }
-            _ => {
-                error!("Unsupported Extension");
-                unimplemented!()
-            }
+        DerWriter::encode(|w| self.write_der(w))
+    }
+
+    /// write one extension with critical flag
+    fn write_der_critical(&self, critical: bool, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        trace!("Encoding extension {:?}, critical {}", self, critical);
+        let oid: &[u8] = match self {
+            OcspExt::Nonce { .. } => &OCSP_EXT_NONCE_HEX,
+            OcspExt::ExtendedRevocation => &OCSP_EXT_EXTENDED_REVOKE_HEX,
+            OcspExt::CrlRef { .. } => &OCSP_EXT_CRLREF_HEX,
+            OcspExt::AcceptableResponses { .. } => &OCSP_EXT_RESP_TYPE_HEX,
+            OcspExt::ArchiveCutoff { .. } => &OCSP_EXT_ARCHIVE_CUTOFF_HEX,
+            OcspExt::CrlReason { .. } => &OCSP_EXT_CRL_REASON_HEX,
+            OcspExt::InvalidityDate { .. } => &OCSP_EXT_INVALID_DATE_HEX,
+            OcspExt::ServiceLocator { .. } => &OCSP_EXT_SERVICE_LOCATOR_HEX,
+            OcspExt::PreferredSignatureAlgorithms { .. } => &OCSP_EXT_PREF_SIG_ALGS_HEX,
+            OcspExt::Unknown { oid, .. } => oid.as_bytes(),
         };
 
+        w.write_sequence(|w| {
+            w.write_tlv(ASN1_OID, oid)?;
+            if critical {
+                w.write_raw(&[ASN1_BOOLEAN, 0x01, 0xff]);
+            }
+            w.write_tagged(ASN1_OCTET, |w| self.write_value(w))
+        })?;
+
         trace!("Extension successfully encoded");
-        Ok(v)
+        Ok(())
+    }
+
+    /// write extnValue content
+    fn write_value(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        match self {
+            OcspExt::Nonce { nonce } => w.write_raw(nonce),
+            OcspExt::ExtendedRevocation => w.write_raw(&ASN1_OID_PADDING),
+            OcspExt::CrlRef { url, num, time } => w.write_sequence(|w| {
+                for (tag, inner, val) in [
+                    (ASN1_EXPLICIT_0, ASN1_IA5STRING, url),
+                    (ASN1_EXPLICIT_1, ASN1_INTEGER, num),
+                    (ASN1_EXPLICIT_2, ASN1_GENERALIZED_TIME, time),
+                ] {
+                    if let Some(val) = val {
+                        w.write_tagged(tag, |w| w.write_tlv(inner, val))?;
+                    }
+                }
+                Ok(())
+            })?,
+            OcspExt::AcceptableResponses { response_types } => w.write_sequence(|w| {
+                for oid in response_types {
+                    oid.write_der(w)?;
+                }
+                Ok(())
+            })?,
+            OcspExt::ArchiveCutoff { time } | OcspExt::InvalidityDate { time } => {
+                time.write_der(w)?
+            }
+            OcspExt::CrlReason { reason } => w.write_raw(&[ASN1_ENUMERATED, 0x01, *reason as u8]),
+            OcspExt::ServiceLocator { issuer, locator } => w.write_sequence(|w| {
+                issuer.write_der(w)?;
+                if let Some(l) = locator {
+                    w.write_raw(l);
+                }
+                Ok(())
+            })?,
+            OcspExt::PreferredSignatureAlgorithms { algorithms } => w.write_sequence(|w| {
+                for a in algorithms {
+                    a.write_der(w)?;
+                }
+                Ok(())
+            })?,
+            OcspExt::Unknown { value, .. } => w.write_raw(value),
+        }
+        Ok(())
+    }
+}
+
+impl DerEncode for OcspExt {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<(), OcspError> {
+        match self {
+            OcspExt::Unknown { critical, .. } => self.write_der_critical(*critical, w),
+            _ => self.write_der_critical(false, w),
+        }
     }
 }
 
@@ -219,6 +521,27 @@ impl OcspExt {
 mod test {
     use super::*;
 
+    /// explicit v1 is accepted
+    #[test]
+    fn version_v1_parse() {
+        let v = Version::parse(&[0x02, 0x01, 0x00]).unwrap();
+        assert_eq!(Version::V1, v);
+        assert!(v.to_der().unwrap().is_empty());
+    }
+
+    /// unknown version is an error, not a panic
+    #[test]
+    fn version_unknown_parse() {
+        match Version::parse(&[0x02, 0x01, 0x01]) {
+            Err(OcspError::OcspUnsupportedVersion(1)) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+        match Version::parse(&[0x02, 0x01, 0xff]) {
+            Err(OcspError::OcspUnsupportedVersion(-1)) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+    }
+
     /// test ext list to ASN.1 DER
     #[test]
     fn list_nonce_to_der() {
@@ -230,10 +553,12 @@ mod test {
         };
         let nonce = OcspExtI {
             id: OCSP_EXT_NONCE_ID,
+            critical: false,
             ext: nonce,
         };
-        let list = [nonce];
-        let v = OcspExtI::list_to_der(&list, ASN1_EXPLICIT_2).unwrap();
+        let list = vec![nonce];
+        let v =
+            DerWriter::encode(|w| w.write_tagged(ASN1_EXPLICIT_2, |w| list.write_der(w))).unwrap();
         let c = vec![
             0xa2, 0x23, 0x30, 0x21, 0x30, 0x1f, 0x06, 0x09, 0x2B, 0x06, 0x01, 0x05, 0x05, 0x07,
             0x30, 0x01, 0x02, 0x04, 0x12, 0x04, 0x10, 0x5E, 0x7A, 0x74, 0xE5, 0x1C, 0x86, 0x1A,
@@ -262,4 +587,296 @@ mod test {
 
         assert_eq!(c, v);
     }
+
+    /// encode, decode and encode again, expecting identical bytes
+    fn ext_round_trip(ext: OcspExt, id: usize) -> OcspExt {
+        let der = ext.to_der().unwrap();
+        let (i, _, ext) = OcspExt::parse_oneext(&der, DecodeMode::Lenient).unwrap();
+        assert_eq!(id, i);
+        assert_eq!(der, ext.to_der().unwrap());
+        ext
+    }
+
+    /// crl reason with explicit critical flag
+    #[test]
+    fn crl_reason_parse() {
+        let c = hex::decode("300d0603551d150101ff04030a0101").unwrap();
+        let (id, critical, ext) = OcspExt::parse_oneext(&c, DecodeMode::Lenient).unwrap();
+        assert_eq!(OCSP_EXT_CRL_REASON_ID, id);
+        assert!(critical);
+        match ext {
+            OcspExt::CrlReason { reason } => {
+                assert_eq!(CrlReason::OcspRevokeKeyCompromise as u8, reason as u8)
+            }
+            e => panic!("unexpected {:?}", e),
+        }
+        let c = hex::decode("300a0603551d1504030a0101").unwrap();
+        assert_eq!(c, ext.to_der().unwrap());
+    }
+
+    /// extended revocation carries a NULL
+    #[test]
+    fn extended_revocation_to_der() {
+        let v = OcspExt::ExtendedRevocation.to_der().unwrap();
+        let c = hex::decode(
+            "300f06092b0601050507300109040205\
+            00",
+        )
+        .unwrap();
+        assert_eq!(c, v);
+        ext_round_trip(OcspExt::ExtendedRevocation, OCSP_EXT_EXTENDED_REVOKE_ID);
+    }
+
+    /// crl reference inside extnValue
+    #[test]
+    fn crlref_round_trip() {
+        let ext = OcspExt::CrlRef {
+            url: Some(b"http://crl.example.com/ca.crl".to_vec()),
+            num: Some(vec![0x01, 0x00]),
+            time: Some(b"20210101000000Z".to_vec()),
+        };
+        match ext_round_trip(ext, OCSP_EXT_CRLREF_ID) {
+            OcspExt::CrlRef { url, num, time } => {
+                assert_eq!(Some(b"http://crl.example.com/ca.crl".to_vec()), url);
+                assert_eq!(Some(vec![0x01, 0x00]), num);
+                assert_eq!(Some(b"20210101000000Z".to_vec()), time);
+            }
+            e => panic!("unexpected {:?}", e),
+        }
+
+        let ext = OcspExt::CrlRef {
+            url: None,
+            num: Some(vec![0x05]),
+            time: None,
+        };
+        let c = hex::decode(
+            "301406092b060105050730010304073005a1030201\
+            05",
+        )
+        .unwrap();
+        assert_eq!(c, ext.to_der().unwrap());
+    }
+
+    /// acceptable response types
+    #[test]
+    fn acceptable_responses_round_trip() {
+        let ext = OcspExt::AcceptableResponses {
+            response_types: vec![Oid::new_from_dot(OCSP_RESPONSE_BASIC_DOT).unwrap()],
+        };
+        let c = hex::decode(
+            "301a06092b0601050507300104040d300b06092b\
+            0601050507300101",
+        )
+        .unwrap();
+        assert_eq!(c, ext.to_der().unwrap());
+        match ext_round_trip(ext, OCSP_EXT_RESP_TYPE_ID) {
+            OcspExt::AcceptableResponses { response_types } => {
+                assert_eq!(Some(OCSP_RESPONSE_BASIC_ID), response_types[0].index())
+            }
+            e => panic!("unexpected {:?}", e),
+        }
+    }
+
+    /// archive cutoff and invalidity date
+    #[test]
+    fn time_ext_round_trip() {
+        let gt = GeneralizedTime::new(2021, 1, 12, 8, 32, 56).unwrap();
+        let ext = OcspExt::ArchiveCutoff { time: gt };
+        let c = hex::decode(
+            "301e06092b060105050730010604111\
+            80f32303231303131323038333235365a",
+        )
+        .unwrap();
+        assert_eq!(c, ext.to_der().unwrap());
+        ext_round_trip(ext, OCSP_EXT_ARCHIVE_CUTOFF_ID);
+
+        let ext = OcspExt::InvalidityDate { time: gt };
+        match ext_round_trip(ext, OCSP_EXT_INVALID_DATE_ID) {
+            OcspExt::InvalidityDate { time } => {
+                assert_eq!(gt.to_der_utc().unwrap(), time.to_der_utc().unwrap())
+            }
+            e => panic!("unexpected {:?}", e),
+        }
+    }
+
+    /// service locator with and without AIA
+    #[test]
+    fn service_locator_round_trip() {
+        let issuer: DistinguishedName = "CN=Test CA,O=Example,C=US".parse().unwrap();
+        // AuthorityInfoAccessSyntax with one ocsp uri
+        let aia = hex::decode(
+            "3025302306082b06010505073001861768\
+            7474703a2f2f6f6373702e6578616d706c652e636f6d",
+        )
+        .unwrap();
+        let ext = OcspExt::ServiceLocator {
+            issuer: issuer.clone(),
+            locator: Some(aia.clone()),
+        };
+        match ext_round_trip(ext, OCSP_EXT_SERVICE_LOCATOR_ID) {
+            OcspExt::ServiceLocator { issuer: i, locator } => {
+                assert_eq!(issuer.to_string(), i.to_string());
+                assert_eq!(Some(aia), locator);
+            }
+            e => panic!("unexpected {:?}", e),
+        }
+
+        let ext = OcspExt::ServiceLocator {
+            issuer,
+            locator: None,
+        };
+        ext_round_trip(ext, OCSP_EXT_SERVICE_LOCATOR_ID);
+    }
+
+    /// preferred signature algorithms
+    #[test]
+    fn pref_sig_algs_round_trip() {
+        // sha256WithRSAEncryption, and ecdsa-with-SHA256 with id-ecPublicKey
+        let rsa = hex::decode("300d06092a864886f70d01010b0500").unwrap();
+        let rsa = AlgorithmIdentifier::parse(&rsa).unwrap();
+        let ecdsa = hex::decode("300a06082a8648ce3d040302").unwrap();
+        let ecdsa = AlgorithmIdentifier::parse(&ecdsa).unwrap();
+        // id-ecPublicKey with namedCurve prime256v1
+        let ec_key = hex::decode("301306072a8648ce3d020106082a8648ce3d030107").unwrap();
+        let ec_key = AlgorithmIdentifier::parse(&ec_key).unwrap();
+        let ext = OcspExt::PreferredSignatureAlgorithms {
+            algorithms: vec![
+                PreferredSignatureAlgorithm {
+                    sig_identifier: rsa.clone(),
+                    cert_identifier: None,
+                },
+                PreferredSignatureAlgorithm {
+                    sig_identifier: ecdsa.clone(),
+                    cert_identifier: Some(ec_key.clone()),
+                },
+            ],
+        };
+        match ext_round_trip(ext, OCSP_EXT_PREF_SIG_ALGS_ID) {
+            OcspExt::PreferredSignatureAlgorithms { algorithms } => {
+                assert_eq!(2, algorithms.len());
+                assert_eq!(rsa, algorithms[0].sig_identifier);
+                assert_eq!(None, algorithms[0].cert_identifier);
+                assert_eq!(ecdsa, algorithms[1].sig_identifier);
+                assert_eq!(Some(ec_key), algorithms[1].cert_identifier);
+            }
+            e => panic!("unexpected {:?}", e),
+        }
+    }
+
+    /// nonce extension decoded from its DER
+    #[test]
+    fn nonce_round_trip() {
+        let ext = OcspExt::Nonce {
+            nonce: vec![0x04, 0x04, 0x01, 0x02, 0x03, 0x04],
+        };
+        ext_round_trip(ext, OCSP_EXT_NONCE_ID);
+    }
+
+    /// malformed extension values are errors
+    #[test]
+    fn ext_malformed_value() {
+        // crl reason not enumerated
+        let c = hex::decode(
+            "300a0603551d1504030201\
+            01",
+        )
+        .unwrap();
+        assert!(OcspExt::parse_oneext(&c, DecodeMode::Lenient).is_err());
+        // crl reason undefined
+        let c = hex::decode("300a0603551d1504030a0107").unwrap();
+        assert!(OcspExt::parse_oneext(&c, DecodeMode::Lenient).is_err());
+        // critical not a boolean
+        let c = hex::decode("300d0603551d150201ff04030a0101").unwrap();
+        assert!(OcspExt::parse_oneext(&c, DecodeMode::Lenient).is_err());
+    }
+
+    /// unknown extension is kept byte exact, with critical flag
+    #[test]
+    fn unknown_ext_round_trip() {
+        // 1.3.6.1.4.1.11129.2.4.2 non critical, and 1.2.3.4 critical
+        let c = hex::decode(
+            "302b3013060a2b06010401d679020402040504030001\
+            02301406032a03040101ff040a30080c0676656e646f72",
+        )
+        .unwrap();
+        let list = OcspExtI::parse(&c).unwrap();
+        assert_eq!(2, list.len());
+        assert_eq!(OCSP_EXT_UNKNOWN_ID, list[0].id);
+        assert!(!list[0].critical);
+        assert!(!list[0].is_unrecognized_critical());
+        assert!(list[1].critical);
+        assert!(list[1].is_unrecognized_critical());
+        match &list[1].ext {
+            OcspExt::Unknown {
+                oid,
+                critical,
+                value,
+            } => {
+                assert_eq!("1.2.3.4", oid.to_string());
+                assert!(critical);
+                assert_eq!(&hex::decode("30080c0676656e646f72").unwrap(), value);
+            }
+            e => panic!("unexpected {:?}", e),
+        }
+
+        let v = list.to_der().unwrap();
+        assert_eq!(c, v);
+        assert_eq!(c.len(), list.encoded_len().unwrap());
+    }
+
+    /// vendor extension under a UUID oid, arc wider than 64 bits
+    #[test]
+    fn unknown_ext_uuid_oid() {
+        let dot = "2.25.329800735698586629295641978511506172918";
+        let oid = Oid::new_from_dot(dot).unwrap();
+        let ext = OcspExtI {
+            id: OCSP_EXT_UNKNOWN_ID,
+            critical: false,
+            ext: OcspExt::Unknown {
+                oid,
+                critical: false,
+                value: vec![0x05, 0x00],
+            },
+        };
+        let c = vec![ext].to_der().unwrap();
+        let list = OcspExtI::parse(&c).unwrap();
+        assert!(!list[0].is_unrecognized_critical());
+        match &list[0].ext {
+            OcspExt::Unknown { oid, .. } => assert_eq!(dot, oid.to_dot()),
+            e => panic!("unexpected {:?}", e),
+        }
+        let list = OcspExtI::parse_with_mode(&c, DecodeMode::Strict).unwrap();
+        assert_eq!(c, list.to_der().unwrap());
+    }
+
+    /// well-known oid that is not an extension is kept as unknown
+    #[test]
+    fn unknown_ext_algorithm_oid() {
+        // sha1 as extnID, critical
+        let c = hex::decode("300e300c06052b0e03021a0101ff0400").unwrap();
+        let list = OcspExtI::parse(&c).unwrap();
+        assert_eq!(OCSP_EXT_UNKNOWN_ID, list[0].id);
+        assert!(list[0].is_unrecognized_critical());
+        match &list[0].ext {
+            OcspExt::Unknown { oid, .. } => assert_eq!(ALGO_SHA1_DOT, oid.to_dot()),
+            e => panic!("unexpected {:?}", e),
+        }
+        assert_eq!(c, list.to_der().unwrap());
+    }
+
+    /// critical flag on a known extension is kept on encoding
+    #[test]
+    fn known_ext_critical_to_der() {
+        let c = hex::decode("300f300d0603551d150101ff04030a0101").unwrap();
+        let list = OcspExtI::parse(&c).unwrap();
+        assert!(list[0].critical);
+        assert!(!list[0].is_unrecognized_critical());
+        let v = list.to_der().unwrap();
+        assert_eq!(c, v);
+
+        // same list through DerDecode
+        let list = Vec::<OcspExtI>::from_der(&c).unwrap();
+        assert!(list[0].critical);
+        assert_eq!(c, list.to_der().unwrap());
+    }
 }
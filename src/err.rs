@@ -1,6 +1,8 @@
 //! Error module
 use thiserror::Error;
 
+use crate::response::OcspRespStatus;
+
 /// all lib functions return OcspError
 pub type Result<T> = std::result::Result<T, OcspError>;
 
@@ -30,10 +32,38 @@ pub enum OcspError {
     #[error("Unable to locate OID info")]
     Asn1OidUnknown,
 
+    /// OID bytes or dot notation is malformed
+    #[error("Invalid OID encoding or dot notation")]
+    Asn1OidInvalid,
+
     /// ASN.1 TLV reaches max length allowed
     #[error("ASN.1 allows max 127 bytes to represents a length in TLV, but got {0}")]
     Asn1LengthOverflow(usize),
 
+    /// DER length is not in its shortest form
+    #[error("Non minimal length encoding")]
+    Asn1NonMinimalLength,
+
+    /// BER indefinite length is not allowed in DER
+    #[error("Indefinite length is not allowed")]
+    Asn1IndefiniteLength,
+
+    /// Data found after a complete TLV
+    #[error("Found {0} bytes of trailing data")]
+    Asn1TrailingData(usize),
+
+    /// TLV is shorter than its length
+    #[error("Truncated TLV")]
+    Asn1Truncated,
+
+    /// DER BOOLEAN must be 0x00 or 0xff
+    #[error("Non canonical BOOLEAN value {0:#04x}")]
+    Asn1NonCanonicalBoolean(u8),
+
+    /// DER requires DEFAULT value to be omitted
+    #[error("DEFAULT value of {0} must be omitted")]
+    Asn1ExplicitDefault(&'static str),
+
     /// Cannot recognize ocsp extension
     #[error("Unable to recognize extension")]
     OcspExtUnknown,
@@ -42,11 +72,35 @@ pub enum OcspError {
     #[error("Non RFC defined tagging")]
     OcspUndefinedTagging,
 
+    /// OCSP version is not v1
+    #[error("Unsupported OCSP version {0}")]
+    OcspUnsupportedVersion(i64),
+
     /// OCSP response type is not supported
     // only basic type is supported now
     #[error("Unsupported response type")]
     OcspUnsupportedResponseType,
 
+    /// Signature does not match signed data and public key
+    #[error("Invalid signature")]
+    OcspBadSignature,
+
+    /// Signature algorithm is unknown, or its crate feature is disabled
+    #[error("Unsupported signature algorithm {0}")]
+    OcspUnsupportedSignatureAlgo(String),
+
+    /// Public key type cannot verify signature algorithm
+    #[error("Public key does not match signature algorithm {0}")]
+    OcspSignatureKeyMismatch(String),
+
+    /// Request is not signed
+    #[error("Request is not signed")]
+    OcspRequestNotSigned,
+
+    /// Signed request carries no certificate to verify with
+    #[error("Signer certificate not found")]
+    OcspSignerCertNotFound,
+
     /// Creating an OCSP response with inappropriate method
     #[error("Inappropriate response creation due to {0}")]
     OcspRespStatusError(&'static str),
@@ -55,6 +109,10 @@ pub enum OcspError {
     #[error("Undefined OCSP response status {0}")]
     OcspRespUndefinedStatus(u8),
 
+    /// Undefined CRL revocation reason
+    #[error("Undefined CRL reason {0}")]
+    OcspUndefinedCrlReason(u8),
+
     /// Cannot parse provided date
     #[error("Invalid date year {0} month {1} day {2}")]
     GenInvalidDate(i32, u32, u32),
@@ -63,7 +121,149 @@ pub enum OcspError {
     #[error("Invalid time hour {0} minute {1} second {2}")]
     GenInvalidTime(u32, u32, u32),
 
+    /// Cannot parse provided distinguished name string
+    #[error("Invalid distinguished name {0}")]
+    GenInvalidDn(String),
+
+    /// Cannot parse or convert serial number
+    #[error("Invalid serial number {0}")]
+    GenInvalidSerialNumber(String),
+
     /// Missing revoke info for revoked certificate
     #[error("Revoke info not found")]
     GenRevokeInfoNotFound,
+
+    /// Required field not set when building
+    #[error("Missing required field {0}")]
+    GenMissingField(&'static str),
+
+    /// Response data without any single response
+    #[error("Response contains no single response")]
+    GenEmptyResponses,
+
+    /// Single response nextUpdate is earlier than thisUpdate
+    #[error("nextUpdate is earlier than thisUpdate")]
+    GenNextUpdateBeforeThisUpdate,
+
+    /// Cannot load private key for signing
+    #[error("Invalid private key {0}")]
+    GenInvalidKey(String),
+
+    /// Signer failed to produce a signature
+    #[error("Unable to sign due to {0}")]
+    GenSignError(String),
+
+    /// Writer failed while writing DER
+    #[error("Unable to write DER")]
+    GenWriteError(#[source] std::io::Error),
+
+    /// Encoder wrote a different length when writing than when counting
+    #[error("Encoded length differs from counted length")]
+    GenEncodeMismatch,
+
+    /// Response producedAt is later than current time
+    #[error("producedAt is in the future")]
+    GenProducedAtInFuture,
+
+    /// Decoding failed within a field of the input  
+    /// see [OcspError::path] and [OcspError::offset]
+    #[error("{source} at offset {offset} in {path}")]
+    Asn1FieldError {
+        /// field names from RFC 6960 ASN.1, eg. `tbsRequest.requestList[3].reqCert.serialNumber`
+        path: String,
+        /// where the field starts in the input
+        offset: usize,
+        /// error decoding the field
+        source: Box<OcspError>,
+    },
+}
+
+impl OcspError {
+    /// path of the field that failed decoding, if known  
+    /// eg. `tbsRequest.requestList[3].reqCert.serialNumber`
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            OcspError::Asn1FieldError { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// byte offset in the input of the field that failed decoding, if known
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            OcspError::Asn1FieldError { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// underlying error, without field and offset
+    pub fn root(&self) -> &OcspError {
+        match self {
+            OcspError::Asn1FieldError { source, .. } => source.root(),
+            e => e,
+        }
+    }
+
+    /// response status a responder may reply with after this error  
+    /// - input that cannot be decoded, [OcspRespStatus::MalformedReq]  
+    /// - unsigned request, [OcspRespStatus::SigRequired]  
+    /// - request signature that cannot be verified, [OcspRespStatus::Unauthorized]  
+    /// - anything else, [OcspRespStatus::InternalError]
+    pub fn suggested_response_status(&self) -> OcspRespStatus {
+        if self.path().is_some() {
+            return OcspRespStatus::MalformedReq;
+        }
+        match self {
+            OcspError::Asn1DecodingError(_)
+            | OcspError::Asn1MismatchError(_)
+            | OcspError::Asn1Utf8Error(_)
+            | OcspError::Asn1LengthError(_)
+            | OcspError::Asn1OidUnknown
+            | OcspError::Asn1OidInvalid
+            | OcspError::Asn1LengthOverflow(_)
+            | OcspError::Asn1NonMinimalLength
+            | OcspError::Asn1IndefiniteLength
+            | OcspError::Asn1TrailingData(_)
+            | OcspError::Asn1Truncated
+            | OcspError::Asn1NonCanonicalBoolean(_)
+            | OcspError::Asn1ExplicitDefault(_)
+            | OcspError::OcspExtUnknown
+            | OcspError::OcspUndefinedTagging
+            | OcspError::OcspUnsupportedVersion(_) => OcspRespStatus::MalformedReq,
+            OcspError::OcspRequestNotSigned => OcspRespStatus::SigRequired,
+            OcspError::OcspBadSignature
+            | OcspError::OcspUnsupportedSignatureAlgo(_)
+            | OcspError::OcspSignatureKeyMismatch(_)
+            | OcspError::OcspSignerCertNotFound => OcspRespStatus::Unauthorized,
+            _ => OcspRespStatus::InternalError,
+        }
+    }
+
+    /// attach the field being decoded and its offset in the enclosing input  
+    /// nested fields are prefixed and offsets accumulated as the error propagates outwards
+    pub(crate) fn at(self, field: &str, offset: usize) -> Self {
+        match self {
+            OcspError::Asn1FieldError {
+                path,
+                offset: inner,
+                source,
+            } => {
+                let path = if path.starts_with('[') {
+                    format!("{}{}", field, path)
+                } else {
+                    format!("{}.{}", field, path)
+                };
+                OcspError::Asn1FieldError {
+                    path,
+                    offset: offset + inner,
+                    source,
+                }
+            }
+            e => OcspError::Asn1FieldError {
+                path: field.to_string(),
+                offset,
+                source: Box::new(e),
+            },
+        }
+    }
 }
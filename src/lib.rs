@@ -4,11 +4,341 @@
 #![warn(rust_2018_idioms)]
 #![warn(missing_docs)]
 
+pub mod cert;
 pub mod common;
 pub mod err;
 pub mod oid;
 pub mod request;
 pub mod response;
+pub mod sign;
+pub mod verify;
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use crate::common::asn1::test::{nested, nested_rsa_pss};
+    use crate::common::asn1::{
+        AlgorithmIdentifier, CertId, CertIdRef, DecodeMode, SequenceRef, ASN1_EXPLICIT_0, ASN1_OID,
+        ASN1_SEQUENCE,
+    };
+    use crate::common::der::{DerDecode, DerEncode, DerWriter};
+    use crate::common::ocsp::OcspExtI;
+    use crate::request::{OcspRequest, OcspRequestRef};
+    use crate::response::OcspResponse;
+    use std::panic;
+
+    // seed corpus for fuzz targets
+    // - two certs with nonce
+    // - signed request with requestor name and certs
+    const SEED_REQUEST: &[&str] = &[
+        "3081b53081b230818a30433041300906052b0e03021a05000414694d18a9be42\
+        f7802614d4844f23601478b788200414397be002a2f571fd80dceb52a17a7f8b\
+        632be755020841300983331f9d4f30433041300906052b0e03021a0500041469\
+        4d18a9be42f7802614d4844f23601478b788200414397be002a2f571fd80dceb\
+        52a17a7f8b632be75502086378e51d448ff46da2233021301f06092b06010505\
+        07300102041204105e7a74e51c861a3f79454658bb090244",
+        "307f305ba11216106f637370406578616d706c652e636f6d3045304330413009\
+        06052b0e03021a05000414694d18a9be42f7802614d4844f23601478b7882004\
+        14397be002a2f571fd80dceb52a17a7f8b632be75502086378e51d448ff46da0\
+        20301e300d06092a864886f70d01010b0500030400010203a007300530030201\
+        01",
+    ];
+    // - sha1 certid
+    // - sha256 certid with padded serial
+    const SEED_CERTID: &[&str] = &[
+        "3041300906052b0e03021a05000414694d18a9be42f7802614d4844f23601478\
+        b788200414397be002a2f571fd80dceb52a17a7f8b632be75502086378e51d44\
+        8ff46d",
+        "3057300d06096086480165030402010500042000000000000000000000000000\
+        0000000000000000000000000000000000000004200101010101010101010101\
+        010101010101010101010101010101010101010101020200ff",
+    ];
+    // - every known extension
+    // - unknown extensions with and without critical
+    const SEED_EXT: &[&str] = &[
+        "3082014e301306092b06010505073001020406040401020304300f06092b0601\
+        05050730010904020500304906092b0601050507300103043c303aa01f161d68\
+        7474703a2f2f63726c2e6578616d706c652e636f6d2f63612e63726ca1040202\
+        0100a211180f32303231303130313030303030305a301a06092b060105050730\
+        0104040d300b06092b0601050507300101301e06092b06010505073001060411\
+        180f32303231303131323038333235365a300a0603551d1504030a0101301806\
+        03551d180411180f32303231303131323038333235365a304206092b06010505\
+        07300107043530333031310b30090603550406130255533110300e060355040a\
+        0c074578616d706c653110300e06035504030c0754657374204341303506092b\
+        0601050507300108042830263024300d06092a864886f70d01010b0500301306\
+        072a8648ce3d020106082a8648ce3d030107",
+        "302b3013060a2b06010401d67902040204050403000102301406032a03040101\
+        ff040a30080c0676656e646f72",
+    ];
+    // - successful response with two certs
+    const SEED_RESPONSE: &[&str] = &[
+        "308202270a0100a08202203082021c06092b06010505073001010482020d3082\
+        02093081f2a2160414366f35fbef16c6ba8a3183426d97ba894d556e91180f32\
+        303231303131323033323634335a3081c630563041300906052b0e03021a0500\
+        0414694d18a9be42f7802614d4844f23601478b788200414397be002a2f571fd\
+        80dceb52a17a7f8b632be755020841300983331f9d4f8000180f323032313031\
+        31323033323634335a306c3041300906052b0e03021a05000414694d18a9be42\
+        f7802614d4844f23601478b788200414397be002a2f571fd80dceb52a17a7f8b\
+        632be75502086378e51d448ff46da116180f3230323031313330303134383235\
+        5aa0030a0100180f32303231303131323033323634335a300d06092a864886f7\
+        0d010105050003820101001e022d5ba25aa6ee97c5d910c61ebeb73db75a767d\
+        eb43af88c2a56377d9e5aeaa548430087b5429d9b90b30569f9444676ad3a988\
+        5fb6d29cd46489ea1a82c369790d2a4943f4ca93c97706c929707fb6e5b49d43\
+        3b84003bd9aa24a395278ab63e7a2622d2ec7d3579453e7960bbcfca6d0d3db0\
+        fe460f7c2bbaf72e8c6fb85c7c6537ea0cb3c36811a8950f7396987598a5b3c8\
+        9fc1466cb17c559589d85d8af954d607c43ff708ddf5d6672faa14fbc717b553\
+        52c2110450f220e8a0be9e6a8664a6acdca63ae3a706b72cc19da227ce5e1a8f\
+        69adce38f45f8dd3874885898d7cebd6057fd8e5f327694198edd90fe6e82161\
+        3be71e3ba24f4db85f10a7",
+    ];
+
+    // - ecdsa with absent parameters
+    // - sha1 with NULL parameters
+    // - RSASSA-PSS with sha256, as generated by OpenSSL
+    const SEED_ALGO: &[&str] = &[
+        "300a06082a8648ce3d040302",
+        "300906052b0e03021a0500",
+        "304106092a864886f70d01010a3034a00f300d0609608648016503040201\
+        0500a11c301a06092a864886f70d010108300d06096086480165030402010500a2\
+        03020120",
+        // arc wider than dot notation conversion allows
+        "302d06292b818181818181818181818181818181818181818181818181818181\
+        818181818181818181818181010500",
+    ];
+
+    fn seq(items: &[&[u8]]) -> Vec<u8> {
+        DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                items.iter().for_each(|i| w.write_raw(i));
+                Ok(())
+            })
+        })
+        .unwrap()
+    }
+
+    /// request seed with reqCert hashAlgorithm replaced
+    fn request_with_hash(algo: &[u8]) -> Vec<u8> {
+        let cid = hex::decode(SEED_CERTID[0]).unwrap();
+        let cid = SequenceRef::decode(&cid).unwrap();
+        let mut fields = cid.iter().map(|f| f.raw()).collect::<Vec<_>>();
+        fields[0] = algo;
+        // Request, requestList, tbsRequest, OCSPRequest
+        let mut req = seq(&fields);
+        for _ in 0..4 {
+            req = seq(&[&req]);
+        }
+        req
+    }
+
+    /// response seed with BasicOCSPResponse signatureAlgorithm replaced
+    fn response_with_sign_algo(algo: &[u8]) -> Vec<u8> {
+        let resp = hex::decode(SEED_RESPONSE[0]).unwrap();
+        let bytes = SequenceRef::decode(&resp).unwrap().iter().nth(1).unwrap();
+        let bytes = SequenceRef::decode(bytes.value()).unwrap();
+        let basic = bytes.iter().nth(1).unwrap();
+        let basic = SequenceRef::decode(basic.value()).unwrap();
+        let mut basic = basic.iter();
+        let tbs = basic.next().unwrap();
+        let sign = basic.nth(1).unwrap();
+        let basic = seq(&[tbs.raw(), algo, sign.raw()]);
+        let basic = DerWriter::encode(|w| w.write_octet(&basic)).unwrap();
+        let bytes = seq(&[&hex::decode("06092b0601050507300101").unwrap(), &basic]);
+        let bytes = DerWriter::encode(|w| {
+            w.write_tagged(ASN1_EXPLICIT_0, |w| {
+                w.write_raw(&bytes);
+                Ok(())
+            })
+        })
+        .unwrap();
+        seq(&[&[0x0a, 0x01, 0x00], &bytes])
+    }
+
+    /// algorithm identifier with a 64 KB oid arc
+    fn large_arc_algo() -> Vec<u8> {
+        let mut oid = vec![0x2b];
+        oid.extend(vec![0xff; 0xffff]);
+        oid.push(0x7f);
+        let oid = DerWriter::encode(|w| w.write_tlv(ASN1_OID, &oid)).unwrap();
+        seq(&[&oid, &[0x05, 0x00]])
+    }
+
+    /// deep nesting mutations of seeds cannot reach, a stack overflow aborts the test run  
+    /// - nested SEQUENCE and explicit tags  
+    /// - RSASSA-PSS nested in its hash algorithm  
+    /// - same in request CertId and response signatureAlgorithm
+    fn deep_inputs() -> Vec<Vec<u8>> {
+        let pss = nested_rsa_pss(4_000);
+        vec![
+            nested(ASN1_SEQUENCE, 20_000, &[]),
+            nested(ASN1_EXPLICIT_0, 20_000, &[0x05, 0x00]),
+            request_with_hash(&pss),
+            response_with_sign_algo(&pss),
+            pss,
+        ]
+    }
+
+    /// oid arcs too long for seeds to grow, converting them to dot notation is quadratic  
+    /// - in request CertId, response signatureAlgorithm and extension extnID
+    fn large_arc_inputs() -> Vec<Vec<u8>> {
+        let algo = large_arc_algo();
+        let oid = SequenceRef::decode(&algo).unwrap().iter().next().unwrap();
+        let ext = seq(&[oid.raw(), &[0x04, 0x00]]);
+        vec![
+            request_with_hash(&algo),
+            response_with_sign_algo(&algo),
+            seq(&[&ext]),
+            algo,
+        ]
+    }
+
+    // deep inputs are built around fields the parsers reach
+    #[test]
+    fn deep_inputs_reach_algorithm() {
+        let pss = nested_rsa_pss(1);
+        OcspRequest::parse(&request_with_hash(&pss)).unwrap();
+        OcspResponse::parse(&response_with_sign_algo(&pss)).unwrap();
+        for input in deep_inputs() {
+            assert!(OcspRequest::parse(&input).is_err());
+            assert!(OcspResponse::parse_with_mode(&input, DecodeMode::Strict).is_err());
+        }
+    }
+
+    // large arc inputs decode, dot notation falls back to hex
+    #[test]
+    fn large_arc_inputs_decode() {
+        let inputs = large_arc_inputs();
+        OcspRequest::parse(&inputs[0]).unwrap();
+        OcspResponse::parse(&inputs[1]).unwrap();
+        assert_eq!(1, Vec::<OcspExtI>::from_der(&inputs[2]).unwrap().len());
+        let algo = AlgorithmIdentifier::from_der(&inputs[3]).unwrap();
+        assert!(algo.oid.to_string().starts_with("2bffff"));
+    }
+
+    /// xorshift64, fixed seed keeps failures reproducible
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    /// feeding mutated seeds to target, any panic fails the test  
+    /// - every truncation  
+    /// - every byte replaced by length and tag boundaries  
+    /// - random stacked flips, inserts, removals and splices  
+    /// - every [deep_inputs] and [large_arc_inputs]
+    fn fuzz(seeds: &[&str], target: fn(&[u8])) {
+        for input in deep_inputs().into_iter().chain(large_arc_inputs()) {
+            if panic::catch_unwind(|| target(&input)).is_err() {
+                panic!(
+                    "target panicked on deep input {}",
+                    hex::encode(&input[..16])
+                );
+            }
+        }
+
+        let mut rng = Rng(0x6f63_7370_2d72_7321);
+        for seed in seeds {
+            let seed = hex::decode(seed).unwrap();
+            let mut inputs = vec![];
+            for i in 0..seed.len() {
+                inputs.push(seed[..i].to_vec());
+            }
+            for i in 0..seed.len() {
+                for b in &[0x00u8, 0x01, 0x02, 0x7f, 0x80, 0x81, 0x82, 0x84, 0x89, 0xff] {
+                    let mut v = seed.clone();
+                    v[i] = *b;
+                    inputs.push(v);
+                }
+            }
+            for _ in 0..4000 {
+                let mut v = seed.clone();
+                for _ in 0..1 + rng.below(4) {
+                    let i = rng.below(v.len() + 1);
+                    match rng.below(4) {
+                        0 if i < v.len() => v[i] ^= 1 << rng.below(8),
+                        1 => v.insert(i, rng.next() as u8),
+                        2 if i < v.len() => {
+                            v.remove(i);
+                        }
+                        _ => {
+                            let j = rng.below(seed.len());
+                            let k = j + rng.below(seed.len() - j + 1);
+                            let tail = v.split_off(i);
+                            v.extend_from_slice(&seed[j..k]);
+                            v.extend(tail);
+                        }
+                    }
+                }
+                inputs.push(v);
+            }
+
+            for input in inputs {
+                if panic::catch_unwind(|| target(&input)).is_err() {
+                    panic!("target panicked on input {}", hex::encode(&input));
+                }
+            }
+        }
+    }
+
+    /// decoded values encode, counted length agrees with encoding
+    fn decode_encode<T: for<'a> DerDecode<'a> + DerEncode>(data: &[u8]) {
+        if let Ok(t) = T::from_der(data) {
+            if let Ok(v) = t.to_der() {
+                assert_eq!(v.len(), t.encoded_len().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_ocsp_request_parse() {
+        fuzz(SEED_REQUEST, |data| {
+            decode_encode::<OcspRequest>(data);
+            let _ = OcspRequest::parse_with_mode(data, DecodeMode::Strict);
+            if let Ok(req) = OcspRequestRef::parse(data) {
+                let _ = req.nonce();
+                let _ = req.to_owned();
+            }
+        });
+    }
+
+    #[test]
+    fn fuzz_certid_parse() {
+        fuzz(SEED_CERTID, |data| {
+            decode_encode::<CertId>(data);
+            if let Ok(cid) = CertIdRef::parse(data) {
+                let _ = cid.to_owned();
+            }
+        });
+    }
+
+    #[test]
+    fn fuzz_ocsp_ext_parse() {
+        fuzz(SEED_EXT, decode_encode::<Vec<OcspExtI>>);
+    }
+
+    #[test]
+    fn fuzz_ocsp_response_parse() {
+        fuzz(SEED_RESPONSE, |data| {
+            decode_encode::<OcspResponse>(data);
+            let _ = OcspResponse::parse_with_mode(data, DecodeMode::Strict);
+        });
+    }
+
+    #[test]
+    fn fuzz_algorithm_identifier_parse() {
+        fuzz(SEED_ALGO, |data| {
+            decode_encode::<AlgorithmIdentifier>(data);
+            if let Ok(algo) = AlgorithmIdentifier::from_der(data) {
+                let _ = algo.oid.to_string();
+            }
+        });
+    }
+}
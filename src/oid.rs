@@ -7,43 +7,228 @@ use tracing::{debug, error};
 use crate::common::asn1::Bytes;
 use crate::{common::asn1::Oid, err::OcspError};
 
-/// oid bytes to internal id
+/// oid bytes to internal id  
+/// only well-known oid has an internal id
 pub(crate) fn b2i_oid(oid: &[u8]) -> Option<usize> {
     debug!("Oid bytes {} to internal", hex::encode(oid));
     match OID_MAP.get(oid) {
         None => {
-            error!("No matching oid found");
+            debug!("No matching well-known oid found");
             None
         }
         Some(u) => Some(u.to_owned()),
     }
 }
 
-/// oid dot notation to internal id
+/// oid dot notation to internal
 pub(crate) fn d2i_oid(oid_dot: &str) -> Option<Oid> {
-    debug!("Oid dot notation {} to internal", hex::encode(oid_dot));
-    match OCSP_OID_DOT_LIST
-        .iter()
-        .enumerate()
-        .find(|(_, dot_name)| **dot_name == oid_dot)
-    {
+    debug!("Oid dot notation {} to internal", oid_dot);
+    match d2b_oid(oid_dot) {
         None => {
-            error!("No matching oid found");
+            error!("Invalid oid dot notation");
             None
         }
-        Some((i, _)) => Some(Oid { index: i }),
+        Some(id) => Some(Oid { id }),
     }
 }
 
 /// oid internal to bytes
-pub fn i2b_oid(oid: &Oid) -> Result<&'static [u8], OcspError> {
+pub fn i2b_oid(oid: &Oid) -> Result<&[u8], OcspError> {
     debug!("Oid {:?} to bytes", oid);
-    let id = oid.index;
-    if id > OID_MAX_ID {
-        error!("No matching oid found");
-        return Err(OcspError::Asn1OidUnknown);
+    Ok(&oid.id[..])
+}
+
+/// whether oid value bytes are well formed, in linear time  
+/// - not empty  
+/// - every arc is minimal, not starting with 0x80  
+/// - last arc is complete
+pub(crate) fn is_oid(oid: &[u8]) -> bool {
+    if oid.is_empty() || oid[oid.len() - 1] & 0x80 != 0 {
+        return false;
+    }
+    // a byte following one without high bit starts an arc
+    std::iter::once(&0)
+        .chain(oid)
+        .zip(oid)
+        .all(|(prev, b)| prev & 0x80 != 0 || *b != 0x80)
+}
+
+/// longest arc in bytes converted to dot notation, 224 bits  
+/// conversion is quadratic in arc length, UUID arcs take 19 bytes
+const OID_ARC_MAX_LEN: usize = 32;
+
+/// oid bytes to dot notation  
+/// each arc is base-128 encoded, first two arcs are packed into one  
+/// arcs may be wider than 64 bits, eg. 2.25 followed by a 128-bit UUID,
+/// but not wider than [OID_ARC_MAX_LEN] bytes
+pub(crate) fn b2d_oid(oid: &[u8]) -> Option<String> {
+    if !is_oid(oid) {
+        return None;
+    }
+
+    let mut arcs = vec![];
+    let mut cur = Arc::default();
+    let mut len = 0;
+    for b in oid {
+        len += 1;
+        if len > OID_ARC_MAX_LEN {
+            debug!("Oid arc is longer than {} bytes", OID_ARC_MAX_LEN);
+            return None;
+        }
+        cur.mul_add(0x80, (b & 0x7f) as u32);
+        if b & 0x80 == 0 {
+            arcs.push(std::mem::take(&mut cur));
+            len = 0;
+        }
+    }
+
+    let first = arcs[0].to_small();
+    let (first, second) = match first {
+        Some(v) if v < 40 => (0, Arc::from(v)),
+        Some(v) if v < 80 => (1, Arc::from(v - 40)),
+        _ => {
+            let mut v = arcs[0].clone();
+            v.sub_small(80);
+            (2, v)
+        }
+    };
+    let mut dot = format!("{}.{}", first, second);
+    for a in &arcs[1..] {
+        dot.push_str(&format!(".{}", a));
+    }
+    Some(dot)
+}
+
+/// oid dot notation to bytes
+pub(crate) fn d2b_oid(oid_dot: &str) -> Option<Bytes> {
+    let mut arcs = vec![];
+    for a in oid_dot.split('.') {
+        arcs.push(Arc::from_dec(a)?);
+    }
+    if arcs.len() < 2 {
+        return None;
+    }
+    let first = match arcs[0].to_small() {
+        Some(f) if f <= 2 => f,
+        _ => return None,
+    };
+    if first < 2 && arcs[1].to_small().unwrap_or(u32::MAX) >= 40 {
+        return None;
+    }
+
+    let mut packed = arcs[1].clone();
+    packed.add_small(first * 40);
+    let mut v = vec![];
+    for mut a in std::iter::once(packed).chain(arcs.drain(2..)) {
+        let mut enc = vec![a.div_small(0x80) as u8];
+        while !a.is_zero() {
+            enc.push(a.div_small(0x80) as u8 | 0x80);
+        }
+        enc.reverse();
+        v.extend(enc);
+    }
+    Some(v)
+}
+
+/// unsigned arc of any width, little-endian limbs in base 10^9
+#[derive(Debug, Clone, Default)]
+struct Arc(Vec<u32>);
+
+const ARC_BASE: u64 = 1_000_000_000;
+
+impl Arc {
+    /// parse decimal digits, sign and empty string are not allowed
+    fn from_dec(dec: &str) -> Option<Self> {
+        if dec.is_empty() || !dec.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let mut a = Arc::default();
+        dec.bytes().for_each(|d| a.mul_add(10, (d - b'0') as u32));
+        Some(a)
+    }
+
+    /// self = self * mul + add
+    fn mul_add(&mut self, mul: u32, add: u32) {
+        let mut carry = add as u64;
+        for l in self.0.iter_mut() {
+            let v = *l as u64 * mul as u64 + carry;
+            *l = (v % ARC_BASE) as u32;
+            carry = v / ARC_BASE;
+        }
+        if carry > 0 {
+            self.0.push(carry as u32);
+        }
+    }
+
+    fn add_small(&mut self, add: u32) {
+        self.mul_add(1, add);
+    }
+
+    /// self = self - sub, caller ensures self >= sub
+    fn sub_small(&mut self, sub: u32) {
+        let mut borrow = sub as u64;
+        for l in self.0.iter_mut() {
+            if borrow == 0 {
+                break;
+            }
+            let v = *l as u64 + ARC_BASE - borrow % ARC_BASE;
+            *l = (v % ARC_BASE) as u32;
+            borrow = borrow / ARC_BASE + u64::from(v < ARC_BASE);
+        }
+        self.trim();
+    }
+
+    /// self = self / div, return remainder
+    fn div_small(&mut self, div: u32) -> u32 {
+        let mut rem = 0u64;
+        for l in self.0.iter_mut().rev() {
+            let v = rem * ARC_BASE + *l as u64;
+            *l = (v / div as u64) as u32;
+            rem = v % div as u64;
+        }
+        self.trim();
+        rem as u32
+    }
+
+    fn trim(&mut self) {
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|l| *l == 0)
+    }
+
+    /// value if below 10^9
+    fn to_small(&self) -> Option<u32> {
+        match self.0.iter().rposition(|l| *l != 0) {
+            None => Some(0),
+            Some(0) => Some(self.0[0]),
+            Some(_) => None,
+        }
+    }
+}
+
+impl From<u32> for Arc {
+    fn from(v: u32) -> Self {
+        let mut a = Arc::default();
+        a.add_small(v);
+        a
+    }
+}
+
+impl std::fmt::Display for Arc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut limbs = self.0.iter().rev().skip_while(|l| **l == 0);
+        match limbs.next() {
+            None => write!(f, "0"),
+            Some(top) => {
+                write!(f, "{}", top)?;
+                limbs.try_for_each(|l| write!(f, "{:09}", l))
+            }
+        }
     }
-    Ok(&OCSP_OID_HEX_LIST[id][..])
 }
 
 // ocsp nonce extension internal id
@@ -83,7 +268,7 @@ pub const OCSP_EXT_ARCHIVE_CUTOFF_NAME: &str = "id-pkix-ocsp 6";
 // crl entry 1
 pub(crate) const OCSP_EXT_CRL_REASON_ID: usize = 4;
 /// ocsp crl reason extension bytes in DER
-pub const OCSP_EXT_CRL_REASON_HEX: [u8; 4] = [0x02, 0x05, 0x1d, 0x15];
+pub const OCSP_EXT_CRL_REASON_HEX: [u8; 3] = [0x55, 0x1d, 0x15];
 /// ocsp crl reason extension name dot notation
 pub const OCSP_EXT_CRL_REASON_DOT: &str = "2.5.29.21";
 /// ocsp crl reason extension name asn1 notation
@@ -92,7 +277,7 @@ pub const OCSP_EXT_CRL_REASON_NAME: &str = "id-ce 21";
 // crl entry 2
 pub(crate) const OCSP_EXT_INVALID_DATE_ID: usize = 5;
 /// ocsp invalid date extension bytes in DER
-pub const OCSP_EXT_INVALID_DATE_HEX: [u8; 4] = [0x02, 0x05, 0x1d, 0x18];
+pub const OCSP_EXT_INVALID_DATE_HEX: [u8; 3] = [0x55, 0x1d, 0x18];
 /// ocsp invalid date extension name dot notation
 pub const OCSP_EXT_INVALID_DATE_DOT: &str = "2.5.29.24";
 /// ocsp invalid date extension name asn1 notation
@@ -125,6 +310,22 @@ pub const OCSP_EXT_EXTENDED_REVOKE_DOT: &str = "1.3.6.1.5.5.7.48.1.9";
 /// ocsp extended revoke extension name asn1 notation
 pub const OCSP_EXT_EXTENDED_REVOKE_NAME: &str = "id-pkix-ocsp 9";
 
+/// internal id for extensions not listed here
+pub const OCSP_EXT_UNKNOWN_ID: usize = usize::MAX;
+
+/// internal ids of extensions listed here, other well-known oid are not extensions
+pub(crate) const OCSP_EXT_ID_LIST: [usize; 9] = [
+    OCSP_EXT_NONCE_ID,
+    OCSP_EXT_CRLREF_ID,
+    OCSP_EXT_RESP_TYPE_ID,
+    OCSP_EXT_ARCHIVE_CUTOFF_ID,
+    OCSP_EXT_CRL_REASON_ID,
+    OCSP_EXT_INVALID_DATE_ID,
+    OCSP_EXT_SERVICE_LOCATOR_ID,
+    OCSP_EXT_PREF_SIG_ALGS_ID,
+    OCSP_EXT_EXTENDED_REVOKE_ID,
+];
+
 pub(crate) const ALGO_SHA1_ID: usize = 9;
 /// sha1 bytes in DER
 pub const ALGO_SHA1_HEX: [u8; 5] = [0x2b, 0x0e, 0x03, 0x02, 0x1a];
@@ -171,11 +372,163 @@ pub const ALGO_SHA256_WITH_ECDSA_ENCRYPTION_DOT: &str = "1.2.840.10045.4.3.2";
 pub const ALGO_SHA256_WITH_ECDSA_ENCRYPTION_NAME: &str =
     "{iso(1) member-body(2) us(840) ansi-x962(10045) signatures(4) ecdsa-with-SHA2(3) ecdsa-with-SHA256(2)}";
 
-/// NOT the number of OID, highest num in OID_MAX  
-/// eg.  
-/// oid_map contains 4 algos [0..3]  
-/// OID_MAX_ID = 3
-pub(crate) const OID_MAX_ID: usize = 13;
+pub(crate) const ALGO_SHA256_ID: usize = 14;
+/// sha256 bytes in DER
+pub const ALGO_SHA256_HEX: [u8; 9] = [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+/// sha256 dot notation
+pub const ALGO_SHA256_DOT: &str = "2.16.840.1.101.3.4.2.1";
+/// sha256 asn1 notation
+pub const ALGO_SHA256_NAME: &str =
+    "{joint-iso-itu-t(2) country(16) us(840) organization(1) gov(101) csor(3) nistAlgorithm(4) hashAlgs(2) sha256(1)}";
+
+pub(crate) const ALGO_SHA384_ID: usize = 15;
+/// sha384 bytes in DER
+pub const ALGO_SHA384_HEX: [u8; 9] = [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02];
+/// sha384 dot notation
+pub const ALGO_SHA384_DOT: &str = "2.16.840.1.101.3.4.2.2";
+/// sha384 asn1 notation
+pub const ALGO_SHA384_NAME: &str =
+    "{joint-iso-itu-t(2) country(16) us(840) organization(1) gov(101) csor(3) nistAlgorithm(4) hashAlgs(2) sha384(2)}";
+
+pub(crate) const ALGO_SHA512_ID: usize = 16;
+/// sha512 bytes in DER
+pub const ALGO_SHA512_HEX: [u8; 9] = [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03];
+/// sha512 dot notation
+pub const ALGO_SHA512_DOT: &str = "2.16.840.1.101.3.4.2.3";
+/// sha512 asn1 notation
+pub const ALGO_SHA512_NAME: &str =
+    "{joint-iso-itu-t(2) country(16) us(840) organization(1) gov(101) csor(3) nistAlgorithm(4) hashAlgs(2) sha512(3)}";
+
+pub(crate) const ALGO_SHA384_WITH_RSA_ENCRYPTION_ID: usize = 17;
+/// sha384WithRSAEncryption bytes in DER
+pub const ALGO_SHA384_WITH_RSA_ENCRYPTION_HEX: [u8; 9] =
+    [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c];
+/// sha384WithRSAEncryption dot notation
+pub const ALGO_SHA384_WITH_RSA_ENCRYPTION_DOT: &str = "1.2.840.113549.1.1.12";
+/// sha384WithRSAEncryption asn1 notation
+pub const ALGO_SHA384_WITH_RSA_ENCRYPTION_NAME: &str =
+    "{iso(1) member-body(2) us(840) rsadsi(113549) pkcs(1) pkcs-1(1) sha384-with-rsa-signature(12)}";
+
+pub(crate) const ALGO_SHA512_WITH_RSA_ENCRYPTION_ID: usize = 18;
+/// sha512WithRSAEncryption bytes in DER
+pub const ALGO_SHA512_WITH_RSA_ENCRYPTION_HEX: [u8; 9] =
+    [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d];
+/// sha512WithRSAEncryption dot notation
+pub const ALGO_SHA512_WITH_RSA_ENCRYPTION_DOT: &str = "1.2.840.113549.1.1.13";
+/// sha512WithRSAEncryption asn1 notation
+pub const ALGO_SHA512_WITH_RSA_ENCRYPTION_NAME: &str =
+    "{iso(1) member-body(2) us(840) rsadsi(113549) pkcs(1) pkcs-1(1) sha512-with-rsa-signature(13)}";
+
+pub(crate) const ALGO_SHA384_WITH_ECDSA_ENCRYPTION_ID: usize = 19;
+/// ecdsa-with-SHA384 bytes in DER
+pub const ALGO_SHA384_WITH_ECDSA_ENCRYPTION_HEX: [u8; 8] =
+    [0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03];
+/// ecdsa-with-SHA384 dot notation
+pub const ALGO_SHA384_WITH_ECDSA_ENCRYPTION_DOT: &str = "1.2.840.10045.4.3.3";
+/// ecdsa-with-SHA384 asn1 notation
+pub const ALGO_SHA384_WITH_ECDSA_ENCRYPTION_NAME: &str =
+    "{iso(1) member-body(2) us(840) ansi-x962(10045) signatures(4) ecdsa-with-SHA2(3) ecdsa-with-SHA384(3)}";
+
+pub(crate) const ALGO_SHA512_WITH_ECDSA_ENCRYPTION_ID: usize = 20;
+/// ecdsa-with-SHA512 bytes in DER
+pub const ALGO_SHA512_WITH_ECDSA_ENCRYPTION_HEX: [u8; 8] =
+    [0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04];
+/// ecdsa-with-SHA512 dot notation
+pub const ALGO_SHA512_WITH_ECDSA_ENCRYPTION_DOT: &str = "1.2.840.10045.4.3.4";
+/// ecdsa-with-SHA512 asn1 notation
+pub const ALGO_SHA512_WITH_ECDSA_ENCRYPTION_NAME: &str =
+    "{iso(1) member-body(2) us(840) ansi-x962(10045) signatures(4) ecdsa-with-SHA2(3) ecdsa-with-SHA512(4)}";
+
+pub(crate) const ALGO_ED25519_ID: usize = 21;
+/// Ed25519 bytes in DER
+pub const ALGO_ED25519_HEX: [u8; 3] = [0x2b, 0x65, 0x70];
+/// Ed25519 dot notation
+pub const ALGO_ED25519_DOT: &str = "1.3.101.112";
+/// Ed25519 asn1 notation
+pub const ALGO_ED25519_NAME: &str =
+    "{iso(1) identified-organization(3) thawte(101) id-Ed25519(112)}";
+
+pub(crate) const ALGO_RSASSA_PSS_ID: usize = 22;
+/// RSASSA-PSS bytes in DER
+pub const ALGO_RSASSA_PSS_HEX: [u8; 9] = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0a];
+/// RSASSA-PSS dot notation
+pub const ALGO_RSASSA_PSS_DOT: &str = "1.2.840.113549.1.1.10";
+/// RSASSA-PSS asn1 notation
+pub const ALGO_RSASSA_PSS_NAME: &str =
+    "{iso(1) member-body(2) us(840) rsadsi(113549) pkcs(1) pkcs-1(1) rsassa-pss(10)}";
+
+pub(crate) const ALGO_MGF1_ID: usize = 23;
+/// MGF1 bytes in DER
+pub const ALGO_MGF1_HEX: [u8; 9] = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x08];
+/// MGF1 dot notation
+pub const ALGO_MGF1_DOT: &str = "1.2.840.113549.1.1.8";
+/// MGF1 asn1 notation
+pub const ALGO_MGF1_NAME: &str =
+    "{iso(1) member-body(2) us(840) rsadsi(113549) pkcs(1) pkcs-1(1) id-mgf1(8)}";
+
+pub(crate) const ALGO_RSA_ENCRYPTION_ID: usize = 24;
+/// rsaEncryption bytes in DER
+pub const ALGO_RSA_ENCRYPTION_HEX: [u8; 9] = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+/// rsaEncryption dot notation
+pub const ALGO_RSA_ENCRYPTION_DOT: &str = "1.2.840.113549.1.1.1";
+/// rsaEncryption asn1 notation
+pub const ALGO_RSA_ENCRYPTION_NAME: &str =
+    "{iso(1) member-body(2) us(840) rsadsi(113549) pkcs(1) pkcs-1(1) rsaEncryption(1)}";
+
+pub(crate) const ALGO_EC_PUBLIC_KEY_ID: usize = 25;
+/// id-ecPublicKey bytes in DER
+pub const ALGO_EC_PUBLIC_KEY_HEX: [u8; 7] = [0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+/// id-ecPublicKey dot notation
+pub const ALGO_EC_PUBLIC_KEY_DOT: &str = "1.2.840.10045.2.1";
+/// id-ecPublicKey asn1 notation
+pub const ALGO_EC_PUBLIC_KEY_NAME: &str =
+    "{iso(1) member-body(2) us(840) ansi-x962(10045) keyType(2) ecPublicKey(1)}";
+
+// certificate extensions and their values, no internal id
+// they never appear as ocsp extensions
+
+/// keyUsage certificate extension bytes in DER
+pub const CERT_EXT_KEY_USAGE_HEX: [u8; 3] = [0x55, 0x1d, 0x0f];
+/// keyUsage certificate extension dot notation
+pub const CERT_EXT_KEY_USAGE_DOT: &str = "2.5.29.15";
+/// keyUsage certificate extension asn1 notation
+pub const CERT_EXT_KEY_USAGE_NAME: &str = "{joint-iso-itu-t(2) ds(5) ce(29) keyUsage(15)}";
+
+/// extKeyUsage certificate extension bytes in DER
+pub const CERT_EXT_EXT_KEY_USAGE_HEX: [u8; 3] = [0x55, 0x1d, 0x25];
+/// extKeyUsage certificate extension dot notation
+pub const CERT_EXT_EXT_KEY_USAGE_DOT: &str = "2.5.29.37";
+/// extKeyUsage certificate extension asn1 notation
+pub const CERT_EXT_EXT_KEY_USAGE_NAME: &str = "{joint-iso-itu-t(2) ds(5) ce(29) extKeyUsage(37)}";
+
+/// authorityInfoAccess certificate extension bytes in DER
+pub const CERT_EXT_AIA_HEX: [u8; 8] = [0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x01, 0x01];
+/// authorityInfoAccess certificate extension dot notation
+pub const CERT_EXT_AIA_DOT: &str = "1.3.6.1.5.5.7.1.1";
+/// authorityInfoAccess certificate extension asn1 notation
+pub const CERT_EXT_AIA_NAME: &str = "id-pe 1";
+
+/// id-pkix-ocsp-nocheck certificate extension bytes in DER
+pub const CERT_EXT_OCSP_NOCHECK_HEX: [u8; 9] =
+    [0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01, 0x05];
+/// id-pkix-ocsp-nocheck certificate extension dot notation
+pub const CERT_EXT_OCSP_NOCHECK_DOT: &str = "1.3.6.1.5.5.7.48.1.5";
+/// id-pkix-ocsp-nocheck certificate extension asn1 notation
+pub const CERT_EXT_OCSP_NOCHECK_NAME: &str = "id-pkix-ocsp 5";
+
+/// id-ad-ocsp access method bytes in DER
+pub const AD_OCSP_HEX: [u8; 8] = [0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01];
+/// id-ad-ocsp access method dot notation
+pub const AD_OCSP_DOT: &str = "1.3.6.1.5.5.7.48.1";
+/// id-ad-ocsp access method asn1 notation
+pub const AD_OCSP_NAME: &str = "id-ad 1";
+
+/// id-kp-OCSPSigning extended key usage bytes in DER
+pub const KP_OCSP_SIGNING_HEX: [u8; 8] = [0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x09];
+/// id-kp-OCSPSigning extended key usage dot notation
+pub const KP_OCSP_SIGNING_DOT: &str = "1.3.6.1.5.5.7.3.9";
+/// id-kp-OCSPSigning extended key usage asn1 notation
+pub const KP_OCSP_SIGNING_NAME: &str = "id-kp 9";
 
 lazy_static! {
     /// search oid index by oid binary
@@ -194,12 +547,24 @@ lazy_static! {
         (OCSP_RESPONSE_BASIC_HEX.to_vec(), OCSP_RESPONSE_BASIC_ID),
         (ALGO_SHA256_WITH_RSA_ENCRYPTION_HEX.to_vec(), ALGO_SHA256_WITH_RSA_ENCRYPTION_ID),
         (ALGO_SHA256_WITH_ECDSA_ENCRYPTION_HEX.to_vec(), ALGO_SHA256_WITH_ECDSA_ENCRYPTION_ID),
+        (ALGO_SHA256_HEX.to_vec(), ALGO_SHA256_ID),
+        (ALGO_SHA384_HEX.to_vec(), ALGO_SHA384_ID),
+        (ALGO_SHA512_HEX.to_vec(), ALGO_SHA512_ID),
+        (ALGO_SHA384_WITH_RSA_ENCRYPTION_HEX.to_vec(), ALGO_SHA384_WITH_RSA_ENCRYPTION_ID),
+        (ALGO_SHA512_WITH_RSA_ENCRYPTION_HEX.to_vec(), ALGO_SHA512_WITH_RSA_ENCRYPTION_ID),
+        (ALGO_SHA384_WITH_ECDSA_ENCRYPTION_HEX.to_vec(), ALGO_SHA384_WITH_ECDSA_ENCRYPTION_ID),
+        (ALGO_SHA512_WITH_ECDSA_ENCRYPTION_HEX.to_vec(), ALGO_SHA512_WITH_ECDSA_ENCRYPTION_ID),
+        (ALGO_ED25519_HEX.to_vec(), ALGO_ED25519_ID),
+        (ALGO_RSASSA_PSS_HEX.to_vec(), ALGO_RSASSA_PSS_ID),
+        (ALGO_MGF1_HEX.to_vec(), ALGO_MGF1_ID),
+        (ALGO_RSA_ENCRYPTION_HEX.to_vec(), ALGO_RSA_ENCRYPTION_ID),
+        (ALGO_EC_PUBLIC_KEY_HEX.to_vec(), ALGO_EC_PUBLIC_KEY_ID),
     ]
     .into_iter()
     .collect();
 
     /// list of ocsp extension oid names
-    pub static ref OCSP_OID_NAME_LIST: [&'static str; 14] = [
+    pub static ref OCSP_OID_NAME_LIST: [&'static str; 26] = [
         OCSP_EXT_NONCE_NAME,
         OCSP_EXT_CRLREF_NAME,
         OCSP_EXT_RESP_TYPE_NAME,
@@ -214,10 +579,22 @@ lazy_static! {
         OCSP_RESPONSE_BASIC_NAME,
         ALGO_SHA256_WITH_RSA_ENCRYPTION_NAME,
         ALGO_SHA256_WITH_ECDSA_ENCRYPTION_NAME,
+        ALGO_SHA256_NAME,
+        ALGO_SHA384_NAME,
+        ALGO_SHA512_NAME,
+        ALGO_SHA384_WITH_RSA_ENCRYPTION_NAME,
+        ALGO_SHA512_WITH_RSA_ENCRYPTION_NAME,
+        ALGO_SHA384_WITH_ECDSA_ENCRYPTION_NAME,
+        ALGO_SHA512_WITH_ECDSA_ENCRYPTION_NAME,
+        ALGO_ED25519_NAME,
+        ALGO_RSASSA_PSS_NAME,
+        ALGO_MGF1_NAME,
+        ALGO_RSA_ENCRYPTION_NAME,
+        ALGO_EC_PUBLIC_KEY_NAME,
     ];
 
     /// list of ocsp extension oid in num dot format
-    pub static ref OCSP_OID_DOT_LIST: [&'static str; 14] = [
+    pub static ref OCSP_OID_DOT_LIST: [&'static str; 26] = [
         OCSP_EXT_NONCE_DOT,
         OCSP_EXT_CRLREF_DOT,
         OCSP_EXT_RESP_TYPE_DOT,
@@ -232,10 +609,22 @@ lazy_static! {
         OCSP_RESPONSE_BASIC_DOT,
         ALGO_SHA256_WITH_RSA_ENCRYPTION_DOT,
         ALGO_SHA256_WITH_ECDSA_ENCRYPTION_DOT,
+        ALGO_SHA256_DOT,
+        ALGO_SHA384_DOT,
+        ALGO_SHA512_DOT,
+        ALGO_SHA384_WITH_RSA_ENCRYPTION_DOT,
+        ALGO_SHA512_WITH_RSA_ENCRYPTION_DOT,
+        ALGO_SHA384_WITH_ECDSA_ENCRYPTION_DOT,
+        ALGO_SHA512_WITH_ECDSA_ENCRYPTION_DOT,
+        ALGO_ED25519_DOT,
+        ALGO_RSASSA_PSS_DOT,
+        ALGO_MGF1_DOT,
+        ALGO_RSA_ENCRYPTION_DOT,
+        ALGO_EC_PUBLIC_KEY_DOT,
     ];
 
     /// list of ocsp extension oid in bytes
-    pub static ref OCSP_OID_HEX_LIST: [Bytes; 14] = [
+    pub static ref OCSP_OID_HEX_LIST: [Bytes; 26] = [
         OCSP_EXT_NONCE_HEX.to_vec(),
         OCSP_EXT_CRLREF_HEX.to_vec(),
         OCSP_EXT_RESP_TYPE_HEX.to_vec(),
@@ -250,6 +639,18 @@ lazy_static! {
         OCSP_RESPONSE_BASIC_HEX.to_vec(),
         ALGO_SHA256_WITH_RSA_ENCRYPTION_HEX.to_vec(),
         ALGO_SHA256_WITH_ECDSA_ENCRYPTION_HEX.to_vec(),
+        ALGO_SHA256_HEX.to_vec(),
+        ALGO_SHA384_HEX.to_vec(),
+        ALGO_SHA512_HEX.to_vec(),
+        ALGO_SHA384_WITH_RSA_ENCRYPTION_HEX.to_vec(),
+        ALGO_SHA512_WITH_RSA_ENCRYPTION_HEX.to_vec(),
+        ALGO_SHA384_WITH_ECDSA_ENCRYPTION_HEX.to_vec(),
+        ALGO_SHA512_WITH_ECDSA_ENCRYPTION_HEX.to_vec(),
+        ALGO_ED25519_HEX.to_vec(),
+        ALGO_RSASSA_PSS_HEX.to_vec(),
+        ALGO_MGF1_HEX.to_vec(),
+        ALGO_RSA_ENCRYPTION_HEX.to_vec(),
+        ALGO_EC_PUBLIC_KEY_HEX.to_vec(),
     ];
 }
 
@@ -261,8 +662,111 @@ mod test {
     #[test]
     fn test_dot2oid() {
         let dot = OCSP_EXT_EXTENDED_REVOKE_DOT;
-        let oid = d2i_oid(dot).unwrap().index;
-        assert_eq!(oid, OCSP_EXT_EXTENDED_REVOKE_ID);
+        let oid = d2i_oid(dot).unwrap().index();
+        assert_eq!(oid, Some(OCSP_EXT_EXTENDED_REVOKE_ID));
+    }
+
+    // test arc encoding, with multi-byte arcs
+    #[test]
+    fn test_dot2bytes() {
+        let v = d2b_oid(ALGO_SHA256_WITH_RSA_ENCRYPTION_DOT).unwrap();
+        assert_eq!(v, ALGO_SHA256_WITH_RSA_ENCRYPTION_HEX);
+        let v = d2b_oid("2.999.3").unwrap();
+        assert_eq!(v, vec![0x88, 0x37, 0x03]);
+        assert!(d2b_oid("1.40").is_none());
+        assert!(d2b_oid("1").is_none());
+        assert!(d2b_oid("1..2").is_none());
+    }
+
+    // test arc decoding, with multi-byte arcs
+    #[test]
+    fn test_bytes2dot() {
+        let dot = b2d_oid(&ALGO_SHA256_WITH_ECDSA_ENCRYPTION_HEX).unwrap();
+        assert_eq!(dot, ALGO_SHA256_WITH_ECDSA_ENCRYPTION_DOT);
+        let dot = b2d_oid(&[0x88, 0x37, 0x03]).unwrap();
+        assert_eq!(dot, "2.999.3");
+        // non minimal and truncated arcs
+        assert!(b2d_oid(&[0x2b, 0x80, 0x01]).is_none());
+        assert!(b2d_oid(&[0x2b, 0x86]).is_none());
+        assert!(b2d_oid(&[0x80, 0x01]).is_none());
+        assert!(b2d_oid(&[]).is_none());
+        // 0x80 within an arc is fine
+        assert_eq!("1.3.16384", b2d_oid(&[0x2b, 0x81, 0x80, 0x00]).unwrap());
+    }
+
+    // structure is checked without converting arcs
+    #[test]
+    fn test_is_oid() {
+        assert!(is_oid(&ALGO_SHA256_WITH_ECDSA_ENCRYPTION_HEX));
+        assert!(is_oid(&[0x2b, 0x81, 0x80, 0x00]));
+        assert!(!is_oid(&[0x2b, 0x80, 0x01]));
+        assert!(!is_oid(&[0x80, 0x01]));
+        assert!(!is_oid(&[0x2b, 0x86]));
+        assert!(!is_oid(&[]));
+
+        // arcs past the conversion limit are still valid
+        let mut long = vec![0x2b];
+        long.extend(vec![0xff; OID_ARC_MAX_LEN - 1]);
+        long.push(0x7f);
+        assert!(b2d_oid(&long).is_some());
+        long.insert(1, 0xff);
+        assert!(is_oid(&long));
+        assert!(b2d_oid(&long).is_none());
+    }
+
+    // arcs wider than 64 bits, eg. UUID under 2.25
+    #[test]
+    fn test_large_arcs() {
+        for dot in [
+            "2.25.329800735698586629295641978511506172918",
+            "2.25.340282366920938463463374607431768211455",
+            "1.3.6.1.4.1.18446744073709551616",
+            "2.18446744073709551536",
+            "2.1000000000000000000000000000",
+            "0.39.1000000000.999999999",
+        ] {
+            let v = d2b_oid(dot).unwrap();
+            assert_eq!(dot, b2d_oid(&v).unwrap());
+        }
+        // u128::MAX is 19 base-128 digits
+        let v = d2b_oid("2.25.340282366920938463463374607431768211455").unwrap();
+        assert_eq!(&[0x69, 0x83], &v[..2]);
+        assert_eq!(20, v.len());
+        assert!(v[1..19].iter().all(|b| b & 0x80 != 0));
+        // u64::MAX + 1 needs a tenth byte
+        let v = d2b_oid("1.2.18446744073709551616").unwrap();
+        assert_eq!(
+            vec![0x2a, 0x82, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00],
+            v
+        );
+        assert!(d2b_oid("1.40000000000000000000").is_none());
+        assert!(d2b_oid("3.1").is_none());
+        assert!(d2b_oid("1.+2").is_none());
+    }
+
+    // every oid constant matches its dot notation
+    #[test]
+    fn test_oid_table_consistent() {
+        for (dot, hex) in OCSP_OID_DOT_LIST.iter().zip(OCSP_OID_HEX_LIST.iter()) {
+            assert_eq!(&d2b_oid(dot).unwrap(), hex, "{}", dot);
+        }
+    }
+
+    // certificate oid constants match their dot notation
+    #[test]
+    fn test_cert_oid_consistent() {
+        let list: [(&str, &[u8]); 6] = [
+            (CERT_EXT_KEY_USAGE_DOT, &CERT_EXT_KEY_USAGE_HEX),
+            (CERT_EXT_EXT_KEY_USAGE_DOT, &CERT_EXT_EXT_KEY_USAGE_HEX),
+            (CERT_EXT_AIA_DOT, &CERT_EXT_AIA_HEX),
+            (CERT_EXT_OCSP_NOCHECK_DOT, &CERT_EXT_OCSP_NOCHECK_HEX),
+            (AD_OCSP_DOT, &AD_OCSP_HEX),
+            (KP_OCSP_SIGNING_DOT, &KP_OCSP_SIGNING_HEX),
+        ];
+        for (dot, hex) in list.iter() {
+            assert_eq!(&d2b_oid(dot).unwrap()[..], *hex, "{}", dot);
+            assert!(b2i_oid(hex).is_none());
+        }
     }
 
     // test dot to oid return None for unknown id
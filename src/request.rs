@@ -3,16 +3,22 @@
 use std::{collections::HashMap, vec};
 
 use asn1_der::DerObject;
-use tracing::{debug, error, trace, warn};
+use tracing::{debug, error, trace};
 
 use crate::common::{
     asn1::{
-        asn1_encode_length, Bytes, CertId, Oid, TryIntoSequence, ASN1_BIT_STRING, ASN1_EXPLICIT_0,
-        ASN1_EXPLICIT_1, ASN1_EXPLICIT_2, ASN1_IA5STRING, ASN1_SEQUENCE,
+        asn1_field, asn1_offset, asn1_validate_der, AlgorithmIdentifier, Bytes, CertId, CertIdRef,
+        DecodeMode, SequenceRef, SerialNumber, TryIntoSequence, ASN1_BIT_STRING, ASN1_EXPLICIT_0,
+        ASN1_EXPLICIT_1, ASN1_EXPLICIT_2, ASN1_EXPLICIT_4, ASN1_OCTET, ASN1_OID, ASN1_SEQUENCE,
     },
-    ocsp::OcspExtI,
+    der::{DerDecode, DerEncode, DerWriter},
+    name::DistinguishedName,
+    ocsp::{OcspExtI, Version},
 };
 use crate::err::{OcspError, Result};
+use crate::oid::OCSP_EXT_NONCE_HEX;
+use crate::sign::Signer;
+use crate::verify::PublicKey;
 
 /// RFC 6960 Request
 #[derive(Debug)]
@@ -27,12 +33,17 @@ pub struct OneReq {
 impl OneReq {
     /// get single request
     pub fn parse(onereq: &[u8]) -> Result<Self> {
+        OneReq::parse_with_mode(onereq, DecodeMode::Lenient)
+    }
+
+    /// get single request, see [DecodeMode]
+    pub(crate) fn parse_with_mode(onereq: &[u8], mode: DecodeMode) -> Result<Self> {
         trace!("OneReq: {}", hex::encode(onereq));
         let s = onereq.try_into()?;
 
         let cid = s.get(0).map_err(OcspError::Asn1DecodingError)?;
-        let cid = CertId::parse(cid.raw())?;
-        trace!("OneReq cert sn {}", hex::encode(&cid.serial_num));
+        let cid = CertId::parse(cid.raw()).map_err(asn1_field(onereq, cid.raw(), "reqCert"))?;
+        trace!("OneReq cert sn {}", cid.serial_num);
         trace!(
             "OneReq issuer key hash {}",
             hex::encode(&cid.issuer_key_hash)
@@ -44,8 +55,17 @@ impl OneReq {
                 trace!("No extension in OneReq");
             }
             2 => {
-                let raw_ext = s.get(1).map_err(OcspError::Asn1DecodingError)?.raw();
-                ext = Some(OcspExtI::parse(raw_ext)?);
+                let tagging = s.get(1).map_err(OcspError::Asn1DecodingError)?;
+                let field = asn1_field(onereq, tagging.raw(), "singleRequestExtensions");
+                if tagging.tag() != ASN1_EXPLICIT_0 {
+                    return Err(field(OcspError::Asn1MismatchError("OneReq extension")));
+                }
+                let list = tagging.value();
+                ext = Some(OcspExtI::parse_with_mode(list, mode).map_err(asn1_field(
+                    onereq,
+                    list,
+                    "singleRequestExtensions",
+                ))?);
             }
             _ => {
                 error!(
@@ -65,49 +85,39 @@ impl OneReq {
 
     /// encode to ASN.1 DER
     pub fn to_der(&self) -> Result<Bytes> {
-        let mut cid = self.certid.to_der()?;
-        match &self.one_req_ext {
-            Some(r) => {
-                cid.extend(OcspExtI::list_to_der(r, ASN1_EXPLICIT_0)?);
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for OneReq {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
+        w.write_sequence(|w| {
+            self.certid.write_der(w)?;
+            if let Some(r) = &self.one_req_ext {
+                w.write_tagged(ASN1_EXPLICIT_0, |w| r.write_der(w))?;
             }
-            None => {}
-        }
-        let len = asn1_encode_length(cid.len())?;
-        let mut r = vec![ASN1_SEQUENCE];
-        r.extend(len);
-        r.extend(cid);
+            Ok(())
+        })?;
 
         trace!("OneReq successfully encoded");
-        Ok(r)
+        Ok(())
     }
+}
 
-    /// encode a list of onereq
-    pub fn list_to_der(onereq_list: &[OneReq]) -> Result<Bytes> {
-        trace!("Encoding {} OneReq.", onereq_list.len());
-
-        let mut r = Vec::new();
-
-        for one in onereq_list {
-            r.extend(one.to_der()?);
-        }
-
-        let mut v = vec![ASN1_SEQUENCE];
-        v.extend(asn1_encode_length(r.len())?);
-        v.extend(r);
-
-        Ok(v)
+impl<'a> DerDecode<'a> for OneReq {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        OneReq::parse(raw)
     }
 }
 
-/// RFC 6960 TBSRequest  
-/// version is omitted as data produced from OpenSSL doesn't contain version  
-/// REVIEW: omit version in tbs request
+/// RFC 6960 TBSRequest
 #[derive(Debug)]
 pub struct TBSRequest {
-    // explicit tag 0
-    // version: u8,
-    /// requestorName is OPTIONAL and indicates the name of the OCSP requestor.
-    /// explicit 1
+    /// explicit tag 0, DEFAULT v1  
+    /// data produced from OpenSSL doesn't contain version
+    pub version: Version,
+    /// requestorName is OPTIONAL and indicates the name of the OCSP requestor.  
+    /// explicit 1, GeneralName kept in DER, see [TBSRequest::requestor_dn]
     pub requestor_name: Option<Bytes>,
     /// requestList contains one or more single certificate status requests.
     pub request_list: Vec<OneReq>,
@@ -119,7 +129,13 @@ pub struct TBSRequest {
 impl TBSRequest {
     /// parse a tbs request
     pub fn parse(tbs: &[u8]) -> Result<Self> {
+        TBSRequest::parse_with_mode(tbs, DecodeMode::Lenient)
+    }
+
+    /// parse a tbs request, see [DecodeMode]
+    pub(crate) fn parse_with_mode(tbs: &[u8], mode: DecodeMode) -> Result<Self> {
         trace!("Parsing tbsrequest {}", hex::encode(tbs));
+        let mut version = Version::default();
         let mut name = None;
         let mut ext = None;
         let mut req: Vec<OneReq> = Vec::new();
@@ -130,22 +146,29 @@ impl TBSRequest {
             let tbs_item = s.get(i).map_err(OcspError::Asn1DecodingError)?;
             match tbs_item.tag() {
                 ASN1_EXPLICIT_0 => {
-                    warn!("Version in TBS REQUEST is defined in RFC but yet implemented");
-                    unimplemented!()
+                    trace!("Found tbs version");
+                    let field = asn1_field(tbs, tbs_item.raw(), "version");
+                    version = Version::parse(tbs_item.value()).map_err(field)?;
+                    if mode.is_strict() && version == Version::default() {
+                        error!("Found explicit v1 in tbs request");
+                        return Err(field(OcspError::Asn1ExplicitDefault("version")));
+                    }
                 }
                 ASN1_EXPLICIT_1 => {
                     trace!("Found requestor name");
+                    let field = asn1_field(tbs, tbs_item.raw(), "requestorName");
                     let val = tbs_item.value();
-                    let val = DerObject::decode(val).map_err(OcspError::Asn1DecodingError)?;
-                    if val.tag() != ASN1_IA5STRING {
-                        return Err(OcspError::Asn1MismatchError("TBS requestor name"));
-                    }
-                    name = Some(val.value().to_vec());
+                    // keep GeneralName as it is
+                    let val = DerObject::decode(val)
+                        .map_err(OcspError::Asn1DecodingError)
+                        .map_err(field)?;
+                    name = Some(val.raw().to_vec());
                 }
                 ASN1_EXPLICIT_2 => {
                     trace!("Found tbs extension");
                     let ext_list = tbs_item.value();
-                    let ext_list = OcspExtI::parse(ext_list)?;
+                    let ext_list = OcspExtI::parse_with_mode(ext_list, mode)
+                        .map_err(asn1_field(tbs, tbs_item.value(), "requestExtensions"))?;
                     ext = Some(ext_list);
                 }
                 ASN1_SEQUENCE => {
@@ -153,10 +176,9 @@ impl TBSRequest {
                     trace!("Found {} OneReq", req_list.len());
                     for j in 0..req_list.len() {
                         let onereq = req_list.get(j).map_err(OcspError::Asn1DecodingError)?;
-                        // onereq.raw() here always return the full sequence starting from first onereq
-                        let mut current = onereq.header().to_vec();
-                        current.extend(onereq.value());
-                        let onereq = OneReq::parse(&current)?;
+                        let offset = asn1_offset(tbs, onereq.raw());
+                        let onereq = OneReq::parse_with_mode(onereq.raw(), mode)
+                            .map_err(|e| e.at(&format!("requestList[{}]", j), offset))?;
                         req.push(onereq);
                     }
                 }
@@ -168,30 +190,86 @@ impl TBSRequest {
 
         trace!("Tbs request successfully decoded");
         Ok(TBSRequest {
+            version,
             requestor_name: name,
             request_list: req,
             request_ext: ext,
         })
     }
+
+    /// get requestor name as distinguished name, None if there is no requestor name  
+    /// return error if requestor name is not a directoryName
+    pub fn requestor_dn(&self) -> Result<Option<DistinguishedName>> {
+        let name = match &self.requestor_name {
+            Some(n) => n,
+            None => return Ok(None),
+        };
+        let name = DerObject::decode(name).map_err(OcspError::Asn1DecodingError)?;
+        if name.tag() != ASN1_EXPLICIT_4 {
+            return Err(OcspError::Asn1MismatchError("TBS requestor name"));
+        }
+        DistinguishedName::parse(name.value()).map(Some)
+    }
+
+    /// encode to ASN.1 DER
+    pub fn to_der(&self) -> Result<Bytes> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for TBSRequest {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
+        trace!(
+            "Encoding tbs request with {} OneReq",
+            self.request_list.len()
+        );
+
+        w.write_sequence(|w| {
+            // DEFAULT version is omitted
+            self.version.write_der(w)?;
+            if let Some(name) = &self.requestor_name {
+                trace!("Found requestor name");
+                w.write_tagged(ASN1_EXPLICIT_1, |w| {
+                    w.write_raw(name);
+                    Ok(())
+                })?;
+            }
+            self.request_list.write_der(w)?;
+            if let Some(ext) = &self.request_ext {
+                trace!("Found {} tbs extensions", ext.len());
+                w.write_tagged(ASN1_EXPLICIT_2, |w| ext.write_der(w))?;
+            }
+            Ok(())
+        })?;
+
+        trace!("Tbs request successfully encoded");
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for TBSRequest {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        TBSRequest::parse(raw)
+    }
 }
 
 /// RFC 6960 Signature
 /// Optional signature in ocsp request  
 /// The requestor MAY choose to sign the OCSP request.  
-/// In that case, the signature is computed over the tbsRequest structure.
-/// REVIEW: *untested*
-/// REVIEW: If the request is signed, the requestor SHALL specify its name in the requestorName field.
+/// In that case, the signature is computed over the tbsRequest structure.  
+/// see [OcspRequest::sign] and [OcspRequest::verify_signature]
 #[derive(Debug)]
 pub struct Signature {
-    /// algo oid for signature
-    pub signing_algo: Oid,
+    /// signature algorithm
+    pub signing_algo: AlgorithmIdentifier,
     /// tho RFC 6960 indicates signature is BIT STRING,  
     /// which has arbitrary length comparing to OCTET,  
     /// but all signatures' length are multiple of 8,  
-    /// so using Vec\<u8\> here.
+    /// so using Vec\<u8\> here, without the unused bits byte.
     pub signature: Bytes,
-    /// \[0\] EXPLICIT SEQUENCE OF Certificate OPTIONAL
-    pub certs: Option<Bytes>,
+    /// \[0\] EXPLICIT SEQUENCE OF Certificate OPTIONAL  
+    /// each certificate is kept in DER
+    pub certs: Option<Vec<Bytes>>,
 }
 
 impl Signature {
@@ -200,51 +278,160 @@ impl Signature {
         trace!("Raw signature: {}", hex::encode(sig));
         let s = sig.try_into()?;
 
-        let oid;
-        let signature;
+        if !(2..=3).contains(&s.len()) {
+            error!(
+                "Provided signature contains {} items, expecting 2 or 3",
+                s.len()
+            );
+            return Err(OcspError::Asn1LengthError("SIGNATURE"));
+        }
 
-        match s.len() {
-            2 => {
-                let id = s.get(0).map_err(OcspError::Asn1DecodingError)?;
-                oid = Oid::parse(id.raw())?;
+        let id = s.get(0).map_err(OcspError::Asn1DecodingError)?;
+        let oid = AlgorithmIdentifier::parse(id.raw()).map_err(asn1_field(
+            sig,
+            id.raw(),
+            "signatureAlgorithm",
+        ))?;
 
-                let raw = s.get(1).map_err(OcspError::Asn1DecodingError)?;
-                if raw.tag() != ASN1_BIT_STRING {
-                    return Err(OcspError::Asn1MismatchError("SIGNATURE"));
-                }
-                signature = raw.value().to_vec();
-            }
-            3 => {
-                warn!("Signature CERT is defined in RFC but yet implemented");
-                unimplemented!()
+        let raw = s.get(1).map_err(OcspError::Asn1DecodingError)?;
+        // leading byte is the number of unused bits
+        if raw.tag() != ASN1_BIT_STRING || raw.value().first() != Some(&0x00) {
+            error!("Provided signature is not a BIT STRING of whole bytes");
+            let e = OcspError::Asn1MismatchError("SIGNATURE");
+            return Err(asn1_field(sig, raw.raw(), "signature")(e));
+        }
+        let signature = raw.value()[1..].to_vec();
+
+        let mut certs = None;
+        if s.len() == 3 {
+            let tagging = s.get(2).map_err(OcspError::Asn1DecodingError)?;
+            let field = asn1_field(sig, tagging.raw(), "certs");
+            if tagging.tag() != ASN1_EXPLICIT_0 {
+                return Err(field(OcspError::Asn1MismatchError("SIGNATURE certs")));
             }
-            _ => return Err(OcspError::Asn1LengthError("SIGNATURE")),
+            let list = tagging.value();
+            let v = SequenceRef::decode(list)
+                .map(|l| l.iter().map(|c| c.raw().to_vec()).collect::<Vec<_>>())
+                .map_err(field)?;
+            trace!("Found {} certs in signature", v.len());
+            certs = Some(v);
         }
 
         trace!("Ocsp request signature successfully decoded");
         Ok(Signature {
             signing_algo: oid,
             signature,
-            // unimplemented 3
-            certs: None,
+            certs,
         })
     }
+
+    /// encode to ASN.1 DER  
+    /// - without explicit 0 tagging from ocsp request
+    pub fn to_der(&self) -> Result<Bytes> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for Signature {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
+        trace!("Encoding request signature");
+
+        w.write_sequence(|w| {
+            self.signing_algo.write_der(w)?;
+            // no unused bits
+            w.write_tagged(ASN1_BIT_STRING, |w| {
+                w.write_raw(&[0x00]);
+                w.write_raw(&self.signature);
+                Ok(())
+            })?;
+            if let Some(certs) = &self.certs {
+                trace!("Found {} certs in signature", certs.len());
+                w.write_tagged(ASN1_EXPLICIT_0, |w| {
+                    w.write_sequence(|w| {
+                        certs.iter().for_each(|c| w.write_raw(c));
+                        Ok(())
+                    })
+                })?;
+            }
+            Ok(())
+        })?;
+
+        trace!("Request signature successfully encoded");
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for Signature {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        Signature::parse(raw)
+    }
 }
 
 /// RFC 6960 OCSPRequest
 #[derive(Debug)]
 pub struct OcspRequest {
-    /// RFC 6960 TBSRequest
-    pub tbs_request: TBSRequest,
+    /// RFC 6960 TBSRequest, see [OcspRequest::tbs_request]
+    tbs_request: TBSRequest,
     /// RFC 6960 optionalSignature, explicit tag 0
     pub optional_signature: Option<Signature>,
+    /// DER of tbs request of a signed request as received or signed, signature is verified over it  
+    /// None if request is unsigned, not from parsing or signing, or changed since
+    tbs_raw: Option<Bytes>,
 }
 
 impl OcspRequest {
-    /// parsing an ocsp request from raw bytes
+    /// return new unsigned request, see [OcspRequest::sign]
+    pub fn new(tbs_request: TBSRequest) -> Self {
+        OcspRequest {
+            tbs_request,
+            optional_signature: None,
+            tbs_raw: None,
+        }
+    }
+
+    /// RFC 6960 TBSRequest
+    pub fn tbs_request(&self) -> &TBSRequest {
+        &self.tbs_request
+    }
+
+    /// mutable tbs request  
+    /// bytes as received or signed are dropped, tbs request is encoded again from now on
+    pub fn tbs_request_mut(&mut self) -> &mut TBSRequest {
+        self.tbs_raw = None;
+        &mut self.tbs_request
+    }
+
+    /// replace tbs request, see [OcspRequest::tbs_request_mut]
+    pub fn set_tbs_request(&mut self, tbs_request: TBSRequest) {
+        self.tbs_raw = None;
+        self.tbs_request = tbs_request;
+    }
+
+    /// take tbs request, dropping signature
+    pub fn into_tbs_request(self) -> TBSRequest {
+        self.tbs_request
+    }
+
+    /// DER of tbs request of a signed request as received or signed, signature is verified over it  
+    /// None if request is unsigned, not from parsing or signing, or changed since
+    pub fn tbs_raw(&self) -> Option<&[u8]> {
+        self.tbs_raw.as_deref()
+    }
+
+    /// parsing an ocsp request from raw bytes  
+    /// data after the request is ignored, see [OcspRequest::parse_with_mode]
     pub fn parse(ocsp_req: &[u8]) -> Result<Self> {
-        debug!("Parsing ocsp request");
+        OcspRequest::parse_with_mode(ocsp_req, DecodeMode::Lenient)
+    }
+
+    /// parsing an ocsp request from raw bytes  
+    /// in strict mode, only DER is accepted
+    pub fn parse_with_mode(ocsp_req: &[u8], mode: DecodeMode) -> Result<Self> {
+        debug!("Parsing ocsp request in {:?} mode", mode);
         trace!("Raw ocsp request: {}", hex::encode(ocsp_req));
+        if mode.is_strict() {
+            asn1_validate_der(ocsp_req)?;
+        }
         let s = ocsp_req.try_into()?;
 
         let mut sig = None;
@@ -256,27 +443,112 @@ impl OcspRequest {
                 let sig_v8 = s.get(1).map_err(OcspError::Asn1DecodingError)?;
                 match sig_v8.tag() {
                     ASN1_EXPLICIT_0 => {
+                        let field = asn1_field(ocsp_req, sig_v8.raw(), "optionalSignature");
                         let val = sig_v8.value();
-                        let val = DerObject::decode(val).map_err(OcspError::Asn1DecodingError)?;
-                        sig = Some(Signature::parse(val.value())?);
+                        let val = DerObject::decode(val)
+                            .map_err(OcspError::Asn1DecodingError)
+                            .map_err(field)?;
+                        sig = Some(Signature::parse(val.raw()).map_err(|e| {
+                            e.at("optionalSignature", asn1_offset(ocsp_req, val.raw()))
+                        })?);
                     }
                     _ => return Err(OcspError::Asn1MismatchError("SIGNATURE EXP 0 tag")),
                 }
             }
-            _ => {}
+            n => {
+                error!(
+                    "Provided ocsp request contains {} items, expecting 1 or 2",
+                    n
+                );
+                return Err(OcspError::Asn1LengthError("OCSPRequest"));
+            }
         }
         let req_v8 = s.get(0).map_err(OcspError::Asn1DecodingError)?;
-        let req = TBSRequest::parse(req_v8.raw())?;
+        let req = TBSRequest::parse_with_mode(req_v8.raw(), mode).map_err(asn1_field(
+            ocsp_req,
+            req_v8.raw(),
+            "tbsRequest",
+        ))?;
 
         debug!("Ocsp request successfully decoded");
+        // only signed bytes need keeping
+        let tbs_raw = sig.as_ref().map(|_| req_v8.raw().to_vec());
         Ok(OcspRequest {
             tbs_request: req,
             optional_signature: sig,
+            tbs_raw,
         })
     }
 
+    /// encode to ASN.1 DER  
+    /// tbs request as received or signed is written as is, so signature still matches
+    pub fn to_der(&self) -> Result<Bytes> {
+        let tlv = DerWriter::encode(|w| self.write_der(w))?;
+        trace!("Ocsp request {}", hex::encode(&tlv));
+        Ok(tlv)
+    }
+
+    /// sign tbs request, replacing any previous signature  
+    /// requestor name is set as directoryName, RFC 6960 requires it in signed requests  
+    /// certs are certificates in DER helping responder verify the signature, signer's first
+    pub fn sign<S: Signer + ?Sized>(
+        mut self,
+        signer: &S,
+        requestor_name: &DistinguishedName,
+        certs: Option<Vec<Bytes>>,
+    ) -> Result<Self> {
+        let name = DerWriter::encode(|w| {
+            w.write_tagged(ASN1_EXPLICIT_4, |w| requestor_name.write_der(w))
+        })?;
+        self.tbs_request.requestor_name = Some(name);
+        let tbs = self.tbs_request.to_der()?;
+        let signature = signer.sign(&tbs)?;
+        debug!("Tbs request of {} bytes signed", tbs.len());
+        self.optional_signature = Some(Signature {
+            signing_algo: signer.algorithm(),
+            signature,
+            certs,
+        });
+        self.tbs_raw = Some(tbs);
+        Ok(self)
+    }
+
+    /// verify request signature with the first certificate in it, see [PublicKey::verify]  
+    /// trusting that certificate is left to the caller  
+    /// received tbs request is verified as is, not re-encoded
+    pub fn verify_signature(&self) -> Result<()> {
+        let sig = self
+            .optional_signature
+            .as_ref()
+            .ok_or(OcspError::OcspRequestNotSigned)?;
+        let cert = sig
+            .certs
+            .as_ref()
+            .and_then(|c| c.first())
+            .ok_or(OcspError::OcspSignerCertNotFound)?;
+        let key = PublicKey::from_certificate_der(cert)?;
+        self.verify_signature_with(&key)
+    }
+
+    /// verify request signature with a requestor public key known beforehand
+    pub fn verify_signature_with(&self, key: &PublicKey) -> Result<()> {
+        let sig = self
+            .optional_signature
+            .as_ref()
+            .ok_or(OcspError::OcspRequestNotSigned)?;
+        let encoded;
+        let tbs = match &self.tbs_raw {
+            Some(raw) => raw,
+            None => {
+                encoded = self.tbs_request.to_der()?;
+                &encoded
+            }
+        };
+        key.verify(&sig.signing_algo, tbs, &sig.signature)
+    }
+
     /// extract all cert serial numbers from request
-    pub fn extract_cert_sn(&self) -> Vec<&Bytes> {
+    pub fn extract_cert_sn(&self) -> Vec<&SerialNumber> {
         let mut sn = vec![];
         let list = &self.tbs_request.request_list;
         list.iter().for_each(|r| sn.push(&(r.certid.serial_num)));
@@ -311,8 +583,9 @@ impl OcspRequest {
         cid
     }
 
-    /// extract cid map sn to cid
-    pub fn extract_certid_map(&self) -> HashMap<Bytes, CertId> {
+    /// extract cid map sn to cid  
+    /// cids from different issuers may share a serial number, see [IssuerIndex](crate::common::issuer::IssuerIndex)
+    pub fn extract_certid_map(&self) -> HashMap<SerialNumber, CertId> {
         let mut map = HashMap::new();
         let list = &self.tbs_request.request_list;
         list.iter().for_each(|r| {
@@ -322,6 +595,241 @@ impl OcspRequest {
     }
 }
 
+impl DerEncode for OcspRequest {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
+        debug!("Encoding ocsp request");
+
+        w.write_sequence(|w| {
+            match &self.tbs_raw {
+                Some(raw) => w.write_raw(raw),
+                None => self.tbs_request.write_der(w)?,
+            }
+            if let Some(sig) = &self.optional_signature {
+                trace!("Found ocsp request signature");
+                w.write_tagged(ASN1_EXPLICIT_0, |w| sig.write_der(w))?;
+            }
+            Ok(())
+        })?;
+
+        debug!("Ocsp request successfully encoded");
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for OcspRequest {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        OcspRequest::parse(raw)
+    }
+}
+
+/// RFC 6960 Request borrowed from raw bytes  
+/// see [OneReq] for the owned type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OneReqRef<'a> {
+    /// cid of a single request
+    pub certid: CertIdRef<'a>,
+    /// extension of a single request  
+    /// SEQUENCE OF Extension in DER, without explicit 0 tagging
+    pub one_req_ext: Option<&'a [u8]>,
+}
+
+impl<'a> OneReqRef<'a> {
+    /// get borrowed single request
+    pub fn parse(onereq: &'a [u8]) -> Result<Self> {
+        trace!("Borrowed OneReq: {}", hex::encode(onereq));
+        let s = SequenceRef::decode(onereq)?;
+
+        let mut items = s.iter();
+        let mut ext = None;
+        let cid = match (items.next(), items.next()) {
+            (Some(cid), None) => cid,
+            (Some(cid), Some(tagging)) if s.len() == 2 => {
+                if tagging.tag() != ASN1_EXPLICIT_0 {
+                    return Err(OcspError::Asn1MismatchError("OneReq extension"));
+                }
+                ext = Some(tagging.value());
+                cid
+            }
+            _ => {
+                error!(
+                    "OneReq contains {} items, expecting no more than 2: cid and extension",
+                    s.len()
+                );
+                return Err(OcspError::Asn1LengthError("OneReq"));
+            }
+        };
+
+        Ok(OneReqRef {
+            certid: CertIdRef::parse(cid.raw()).map_err(asn1_field(
+                onereq,
+                cid.raw(),
+                "reqCert",
+            ))?,
+            one_req_ext: ext,
+        })
+    }
+
+    /// copy into an owned [OneReq], decoding extensions
+    pub fn to_owned(&self) -> Result<OneReq> {
+        Ok(OneReq {
+            certid: self.certid.to_owned()?,
+            one_req_ext: self.one_req_ext.map(OcspExtI::parse).transpose()?,
+        })
+    }
+}
+
+impl<'a> DerDecode<'a> for OneReqRef<'a> {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        OneReqRef::parse(raw)
+    }
+}
+
+/// RFC 6960 OCSPRequest borrowed from raw bytes  
+/// fields are slices of the input, only the request list allocates  
+/// extensions and signature are decoded in [OcspRequestRef::to_owned]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OcspRequestRef<'a> {
+    /// RFC 6960 TBSRequest in DER, as covered by the signature
+    pub tbs_request: &'a [u8],
+    /// explicit tag 0, DEFAULT v1
+    pub version: Version,
+    /// requestor name, explicit 1, GeneralName in DER
+    pub requestor_name: Option<&'a [u8]>,
+    /// single certificate status requests
+    pub request_list: Vec<OneReqRef<'a>>,
+    /// request extensions  
+    /// SEQUENCE OF Extension in DER, without explicit 2 tagging
+    pub request_ext: Option<&'a [u8]>,
+    /// RFC 6960 Signature in DER, without explicit 0 tagging
+    pub optional_signature: Option<&'a [u8]>,
+}
+
+impl<'a> OcspRequestRef<'a> {
+    /// get borrowed ocsp request from raw bytes  
+    /// data after the request is ignored, same as [OcspRequest::parse]
+    pub fn parse(ocsp_req: &'a [u8]) -> Result<Self> {
+        trace!("Raw borrowed ocsp request: {}", hex::encode(ocsp_req));
+        let s = SequenceRef::decode(ocsp_req)?;
+
+        let mut items = s.iter();
+        let (tbs, sig) = match (items.next(), items.next()) {
+            (Some(tbs), sig) if s.len() <= 2 => (tbs, sig),
+            _ => return Err(OcspError::Asn1LengthError("OCSPRequest")),
+        };
+        let sig = match sig {
+            Some(tagging) => {
+                if tagging.tag() != ASN1_EXPLICIT_0 {
+                    return Err(OcspError::Asn1MismatchError("SIGNATURE EXP 0 tag"));
+                }
+                let val =
+                    DerObject::decode(tagging.value()).map_err(OcspError::Asn1DecodingError)?;
+                if val.tag() != ASN1_SEQUENCE {
+                    return Err(OcspError::Asn1MismatchError("SIGNATURE"));
+                }
+                Some(val.raw())
+            }
+            None => None,
+        };
+
+        let mut req = OcspRequestRef {
+            tbs_request: tbs.raw(),
+            version: Version::default(),
+            requestor_name: None,
+            request_list: vec![],
+            request_ext: None,
+            optional_signature: sig,
+        };
+
+        let tbs_field = asn1_field(ocsp_req, tbs.raw(), "tbsRequest");
+        for tbs_item in SequenceRef::decode(tbs.raw()).map_err(tbs_field)?.iter() {
+            match tbs_item.tag() {
+                ASN1_EXPLICIT_0 => {
+                    req.version = Version::parse(tbs_item.value())
+                        .map_err(asn1_field(tbs.raw(), tbs_item.raw(), "version"))
+                        .map_err(tbs_field)?;
+                }
+                ASN1_EXPLICIT_1 => {
+                    let field = asn1_field(tbs.raw(), tbs_item.raw(), "requestorName");
+                    let val = DerObject::decode(tbs_item.value())
+                        .map_err(OcspError::Asn1DecodingError)
+                        .map_err(field)
+                        .map_err(tbs_field)?;
+                    req.requestor_name = Some(val.raw());
+                }
+                ASN1_EXPLICIT_2 => {
+                    req.request_ext = Some(tbs_item.value());
+                }
+                ASN1_SEQUENCE => {
+                    let list = SequenceRef::decode(tbs_item.raw())?;
+                    req.request_list.reserve_exact(list.len());
+                    for (j, onereq) in list.iter().enumerate() {
+                        let onereq = OneReqRef::parse(onereq.raw()).map_err(|e| {
+                            let offset = asn1_offset(tbs.raw(), onereq.raw());
+                            tbs_field(e.at(&format!("requestList[{}]", j), offset))
+                        })?;
+                        req.request_list.push(onereq);
+                    }
+                }
+                _ => {
+                    return Err(tbs_field(OcspError::Asn1MismatchError("TBS Request")));
+                }
+            }
+        }
+
+        debug!(
+            "Borrowed ocsp request with {} OneReq successfully decoded",
+            req.request_list.len()
+        );
+        Ok(req)
+    }
+
+    /// return nonce extension value from request extensions  
+    /// other extensions are skipped without decoding
+    pub fn nonce(&self) -> Result<Option<&'a [u8]>> {
+        let list = match self.request_ext {
+            Some(l) => SequenceRef::decode(l)?,
+            None => return Ok(None),
+        };
+        for ext in list.iter() {
+            let ext = SequenceRef::decode(ext.raw())?;
+            if !(2..=3).contains(&ext.len()) {
+                return Err(OcspError::Asn1LengthError("Extension"));
+            }
+            let mut items = ext.iter();
+            match (items.next(), items.last()) {
+                (Some(oid), _) if oid.tag() != ASN1_OID || oid.value() != OCSP_EXT_NONCE_HEX => {}
+                (_, Some(octet)) if octet.tag() == ASN1_OCTET => return Ok(Some(octet.value())),
+                _ => return Err(OcspError::Asn1MismatchError("Extension value")),
+            }
+        }
+        Ok(None)
+    }
+
+    /// copy into an owned [OcspRequest], decoding extensions and signature
+    pub fn to_owned(&self) -> Result<OcspRequest> {
+        let mut list = Vec::with_capacity(self.request_list.len());
+        for r in &self.request_list {
+            list.push(r.to_owned()?);
+        }
+        Ok(OcspRequest {
+            tbs_request: TBSRequest {
+                version: self.version,
+                requestor_name: self.requestor_name.map(<[u8]>::to_vec),
+                request_list: list,
+                request_ext: self.request_ext.map(OcspExtI::parse).transpose()?,
+            },
+            optional_signature: self.optional_signature.map(Signature::parse).transpose()?,
+            tbs_raw: self.optional_signature.map(|_| self.tbs_request.to_vec()),
+        })
+    }
+}
+
+impl<'a> DerDecode<'a> for OcspRequestRef<'a> {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        OcspRequestRef::parse(raw)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use asn1_der::{
@@ -330,9 +838,13 @@ mod test {
     };
     use tracing_subscriber::{layer::SubscriberExt, EnvFilter, Registry};
 
+    use crate::common::asn1::{asn1_validate_der, DecodeMode, Oid, SerialNumber, ASN1_INTEGER};
+    use crate::common::der::{DerDecode, DerEncode, DerWriter};
+    use crate::err::OcspError;
     use crate::oid::i2b_oid;
+    use crate::response::OcspRespStatus;
 
-    use super::{CertId, OcspRequest, Oid, OneReq, TBSRequest};
+    use super::{CertId, OcspRequest, OcspRequestRef, OneReq, Signature, TBSRequest, Version};
 
     // init log
     #[allow(dead_code)]
@@ -358,6 +870,82 @@ mod test {
         assert_eq!(onereq_v8, v)
     }
 
+    // two certs with nonce round trip
+    #[test]
+    fn ocsprequest_to_der() {
+        let ocsp_req_hex = "3081B53081B230818A30433041300906\
+    052B0E03021A05000414694D18A9BE42\
+    F7802614D4844F23601478B788200414\
+    397BE002A2F571FD80DCEB52A17A7F8B\
+    632BE755020841300983331F9D4F3043\
+    3041300906052B0E03021A0500041469\
+    4D18A9BE42F7802614D4844F23601478\
+    B788200414397BE002A2F571FD80DCEB\
+    52A17A7F8B632BE75502086378E51D44\
+    8FF46DA2233021301F06092B06010505\
+    07300102041204105E7A74E51C861A3F\
+    79454658BB090244";
+        let req_v8 = hex::decode(ocsp_req_hex).unwrap();
+        let req = OcspRequest::parse(&req_v8[..]).unwrap();
+        assert_eq!(req_v8, req.to_der().unwrap());
+    }
+
+    // tbs request with nonce round trip
+    #[test]
+    fn tbs_nonce_ext_to_der() {
+        let tbs_hex = "306c304530433041300906052b0e\
+    03021a05000414694d18a9be42f78026\
+    14d4844f23601478b788200414397be0\
+    02a2f571fd80dceb52a17a7f8b632be7\
+    5502086378e51d448ff46da223302130\
+    1f06092b060105050730010204120410\
+    1cfc8fa3f5e15ed760707bc46670559b";
+        let tbs_v8 = hex::decode(tbs_hex).unwrap();
+        let tbs = TBSRequest::parse(&tbs_v8[..]).unwrap();
+        assert_eq!(tbs_v8, tbs.to_der().unwrap());
+    }
+
+    // signed request with requestor name round trip
+    #[test]
+    fn signed_ocsprequest_to_der() {
+        let onereq_hex = "30433041300906052b0e\
+    03021a05000414694d18a9be42f78026\
+    14d4844f23601478b788200414397be0\
+    02a2f571fd80dceb52a17a7f8b632be7\
+    5502086378e51d448ff46d";
+        let onereq_v8 = hex::decode(onereq_hex).unwrap();
+        // rfc822Name
+        let name = [&[0x81, 0x10][..], b"ocsp@example.com"].concat();
+        let tbs = TBSRequest {
+            version: Version::V1,
+            requestor_name: Some(name.clone()),
+            request_list: vec![OneReq::parse(&onereq_v8[..]).unwrap()],
+            request_ext: None,
+        };
+        let sig = Signature {
+            signing_algo: Oid::new_from_dot(crate::oid::ALGO_SHA256_WITH_RSA_ENCRYPTION_DOT)
+                .unwrap()
+                .into(),
+            signature: vec![0x01, 0x02, 0x03],
+            certs: Some(vec![
+                vec![0x30, 0x03, 0x02, 0x01, 0x01],
+                vec![0x30, 0x03, 0x02, 0x01, 0x02],
+            ]),
+        };
+        let mut req = OcspRequest::new(tbs);
+        req.optional_signature = Some(sig);
+        let v = req.to_der().unwrap();
+
+        let parsed = OcspRequest::parse(&v[..]).unwrap();
+        assert_eq!(parsed.tbs_request().requestor_name, Some(name));
+        assert!(parsed.tbs_request().requestor_dn().is_err());
+        let sig = parsed.optional_signature.as_ref().unwrap();
+        assert_eq!(sig.signature, vec![0x01, 0x02, 0x03]);
+        let certs = sig.certs.as_ref();
+        assert_eq!(certs.unwrap()[1], vec![0x30, 0x03, 0x02, 0x01, 0x02]);
+        assert_eq!(v, parsed.to_der().unwrap());
+    }
+
     // extracting cert cids from request
     #[test]
     fn extract_cert_certid_owned() {
@@ -379,8 +967,8 @@ mod test {
         let mut v = vec![];
         cid_list.iter().for_each(|c| v.push(c.serial_num.clone()));
 
-        let c1 = vec![0x41u8, 0x30, 0x09, 0x83, 0x33, 0x1F, 0x9D, 0x4F];
-        let c2 = vec![0x63, 0x78, 0xE5, 0x1D, 0x44, 0x8F, 0xF4, 0x6D];
+        let c1 = SerialNumber::from_bytes(&[0x41u8, 0x30, 0x09, 0x83, 0x33, 0x1F, 0x9D, 0x4F]);
+        let c2 = SerialNumber::from_bytes(&[0x63, 0x78, 0xE5, 0x1D, 0x44, 0x8F, 0xF4, 0x6D]);
         let c = vec![c1, c2];
 
         assert_eq!(c, v);
@@ -405,8 +993,8 @@ mod test {
         let req = OcspRequest::parse(&req_v8[..]).unwrap();
         let v = req.extract_cert_sn();
 
-        let c1 = vec![0x41u8, 0x30, 0x09, 0x83, 0x33, 0x1F, 0x9D, 0x4F];
-        let c2 = vec![0x63, 0x78, 0xE5, 0x1D, 0x44, 0x8F, 0xF4, 0x6D];
+        let c1 = SerialNumber::from_bytes(&[0x41u8, 0x30, 0x09, 0x83, 0x33, 0x1F, 0x9D, 0x4F]);
+        let c2 = SerialNumber::from_bytes(&[0x63, 0x78, 0xE5, 0x1D, 0x44, 0x8F, 0xF4, 0x6D]);
         let c = vec![&c1, &c2];
 
         assert_eq!(c, v);
@@ -494,6 +1082,451 @@ mod test {
         let _ = TBSRequest::parse(&tbs_v8[..]).unwrap();
     }
 
+    // explicit v1 is accepted and omitted when encoding
+    #[test]
+    fn parse_tbs_explicit_version() {
+        let tbs_hex = "304ca003020100304530433041300906\
+    052b0e03021a05000414694d18a9be42\
+    f7802614d4844f23601478b788200414\
+    397be002a2f571fd80dceb52a17a7f8b\
+    632be75502086378e51d448ff46d";
+        let tbs_v8 = hex::decode(tbs_hex).unwrap();
+        let tbs = TBSRequest::parse(&tbs_v8[..]).unwrap();
+        assert_eq!(tbs.version, Version::V1);
+        // same tbs without version
+        let v = tbs.to_der().unwrap();
+        assert_eq!(&tbs_v8[7..], &v[2..]);
+
+        // explicit DEFAULT is not DER
+        let mut req_v8 = vec![0x30, 0x4e];
+        req_v8.extend(&tbs_v8);
+        let _ = OcspRequest::parse(&req_v8[..]).unwrap();
+        let e = OcspRequest::parse_with_mode(&req_v8[..], DecodeMode::Strict).unwrap_err();
+        match e.root() {
+            OcspError::Asn1ExplicitDefault("version") => {}
+            r => panic!("unexpected {:?}", r),
+        }
+        assert_eq!(Some("tbsRequest.version"), e.path());
+        assert_eq!(Some(4), e.offset());
+        // DER request is accepted
+        let mut req_v8 = vec![0x30, 0x49];
+        req_v8.extend(&v);
+        let _ = OcspRequest::parse_with_mode(&req_v8[..], DecodeMode::Strict).unwrap();
+    }
+
+    // borrowed request points into input and matches owned decoding
+    #[test]
+    fn ocsprequest_ref_parse() {
+        let ocsp_req_hex = "3081B53081B230818A30433041300906\
+    052B0E03021A05000414694D18A9BE42\
+    F7802614D4844F23601478B788200414\
+    397BE002A2F571FD80DCEB52A17A7F8B\
+    632BE755020841300983331F9D4F3043\
+    3041300906052B0E03021A0500041469\
+    4D18A9BE42F7802614D4844F23601478\
+    B788200414397BE002A2F571FD80DCEB\
+    52A17A7F8B632BE75502086378E51D44\
+    8FF46DA2233021301F06092B06010505\
+    07300102041204105E7A74E51C861A3F\
+    79454658BB090244";
+        let req_v8 = hex::decode(ocsp_req_hex).unwrap();
+        let req = OcspRequestRef::parse(&req_v8[..]).unwrap();
+        assert_eq!(&req_v8[3..], req.tbs_request);
+        assert_eq!(2, req.request_list.len());
+        assert_eq!(
+            &[0x41u8, 0x30, 0x09, 0x83, 0x33, 0x1F, 0x9D, 0x4F],
+            req.request_list[0].certid.serial_num
+        );
+        assert_eq!(Some(&req_v8[req_v8.len() - 18..]), req.nonce().unwrap());
+        assert!(req.optional_signature.is_none());
+
+        let owned = req.to_owned().unwrap();
+        assert_eq!(req_v8, owned.to_der().unwrap());
+        assert_eq!(req_v8.len(), owned.encoded_len().unwrap());
+        let req = OcspRequestRef::from_der(&req_v8[..]).unwrap();
+        assert_eq!(2, req.request_list.len());
+        let parsed = OcspRequest::parse(&req_v8[..]).unwrap();
+        assert_eq!(parsed.extract_certid(), owned.extract_certid());
+    }
+
+    // borrowed signed request
+    #[test]
+    fn signed_ocsprequest_ref_parse() {
+        let req_hex = "307f305ba11216106f63737040657861\
+    6d706c652e636f6d3045304330413009\
+    06052b0e03021a05000414694d18a9be\
+    42f7802614d4844f23601478b7882004\
+    14397be002a2f571fd80dceb52a17a7f\
+    8b632be75502086378e51d448ff46da0\
+    20301e300d06092a864886f70d01010b\
+    0500030400010203a007300530030201\
+    01";
+        let req_v8 = hex::decode(req_hex).unwrap();
+        let req = OcspRequestRef::parse(&req_v8[..]).unwrap();
+        assert_eq!(Some(&req_v8[6..24]), req.requestor_name);
+        assert_eq!(Some(&req_v8[97..]), req.optional_signature);
+        assert_eq!(None, req.nonce().unwrap());
+        let owned = req.to_owned().unwrap();
+        let sig = owned.optional_signature.as_ref().unwrap();
+        assert_eq!(vec![0x01, 0x02, 0x03], sig.signature);
+        assert_eq!(req_v8, owned.to_der().unwrap());
+    }
+
+    // signature BIT STRING with unused bits
+    #[test]
+    fn signature_unused_bits() {
+        let sig_hex = "3015300d06092a864886f70d01010b0500030401010203";
+        let sig_v8 = hex::decode(sig_hex).unwrap();
+        let e = Signature::parse(&sig_v8[..]).unwrap_err();
+        assert_eq!(Some("signature"), e.path());
+        assert_eq!(Some(17), e.offset());
+    }
+
+    // request signed by OpenSSL with P-256 CA, requestor name is directoryName
+    #[cfg(feature = "ecdsa")]
+    #[test]
+    fn signed_ocsprequest_openssl() {
+        let req_hex = "308202403059a116a41430123110300e\
+    06035504030c0754657374204341303f\
+    303d303b300906052b0e03021a050004\
+    14bf7052c8b9c0f760c89123e099815e\
+    b2c039422604140c316bc36fbf60ce61\
+    08ebbdffd2614737ae0e0d02021234a0\
+    8201e1308201dd300a06082a8648ce3d\
+    0403020349003046022100a76d20bcaa\
+    033b5b5e959243a7ba98c8362239f57a\
+    5b9791d2d850f88f61a0920221009d3b\
+    0efb325a3046281b0434b98f519724c7\
+    dc0201bfffe7914151427be8a333a082\
+    01823082017e3082017a30820121a003\
+    020102021436f4daae513a80584ae673\
+    9910cdd0a8a4f15b66300a06082a8648\
+    ce3d04030230123110300e0603550403\
+    0c07546573742043413020170d323631\
+    3031383037313335355a180f32313236\
+    303932343037313335355a3012311030\
+    0e06035504030c075465737420434130\
+    59301306072a8648ce3d020106082a86\
+    48ce3d030107034200043c22d008fb95\
+    ed378a3db94b2ab90f1dfe731d29cb2d\
+    d8355351dfad208930f6adb02d17abba\
+    eb9841a402524748ccd19cebfa1131c4\
+    7d23624d663a0cfc844da3533051301d\
+    0603551d0e041604140c316bc36fbf60\
+    ce6108ebbdffd2614737ae0e0d301f06\
+    03551d230418301680140c316bc36fbf\
+    60ce6108ebbdffd2614737ae0e0d300f\
+    0603551d130101ff040530030101ff30\
+    0a06082a8648ce3d0403020347003044\
+    02201aa4a3a11c9880e1a28d45ff00d6\
+    a59ca3c2e4d79b628d9f808ba4df24ba\
+    d66e02206156f5e6e38f353fd0ba28d5\
+    7d47db0dcaa9159a42bdf09d0dfae41e\
+    fdfaba09";
+        let req_v8 = hex::decode(req_hex).unwrap();
+        let req = OcspRequest::parse_with_mode(&req_v8[..], DecodeMode::Strict).unwrap();
+        let dn = req.tbs_request().requestor_dn().unwrap().unwrap();
+        assert_eq!("CN=Test CA", dn.to_string());
+        assert_eq!(req_v8, req.to_der().unwrap());
+        req.verify_signature().unwrap();
+
+        let req = OcspRequestRef::parse(&req_v8[..]).unwrap();
+        req.to_owned().unwrap().verify_signature().unwrap();
+
+        // issuer key hash in request list
+        let mut bad = req_v8.clone();
+        bad[82] ^= 0x01;
+        let req = OcspRequest::parse(&bad[..]).unwrap();
+        match req.verify_signature() {
+            Err(OcspError::OcspBadSignature) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+    }
+
+    // signing and verifying request
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn ocsprequest_sign() {
+        use crate::common::name::DistinguishedName;
+        use crate::sign::{test::ED25519_KEY_PKCS8, Ed25519Signer};
+        use crate::verify::test::{key, CA_CERT, ED25519_SPKI};
+
+        let ocsp_req_hex = "3081B53081B230818A30433041300906\
+    052B0E03021A05000414694D18A9BE42\
+    F7802614D4844F23601478B788200414\
+    397BE002A2F571FD80DCEB52A17A7F8B\
+    632BE755020841300983331F9D4F3043\
+    3041300906052B0E03021A0500041469\
+    4D18A9BE42F7802614D4844F23601478\
+    B788200414397BE002A2F571FD80DCEB\
+    52A17A7F8B632BE75502086378E51D44\
+    8FF46DA2233021301F06092B06010505\
+    07300102041204105E7A74E51C861A3F\
+    79454658BB090244";
+        let req_v8 = hex::decode(ocsp_req_hex).unwrap();
+        let req = OcspRequest::parse(&req_v8[..]).unwrap();
+        assert_eq!(None, req.tbs_raw());
+        match req.verify_signature() {
+            Err(e @ OcspError::OcspRequestNotSigned) => {
+                assert!(matches!(
+                    e.suggested_response_status(),
+                    OcspRespStatus::SigRequired
+                ))
+            }
+            r => panic!("unexpected {:?}", r),
+        }
+
+        let signer = Ed25519Signer::from_pem(ED25519_KEY_PKCS8).unwrap();
+        let dn: DistinguishedName = "CN=Requestor,O=Example".parse().unwrap();
+        let req = req.sign(&signer, &dn, None).unwrap();
+        assert_eq!(
+            Some(&dn),
+            req.tbs_request().requestor_dn().unwrap().as_ref()
+        );
+        let ed = key(ED25519_SPKI);
+        req.verify_signature_with(&ed).unwrap();
+        match req.verify_signature() {
+            Err(e @ OcspError::OcspSignerCertNotFound) => {
+                assert!(matches!(
+                    e.suggested_response_status(),
+                    OcspRespStatus::Unauthorized
+                ))
+            }
+            r => panic!("unexpected {:?}", r),
+        }
+
+        let v = req.to_der().unwrap();
+        let parsed = OcspRequest::parse_with_mode(&v[..], DecodeMode::Strict).unwrap();
+        assert_eq!(req.tbs_raw(), parsed.tbs_raw());
+        assert_eq!(v, parsed.to_der().unwrap());
+        parsed.verify_signature_with(&ed).unwrap();
+
+        // signed with Ed25519, first cert holds P-256 key
+        let ca = pem_rfc7468::decode_vec(CA_CERT.as_bytes()).unwrap().1;
+        let mut req = req.sign(&signer, &dn, Some(vec![ca])).unwrap();
+        match req.verify_signature() {
+            Err(OcspError::OcspSignatureKeyMismatch(_)) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+
+        // tbs request changed after signing
+        req.tbs_request_mut().request_list.pop();
+        assert_eq!(None, req.tbs_raw());
+        match req.verify_signature_with(&ed) {
+            Err(OcspError::OcspBadSignature) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+    }
+
+    // non DER tbs request of signed request is verified and encoded as received
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn signed_ocsprequest_non_der() {
+        use crate::common::asn1::ASN1_EXPLICIT_0;
+        use crate::sign::{test::ED25519_KEY_PKCS8, Ed25519Signer, Signer};
+        use crate::verify::test::{key, ED25519_SPKI};
+
+        let req_v8 = hex::decode(
+            "306e306c304530433041300906052b0e03021a05000414694d18a9be42f78026\
+            14d4844f23601478b788200414397be002a2f571fd80dceb52a17a7f8b632be7\
+            5502086378e51d448ff46da2233021301f06092b060105050730010204120410\
+            1cfc8fa3f5e15ed760707bc46670559b",
+        )
+        .unwrap();
+        let tbs = OcspRequest::parse(&req_v8[..])
+            .unwrap()
+            .into_tbs_request()
+            .to_der()
+            .unwrap();
+        // DEFAULT v1 written explicitly, re-encoding omits it
+        let data = DerObject::decode(&tbs).unwrap();
+        let tbs = DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                w.write_raw(&[ASN1_EXPLICIT_0, 0x03, 0x02, 0x01, 0x00]);
+                w.write_raw(data.value());
+                Ok(())
+            })
+        })
+        .unwrap();
+        let signer = Ed25519Signer::from_pem(ED25519_KEY_PKCS8).unwrap();
+        let sig = Signature {
+            signing_algo: signer.algorithm(),
+            signature: signer.sign(&tbs).unwrap(),
+            certs: None,
+        };
+        let v = DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                w.write_raw(&tbs);
+                w.write_tagged(ASN1_EXPLICIT_0, |w| sig.write_der(w))
+            })
+        })
+        .unwrap();
+
+        let mut req = OcspRequest::parse(&v[..]).unwrap();
+        assert_eq!(Some(&tbs[..]), req.tbs_raw());
+        let ed = key(ED25519_SPKI);
+        req.verify_signature_with(&ed).unwrap();
+        assert_eq!(v, req.to_der().unwrap());
+        assert_eq!(v.len(), req.encoded_len().unwrap());
+
+        // touching tbs request encodes it again
+        req.tbs_request_mut();
+        assert_ne!(v, req.to_der().unwrap());
+        match req.verify_signature_with(&ed) {
+            Err(OcspError::OcspBadSignature) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+    }
+
+    // decode errors carry field path and offset
+    #[test]
+    fn parse_error_path() {
+        let good = CertId::new(
+            Oid::new_from_dot(crate::oid::ALGO_SHA1_DOT).unwrap(),
+            &[0; 20],
+            &[0; 20],
+            &[0x01],
+        );
+        // empty serial number INTEGER in second request
+        let req_v8 = DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                w.write_sequence(|w| {
+                    w.write_sequence(|w| {
+                        w.write_sequence(|w| good.write_der(w))?;
+                        w.write_sequence(|w| {
+                            w.write_sequence(|w| {
+                                good.hash_algo.write_der(w)?;
+                                w.write_octet(&[0; 20])?;
+                                w.write_octet(&[0; 20])?;
+                                w.write_tlv(ASN1_INTEGER, &[])
+                            })
+                        })
+                    })
+                })
+            })
+        })
+        .unwrap();
+
+        let e = OcspRequest::parse(&req_v8[..]).unwrap_err();
+        assert_eq!(
+            Some("tbsRequest.requestList[1].reqCert.serialNumber"),
+            e.path()
+        );
+        assert_eq!(Some(req_v8.len() - 2), e.offset());
+        assert!(matches!(
+            e.root(),
+            OcspError::Asn1MismatchError("SerialNumber")
+        ));
+        assert!(e.to_string().ends_with(&format!(
+            "at offset {} in tbsRequest.requestList[1].reqCert.serialNumber",
+            req_v8.len() - 2
+        )));
+        assert!(matches!(
+            e.suggested_response_status(),
+            OcspRespStatus::MalformedReq
+        ));
+
+        // borrowed decoding rejects the serial when checking cid tags
+        let e = OcspRequestRef::parse(&req_v8[..]).unwrap_err();
+        assert_eq!(Some("tbsRequest.requestList[1].reqCert"), e.path());
+        assert_eq!(Some(req_v8.len() - 59), e.offset());
+
+        assert!(matches!(
+            OcspError::GenRevokeInfoNotFound.suggested_response_status(),
+            OcspRespStatus::InternalError
+        ));
+        assert!(matches!(
+            OcspError::Asn1Truncated.suggested_response_status(),
+            OcspRespStatus::MalformedReq
+        ));
+    }
+
+    // strict mode rejects BER
+    #[test]
+    fn parse_req_strict() {
+        // explicit FALSE critical in nonce extension
+        let req_hex = "30673065303e303c303a300906052b0e03021a\
+    050004140000000000000000000000000000000000000000041400\
+    00000000000000000000000000000000000000020101a223302130\
+    1f06092b0601050507300102010100040f040d0000000000000000\
+    0000000000";
+        let req_v8 = hex::decode(req_hex).unwrap();
+        let _ = OcspRequest::parse(&req_v8[..]).unwrap();
+        let e = OcspRequest::parse_with_mode(&req_v8[..], DecodeMode::Strict).unwrap_err();
+        match e.root() {
+            OcspError::Asn1ExplicitDefault("critical") => {}
+            r => panic!("unexpected {:?}", r),
+        }
+        assert_eq!(Some("tbsRequest.requestExtensions[0]"), e.path());
+        assert_eq!(Some(72), e.offset());
+
+        // non canonical TRUE
+        let mut v = req_v8.clone();
+        let pos = req_v8
+            .windows(3)
+            .position(|w| w == [0x01, 0x01, 0x00])
+            .unwrap();
+        v[pos + 2] = 0x01;
+        match OcspRequest::parse_with_mode(&v[..], DecodeMode::Strict) {
+            Err(OcspError::Asn1NonCanonicalBoolean(0x01)) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+
+        // long form length for a short request
+        let mut v = vec![0x30, 0x81, 0x67];
+        v.extend(&req_v8[2..]);
+        match OcspRequest::parse_with_mode(&v[..], DecodeMode::Strict) {
+            Err(OcspError::Asn1NonMinimalLength) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+
+        // indefinite length
+        let mut v = vec![0x30, 0x80];
+        v.extend(&req_v8[2..]);
+        v.extend([0x00, 0x00]);
+        match OcspRequest::parse_with_mode(&v[..], DecodeMode::Strict) {
+            Err(OcspError::Asn1IndefiniteLength) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+
+        // items after optionalSignature
+        let tbs = OcspRequest::parse(&req_v8[..])
+            .unwrap()
+            .tbs_request()
+            .to_der()
+            .unwrap();
+        let v = DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                w.write_raw(&tbs);
+                w.write_raw(&[0xa0, 0x00, 0xa7, 0x00]);
+                Ok(())
+            })
+        })
+        .unwrap();
+        for mode in &[DecodeMode::Lenient, DecodeMode::Strict] {
+            match OcspRequest::parse_with_mode(&v[..], *mode) {
+                Err(OcspError::Asn1LengthError("OCSPRequest")) => {}
+                r => panic!("unexpected {:?}", r),
+            }
+        }
+        assert!(matches!(
+            OcspRequestRef::parse(&v[..]),
+            Err(OcspError::Asn1LengthError("OCSPRequest"))
+        ));
+    }
+
+    // unknown version returns error
+    #[test]
+    fn parse_tbs_unknown_version() {
+        let tbs_hex = "304ca003020101304530433041300906\
+    052b0e03021a05000414694d18a9be42\
+    f7802614d4844f23601478b788200414\
+    397be002a2f571fd80dceb52a17a7f8b\
+    632be75502086378e51d448ff46d";
+        let tbs_v8 = hex::decode(tbs_hex).unwrap();
+        assert!(TBSRequest::parse(&tbs_v8[..]).is_err());
+    }
+
     // get one request with no extension on either request
     #[test]
     fn parse_onereq_no_ext() {
@@ -527,6 +1560,12 @@ mod test {
         //let s = oid_v8.try_into().unwrap();
         //let d = s.get(1).unwrap();
         //println!("{:?}", d.header());
+
+        // strict mode does not
+        match asn1_validate_der(&oid_v8[..]) {
+            Err(OcspError::Asn1TrailingData(3)) => {}
+            r => panic!("unexpected {:?}", r),
+        }
     }
 
     // get oid Bytes from raw hex
@@ -1,24 +1,32 @@
 //! OCSP response  
-use tracing::{error, trace, warn};
+use std::convert::TryFrom;
+use std::time::SystemTime;
+
+use asn1_der::DerObject;
+use tracing::{debug, error, trace};
 
 use crate::common::asn1::Bytes;
+use crate::common::der::{DerDecode, DerEncode, DerWriter};
 use crate::common::{
     asn1::{
-        asn1_encode_length, CertId, GeneralizedTime, Oid, ASN1_ENUMERATED, ASN1_EXPLICIT_0,
-        ASN1_EXPLICIT_1,
+        asn1_decode_children, asn1_field, asn1_offset, asn1_validate_der, AlgorithmIdentifier,
+        CertId, DecodeMode, GeneralizedTime, Oid, SequenceRef, TryIntoSequence, ASN1_BIT_STRING,
+        ASN1_ENUMERATED, ASN1_EXPLICIT_0, ASN1_EXPLICIT_1,
     },
-    ocsp::OcspExtI,
+    name::DistinguishedName,
+    ocsp::{OcspExtI, Version},
 };
-use crate::{common::asn1::asn1_encode_bit_string, oid::OCSP_OID_DOT_LIST};
 use crate::{
-    common::asn1::{ASN1_EXPLICIT_2, ASN1_OCTET, ASN1_SEQUENCE},
+    common::asn1::{ASN1_EXPLICIT_2, ASN1_OCTET},
     err::{OcspError, Result},
-    oid::OCSP_RESPONSE_BASIC_ID,
+    oid::{OCSP_RESPONSE_BASIC_HEX, OCSP_RESPONSE_BASIC_ID},
+    sign::Signer,
+    verify::PublicKey,
 };
 
 /// possible revocation reasons, See RFC 5280
 #[repr(u8)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum CrlReason {
     /// RFC defined revocation reason
     OcspRevokeUnspecified = 0u8,
@@ -42,8 +50,28 @@ pub enum CrlReason {
     OcspRevokeAaCompromise = 10u8,
 }
 
+impl TryFrom<u8> for CrlReason {
+    type Error = OcspError;
+
+    fn try_from(reason: u8) -> Result<Self> {
+        match reason {
+            0 => Ok(CrlReason::OcspRevokeUnspecified),
+            1 => Ok(CrlReason::OcspRevokeKeyCompromise),
+            2 => Ok(CrlReason::OcspRevokeCaCompromise),
+            3 => Ok(CrlReason::OcspRevokeAffChanged),
+            4 => Ok(CrlReason::OcspRevokeSuperseded),
+            5 => Ok(CrlReason::OcspRevokeCessOperation),
+            6 => Ok(CrlReason::OcspRevokeCertHold),
+            8 => Ok(CrlReason::OcspRevokeRemoveFromCrl),
+            9 => Ok(CrlReason::OcspRevokePrivWithdrawn),
+            10 => Ok(CrlReason::OcspRevokeAaCompromise),
+            _ => Err(OcspError::OcspUndefinedCrlReason(reason)),
+        }
+    }
+}
+
 /// RFC 6960 Revokeinfo
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RevokedInfo {
     /// revocation time
     pub revocation_time: GeneralizedTime,
@@ -60,32 +88,90 @@ impl RevokedInfo {
         }
     }
 
+    /// get revoke info from raw bytes  
+    /// - with implicit 1 tagging from cert status
+    pub fn parse(revoke: &[u8]) -> Result<Self> {
+        trace!("Parsing revokeinfo {}", hex::encode(revoke));
+        let tagging = DerObject::decode(revoke).map_err(OcspError::Asn1DecodingError)?;
+        if tagging.tag() != ASN1_EXPLICIT_1 {
+            error!(
+                "Provided revokeinfo tag is {}, expecting 0xa1",
+                tagging.tag()
+            );
+            return Err(OcspError::Asn1MismatchError("RevokedInfo"));
+        }
+
+        let items = asn1_decode_children(tagging.value())?;
+        if items.is_empty() || items.len() > 2 {
+            error!(
+                "Provided revokeinfo contains {} items, expecting 1 or 2",
+                items.len()
+            );
+            return Err(OcspError::Asn1LengthError("RevokedInfo"));
+        }
+
+        let time = GeneralizedTime::parse(items[0].raw()).map_err(asn1_field(
+            revoke,
+            items[0].raw(),
+            "revocationTime",
+        ))?;
+        let mut reason = None;
+        if let Some(re) = items.get(1) {
+            let field = asn1_field(revoke, re.raw(), "revocationReason");
+            if re.tag() != ASN1_EXPLICIT_0 {
+                error!("Provided revoke reason tag is {}, expecting 0xa0", re.tag());
+                return Err(field(OcspError::Asn1MismatchError("RevokedInfo reason")));
+            }
+            let re = DerObject::decode(re.value())
+                .map_err(OcspError::Asn1DecodingError)
+                .map_err(field)?;
+            if re.tag() != ASN1_ENUMERATED || re.value().len() != 1 {
+                return Err(field(OcspError::Asn1MismatchError("RevokedInfo reason")));
+            }
+            reason = Some(CrlReason::try_from(re.value()[0]).map_err(field)?);
+        }
+
+        trace!("Revokeinfo successfully decoded");
+        Ok(RevokedInfo {
+            revocation_time: time,
+            revocation_reason: reason,
+        })
+    }
+
     /// encode to ASN.1 DER
     pub fn to_der(&self) -> Result<Bytes> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for RevokedInfo {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
         trace!(
             "Encoding revokeinfo with reason {:?}",
             self.revocation_reason
         );
-        let mut time = self.revocation_time.to_der_utc()?;
-        let mut reason = vec![];
-        if let Some(re) = self.revocation_reason {
-            reason = vec![ASN1_EXPLICIT_0, 0x03, ASN1_ENUMERATED, 0x01, re as u8];
-        }
-        time.extend(reason);
-        let len = asn1_encode_length(time.len())?;
-        let mut tag = vec![ASN1_EXPLICIT_1];
-        tag.extend(len);
-        tag.extend(time);
+        w.write_tagged(ASN1_EXPLICIT_1, |w| {
+            self.revocation_time.write_der(w)?;
+            if let Some(re) = self.revocation_reason {
+                w.write_raw(&[ASN1_EXPLICIT_0, 0x03, ASN1_ENUMERATED, 0x01, re as u8]);
+            }
+            Ok(())
+        })?;
 
         trace!("Revokeinfo successfully encoded");
-        trace!("Revokeinfo {}", hex::encode(&tag));
-        Ok(tag)
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for RevokedInfo {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        RevokedInfo::parse(raw)
     }
 }
 
 /// Certificate status enum, value is defined in RFC 6960
 #[repr(u8)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum CertStatusCode {
     /// cert is valid
     Good = 0x80,
@@ -97,51 +183,108 @@ pub enum CertStatusCode {
     Unknown = 0x82,
 }
 
-/// RFC 6960 cert status
-#[derive(Debug, Clone)]
-pub struct CertStatus {
-    code: CertStatusCode,
-    revoke_info: Option<RevokedInfo>,
+impl From<&CertStatus> for CertStatusCode {
+    fn from(status: &CertStatus) -> Self {
+        status.code()
+    }
+}
+
+/// RFC 6960 cert status  
+/// revoked status always carries its revoke info
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertStatus {
+    /// cert is valid
+    Good,
+    /// cert is revoked
+    Revoked(RevokedInfo),
+    /// responder doesn't know about the cert
+    Unknown,
 }
 
 impl CertStatus {
-    /// create new status
-    pub fn new(status: CertStatusCode, rev_info: Option<RevokedInfo>) -> Self {
-        match status {
-            CertStatusCode::Good | CertStatusCode::Unknown => {
-                if rev_info.is_some() {
-                    warn!("Cert status good or unknown with revoke info, ignored.");
-                }
-                CertStatus {
-                    code: status,
-                    revoke_info: None,
-                }
-            }
+    /// status code of the cert
+    pub fn code(&self) -> CertStatusCode {
+        match self {
+            CertStatus::Good => CertStatusCode::Good,
+            CertStatus::Revoked(_) => CertStatusCode::Revoked,
+            CertStatus::Unknown => CertStatusCode::Unknown,
+        }
+    }
 
-            CertStatusCode::Revoked => CertStatus {
-                code: status,
-                revoke_info: rev_info,
-            },
+    /// revoke info if cert is revoked
+    pub fn revoke_info(&self) -> Option<&RevokedInfo> {
+        match self {
+            CertStatus::Revoked(info) => Some(info),
+            _ => None,
         }
     }
 
+    /// get cert status from raw bytes
+    pub fn parse(status: &[u8]) -> Result<Self> {
+        trace!("Parsing cert status {}", hex::encode(status));
+        let obj = DerObject::decode(status).map_err(OcspError::Asn1DecodingError)?;
+
+        let tag = obj.tag();
+        let r = if tag == CertStatusCode::Good as u8 && obj.value().is_empty() {
+            CertStatus::Good
+        } else if tag == CertStatusCode::Unknown as u8 && obj.value().is_empty() {
+            CertStatus::Unknown
+        } else if tag == CertStatusCode::Revoked as u8 {
+            CertStatus::Revoked(RevokedInfo::parse(obj.raw())?)
+        } else {
+            error!(
+                "Provided cert status tag is {}, expecting 0x80, 0xa1 or 0x82",
+                tag
+            );
+            return Err(OcspError::Asn1MismatchError("CertStatus"));
+        };
+
+        trace!("Cert status {:?} successfully decoded", r.code());
+        Ok(r)
+    }
+
     /// encode to ASN.1 DER
     pub fn to_der(&self) -> Result<Bytes> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+/// status without revoke info  
+/// revoked code has no info to carry, returns [OcspError::GenRevokeInfoNotFound]
+impl TryFrom<CertStatusCode> for CertStatus {
+    type Error = OcspError;
+
+    fn try_from(code: CertStatusCode) -> Result<Self> {
+        match code {
+            CertStatusCode::Good => Ok(CertStatus::Good),
+            CertStatusCode::Unknown => Ok(CertStatus::Unknown),
+            CertStatusCode::Revoked => Err(OcspError::GenRevokeInfoNotFound),
+        }
+    }
+}
+
+impl From<RevokedInfo> for CertStatus {
+    fn from(info: RevokedInfo) -> Self {
+        CertStatus::Revoked(info)
+    }
+}
+
+impl DerEncode for CertStatus {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
         trace!("Encoding cert status {:?}", self);
-        match self.code {
-            CertStatusCode::Good => Ok(vec![CertStatusCode::Good as u8, 0x00]),
-            CertStatusCode::Unknown => Ok(vec![CertStatusCode::Unknown as u8, 0x00]),
-            CertStatusCode::Revoked => {
-                let v = match &self.revoke_info {
-                    Some(r) => {
-                        // revoke_info to_der contains status code
-                        r.to_der()?
-                    }
-                    None => return Err(OcspError::GenRevokeInfoNotFound),
-                };
-                Ok(v)
-            }
+        match self {
+            CertStatus::Good => w.write_raw(&[CertStatusCode::Good as u8, 0x00]),
+            CertStatus::Unknown => w.write_raw(&[CertStatusCode::Unknown as u8, 0x00]),
+            // revoke_info to_der contains status code
+            CertStatus::Revoked(r) => r.write_der(w)?,
         }
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for CertStatus {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        CertStatus::parse(raw)
     }
 }
 
@@ -161,70 +304,119 @@ pub struct OneResp {
 }
 
 impl OneResp {
-    /// encode list of resp to ASN.1 DER
-    pub fn list_to_der(list: &[OneResp]) -> Result<Bytes> {
-        trace!("Encoding {} OneResp", list.len());
+    /// get single response from raw bytes
+    pub fn parse(one: &[u8]) -> Result<Self> {
+        OneResp::parse_with_mode(one, DecodeMode::Lenient)
+    }
+
+    /// get single response from raw bytes, see [DecodeMode]
+    pub(crate) fn parse_with_mode(one: &[u8], mode: DecodeMode) -> Result<Self> {
+        trace!("Parsing OneResp {}", hex::encode(one));
+        let s = one.try_into()?;
 
-        let mut v = vec![];
-        for i in list {
-            v.extend(i.to_der()?);
+        let len = s.len();
+        if !(3..=5).contains(&len) {
+            error!("Provided OneResp contains {} items, expecting 3 to 5", len);
+            return Err(OcspError::Asn1LengthError("OneResp"));
         }
 
-        let len = asn1_encode_length(v.len())?;
-        let mut tlv = vec![ASN1_SEQUENCE];
-        tlv.extend(len);
-        tlv.extend(v);
+        let cid = s.get(0).map_err(OcspError::Asn1DecodingError)?.raw();
+        let cid = CertId::parse(cid).map_err(asn1_field(one, cid, "certID"))?;
+        let status = s.get(1).map_err(OcspError::Asn1DecodingError)?.raw();
+        let status = CertStatus::parse(status).map_err(asn1_field(one, status, "certStatus"))?;
+        let this = s.get(2).map_err(OcspError::Asn1DecodingError)?.raw();
+        let this = GeneralizedTime::parse(this).map_err(asn1_field(one, this, "thisUpdate"))?;
+
+        let mut next = None;
+        let mut ext = None;
+        for i in 3..len {
+            let item = s.get(i).map_err(OcspError::Asn1DecodingError)?;
+            match item.tag() {
+                ASN1_EXPLICIT_0 if next.is_none() && ext.is_none() => {
+                    trace!("Found OneResp nextUpdate");
+                    next = Some(GeneralizedTime::parse(item.value()).map_err(asn1_field(
+                        one,
+                        item.raw(),
+                        "nextUpdate",
+                    ))?);
+                }
+                ASN1_EXPLICIT_1 if ext.is_none() => {
+                    trace!("Found OneResp extensions");
+                    let list = item.value();
+                    ext = Some(OcspExtI::parse_with_mode(list, mode).map_err(asn1_field(
+                        one,
+                        list,
+                        "singleExtensions",
+                    ))?);
+                }
+                _ => {
+                    error!("Unexpected OneResp item {} with tag {:02x}", i, item.tag());
+                    let e = OcspError::Asn1MismatchError("OneResp");
+                    let offset = asn1_offset(one, item.raw());
+                    return Err(e.at(&format!("[{}]", i), offset));
+                }
+            }
+        }
 
-        trace!("OneResp list successfully encoded");
-        Ok(tlv)
+        trace!(
+            "OneResp with sn {} successfully decoded",
+            cid.serial_num.to_string()
+        );
+        Ok(OneResp {
+            cid,
+            cert_status: status,
+            this_update: this,
+            next_update: next,
+            one_resp_ext: ext,
+        })
     }
 
     /// encode to ASN.1 DER
     pub fn to_der(&self) -> Result<Bytes> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for OneResp {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
         trace!(
             "Encoding OneResp sn {} with status {:?}",
-            hex::encode(&self.cid.serial_num),
+            self.cid.serial_num.to_string(),
             self.cert_status
         );
         trace!("OneResp: {:?}", self);
-        let mut certid = self.cid.to_der()?;
-        let status = self.cert_status.to_der()?;
-        let this = self.this_update.to_der_utc()?;
-
-        certid.extend(status);
-        certid.extend(this);
-
-        if let Some(t) = self.next_update {
-            trace!("Found OneResp nextUpdate");
-            let next = t.to_der_utc()?;
-            let len = asn1_encode_length(next.len())?;
-            let mut tagging = vec![ASN1_EXPLICIT_0];
-            tagging.extend(len);
-            tagging.extend(next);
-            certid.extend(tagging);
-        }
-
-        if let Some(e) = self.one_resp_ext.clone() {
-            trace!("Found {} OneResp extensions", e.len());
-            // list_to_der comes with explicit tagging
-            let list = OcspExtI::list_to_der(&e, ASN1_EXPLICIT_1)?;
-            certid.extend(list);
-        }
+        w.write_sequence(|w| {
+            self.cid.write_der(w)?;
+            self.cert_status.write_der(w)?;
+            self.this_update.write_der(w)?;
+
+            if let Some(t) = self.next_update {
+                trace!("Found OneResp nextUpdate");
+                w.write_tagged(ASN1_EXPLICIT_0, |w| t.write_der(w))?;
+            }
 
-        let len = asn1_encode_length(certid.len())?;
-        let mut r = vec![ASN1_SEQUENCE];
-        r.extend(len);
-        r.extend(certid);
+            if let Some(e) = &self.one_resp_ext {
+                trace!("Found {} OneResp extensions", e.len());
+                // list comes with explicit tagging
+                w.write_tagged(ASN1_EXPLICIT_1, |w| e.write_der(w))?;
+            }
+            Ok(())
+        })?;
 
         trace!("OneResp successfully encoded");
-        trace!("OneResp {}", hex::encode(&r));
-        Ok(r)
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for OneResp {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        OneResp::parse(raw)
     }
 }
 
 /// RFC 6960 defined responder types
 #[allow(non_camel_case_types)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum ResponderType {
     /// responder by name
     BY_NAME = 0x00,
@@ -233,7 +425,7 @@ pub enum ResponderType {
 }
 
 /// Munzo responder id
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ResponderId {
     /// id by name or key hash
     pub id_by: ResponderType,
@@ -250,8 +442,64 @@ impl ResponderId {
         }
     }
 
-    /// encode to ASN.1, by name unimplemented!
-    // example by name
+    /// create new responder id by name
+    pub fn new_by_name(name: &DistinguishedName) -> Result<Self> {
+        Ok(ResponderId {
+            id_by: ResponderType::BY_NAME,
+            id: name.to_der()?,
+        })
+    }
+
+    /// get responder name  
+    /// return error if responder id is by key hash
+    pub fn name(&self) -> Result<DistinguishedName> {
+        match self.id_by {
+            ResponderType::BY_NAME => DistinguishedName::parse(&self.id),
+            ResponderType::BY_KEY_HASH => Err(OcspError::Asn1MismatchError("ResponderId name")),
+        }
+    }
+
+    /// get responder id from raw bytes  
+    /// - with explicit 1 or 2 tagging
+    pub fn parse(rid: &[u8]) -> Result<Self> {
+        trace!("Parsing responder id {}", hex::encode(rid));
+        let tagging = DerObject::decode(rid).map_err(OcspError::Asn1DecodingError)?;
+
+        let r = match tagging.tag() {
+            ASN1_EXPLICIT_1 => {
+                // keep Name as it is
+                let name =
+                    DerObject::decode(tagging.value()).map_err(OcspError::Asn1DecodingError)?;
+                let _ = DistinguishedName::parse(name.raw())?;
+                ResponderId {
+                    id_by: ResponderType::BY_NAME,
+                    id: name.raw().to_vec(),
+                }
+            }
+            ASN1_EXPLICIT_2 => {
+                let key =
+                    DerObject::decode(tagging.value()).map_err(OcspError::Asn1DecodingError)?;
+                if key.tag() != ASN1_OCTET {
+                    return Err(OcspError::Asn1MismatchError("ResponderId key hash"));
+                }
+                ResponderId::new_key_hash(key.value())
+            }
+            _ => {
+                error!(
+                    "Provided responder id tag is {}, expecting 0xa1 or 0xa2",
+                    tagging.tag()
+                );
+                return Err(OcspError::Asn1MismatchError("ResponderId"));
+            }
+        };
+
+        trace!("Responder id by {:?} successfully decoded", r.id_by);
+        Ok(r)
+    }
+
+    /// encode to ASN.1  
+    /// in case of by name, id holds the DER encoded Name
+    // example by name, CN=OCSP,O=Internet Widgits Pty Ltd,ST=Some-State,C=AU
     // a1 56
     //  30 54
     //      31 0b 30 09 06 03 55 04 06 13 02 41 55
@@ -259,38 +507,38 @@ impl ResponderId {
     //      31 21 30 1f 06 03 55 04 0a 0c 18 49 6e 74 65 72 6e 65 74 20 57 69 64 67 69 74 73 20 50 74 79 20 4c 74 64
     //      31 0d 30 0b 06 03 55 04 03 0c 04 4f 43 53 50
     pub fn to_der(&self) -> Result<Bytes> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
+
+impl DerEncode for ResponderId {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
         trace!("Encoding Responder Id by {:?}", self.id_by);
         trace!("Responder Id: {:?}", self);
 
-        let mut v = vec![];
         match self.id_by {
-            ResponderType::BY_NAME => {
-                // FIXME:
-                unimplemented!()
-            }
+            ResponderType::BY_NAME => w.write_tlv(ASN1_EXPLICIT_1, &self.id)?,
             ResponderType::BY_KEY_HASH => {
-                let len = asn1_encode_length(self.id.len())?;
-                let mut octet = vec![ASN1_OCTET];
-                octet.extend(len);
-                octet.extend(self.id.clone());
-                let len = asn1_encode_length(octet.len())?;
-                v.push(ASN1_EXPLICIT_2);
-                v.extend(len);
-                v.extend(octet);
+                w.write_tagged(ASN1_EXPLICIT_2, |w| w.write_octet(&self.id))?
             }
         }
 
         trace!("Responder id successfully encoded");
-        trace!("Responder id {}", hex::encode(&v));
-        Ok(v)
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for ResponderId {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        ResponderId::parse(raw)
     }
 }
 
 /// RFC 6960 Response Data
 #[derive(Debug)]
 pub struct ResponseData {
-    // REVIEW:
-    // version
+    /// explicit tag 0, DEFAULT v1
+    pub version: Version,
     /// responder id  
     /// in case of KeyHash ::= OCTET STRING  
     /// SHA-1 hash of responder's public key (excluding the tag and length fields)
@@ -312,6 +560,7 @@ impl ResponseData {
         ext: Option<Vec<OcspExtI>>,
     ) -> Self {
         ResponseData {
+            version: Version::default(),
             responder_id: id,
             produced_at: produce,
             responses: list,
@@ -319,36 +568,145 @@ impl ResponseData {
         }
     }
 
+    /// get response data from raw bytes
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        ResponseData::parse_with_mode(data, DecodeMode::Lenient)
+    }
+
+    /// get response data from raw bytes, see [DecodeMode]
+    pub(crate) fn parse_with_mode(data: &[u8], mode: DecodeMode) -> Result<Self> {
+        trace!("Parsing response data {}", hex::encode(data));
+        let s = data.try_into()?;
+
+        let len = s.len();
+        if !(3..=5).contains(&len) {
+            error!(
+                "Provided response data contains {} items, expecting 3 to 5",
+                len
+            );
+            return Err(OcspError::Asn1LengthError("ResponseData"));
+        }
+
+        let mut version = Version::default();
+        let mut i = 0;
+        let first = s.get(0).map_err(OcspError::Asn1DecodingError)?;
+        if first.tag() == ASN1_EXPLICIT_0 {
+            trace!("Found response data version");
+            let field = asn1_field(data, first.raw(), "version");
+            version = Version::parse(first.value()).map_err(field)?;
+            if mode.is_strict() && version == Version::default() {
+                error!("Found explicit v1 in response data");
+                return Err(field(OcspError::Asn1ExplicitDefault("version")));
+            }
+            i += 1;
+        }
+        if !(3..=4).contains(&(len - i)) {
+            error!(
+                "Provided response data contains {} items after version, expecting 3 or 4",
+                len - i
+            );
+            return Err(OcspError::Asn1LengthError("ResponseData"));
+        }
+
+        let rid = s.get(i).map_err(OcspError::Asn1DecodingError)?.raw();
+        let rid = ResponderId::parse(rid).map_err(asn1_field(data, rid, "responderID"))?;
+        let produce = s.get(i + 1).map_err(OcspError::Asn1DecodingError)?.raw();
+        let produce =
+            GeneralizedTime::parse(produce).map_err(asn1_field(data, produce, "producedAt"))?;
+
+        let list = s.get(i + 2).map_err(OcspError::Asn1DecodingError)?;
+        let list = list
+            .try_into()
+            .map_err(asn1_field(data, list.raw(), "responses"))?;
+        let mut resp = vec![];
+        for j in 0..list.len() {
+            let one = list.get(j).map_err(OcspError::Asn1DecodingError)?;
+            let offset = asn1_offset(data, one.raw());
+            resp.push(
+                OneResp::parse_with_mode(one.raw(), mode)
+                    .map_err(|e| e.at(&format!("responses[{}]", j), offset))?,
+            );
+        }
+        trace!("Found {} OneResp", resp.len());
+
+        let mut ext = None;
+        if len - i == 4 {
+            let e = s.get(i + 3).map_err(OcspError::Asn1DecodingError)?;
+            let field = asn1_field(data, e.raw(), "responseExtensions");
+            if e.tag() != ASN1_EXPLICIT_1 {
+                return Err(field(OcspError::Asn1MismatchError(
+                    "ResponseData extension",
+                )));
+            }
+            let list = e.value();
+            ext = Some(OcspExtI::parse_with_mode(list, mode).map_err(asn1_field(
+                data,
+                list,
+                "responseExtensions",
+            ))?);
+        }
+
+        trace!("Response data successfully decoded");
+        Ok(ResponseData {
+            version,
+            responder_id: rid,
+            produced_at: produce,
+            responses: resp,
+            resp_ext: ext,
+        })
+    }
+
     /// encode to ASN.1 DER
     pub fn to_der(&self) -> Result<Bytes> {
-        trace!("Encoding response data: {:?}", self);
+        DerWriter::encode(|w| self.write_der(w))
+    }
 
-        let mut v = vec![];
-        v.extend(self.responder_id.to_der()?);
-        v.extend(self.produced_at.to_der_utc()?);
-        v.extend(OneResp::list_to_der(&self.responses)?);
-        if let Some(l) = &self.resp_ext {
-            v.extend(OcspExtI::list_to_der(l, ASN1_EXPLICIT_1)?);
-        }
+    /// sign DER of response data, return basic response without certs  
+    /// signature algorithm is reported by signer
+    pub fn sign<S: Signer + ?Sized>(self, signer: &S) -> Result<BasicResponse> {
+        let tbs = self.to_der()?;
+        let sign = signer.sign(&tbs)?;
+        debug!("Response data of {} bytes signed", tbs.len());
+        let mut r = BasicResponse::new(self, signer.algorithm(), sign, None);
+        r.tbs_raw = Some(tbs);
+        Ok(r)
+    }
+}
 
-        let len = asn1_encode_length(v.len())?;
-        let mut tlv = vec![ASN1_SEQUENCE];
-        tlv.extend(len);
-        tlv.extend(v);
+impl DerEncode for ResponseData {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
+        trace!("Encoding response data: {:?}", self);
+
+        w.write_sequence(|w| {
+            // DEFAULT version is omitted
+            self.version.write_der(w)?;
+            self.responder_id.write_der(w)?;
+            self.produced_at.write_der(w)?;
+            self.responses.write_der(w)?;
+            if let Some(l) = &self.resp_ext {
+                w.write_tagged(ASN1_EXPLICIT_1, |w| l.write_der(w))?;
+            }
+            Ok(())
+        })?;
 
         trace!("Response data successfully encoded");
-        trace!("Response data {}", hex::encode(&tlv));
-        Ok(tlv)
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for ResponseData {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        ResponseData::parse(raw)
     }
 }
 
 /// RFC 6960 Basic Response
 #[derive(Debug)]
 pub struct BasicResponse {
-    /// response data
-    pub tbs_resp_data: ResponseData,
-    /// signature algorithm oid
-    pub signature_algo: Oid,
+    /// response data, see [BasicResponse::tbs_resp_data]
+    tbs_resp_data: ResponseData,
+    /// signature algorithm
+    pub signature_algo: AlgorithmIdentifier,
     ///  The value for signature SHALL be computed on the hash of the DER encoding of ResponseData
     pub signature: Bytes,
     /// The responder MAY include certificates in  
@@ -356,53 +714,176 @@ pub struct BasicResponse {
     /// the responder's signature.  
     /// If no certificates are included, then certs SHOULD be absent
     pub certs: Option<Vec<Bytes>>,
+    /// DER of response data as received or signed, signature is verified over it  
+    /// None if response data is not from parsing or signing, or changed since
+    tbs_raw: Option<Bytes>,
 }
 
 impl BasicResponse {
-    /// return new response data
-    pub fn new(data: ResponseData, algo: Oid, sign: Bytes, certs: Option<Vec<Bytes>>) -> Self {
+    /// return new response data  
+    /// signature algorithm can be an [Oid], parameters are chosen per algorithm
+    pub fn new(
+        data: ResponseData,
+        algo: impl Into<AlgorithmIdentifier>,
+        sign: Bytes,
+        certs: Option<Vec<Bytes>>,
+    ) -> Self {
         BasicResponse {
             tbs_resp_data: data,
-            signature_algo: algo,
+            signature_algo: algo.into(),
             signature: sign,
             certs,
+            tbs_raw: None,
         }
     }
 
-    /// encode to ASN.1 DER
-    pub fn to_der(&self) -> Result<Bytes> {
-        trace!("Encoding basic response: {:?}", self);
+    /// get basic response from raw bytes
+    pub fn parse(basic: &[u8]) -> Result<Self> {
+        BasicResponse::parse_with_mode(basic, DecodeMode::Lenient)
+    }
 
-        let mut v = vec![];
-
-        v.extend(self.tbs_resp_data.to_der()?);
-        v.extend(self.signature_algo.to_der_with_null()?);
-        let mut pad = vec![0x00u8];
-        pad.extend(&self.signature);
-        v.extend(asn1_encode_bit_string(&pad)?);
-        if let Some(certs) = &self.certs {
-            let d =
-                yasna::construct_der_seq(|w| {
-                    w.next().write_sequence_of(|w| 
-                        for c in certs {
-                            w.next().write_der(c)
-                        })
-                    });
-            let tag = yasna::Tag::context(0);
-            let pc = yasna::PCBit::Constructed;
-            let tv = yasna::models::TaggedDerValue::from_tag_pc_and_bytes(tag, pc, d);
-            let der = yasna::construct_der(|w| w.write_tagged_der(&tv));
-            v.extend(der);
+    /// get basic response from raw bytes, see [DecodeMode]
+    pub(crate) fn parse_with_mode(basic: &[u8], mode: DecodeMode) -> Result<Self> {
+        trace!("Parsing basic response {}", hex::encode(basic));
+        let s = basic.try_into()?;
+
+        let len = s.len();
+        if !(3..=4).contains(&len) {
+            error!(
+                "Provided basic response contains {} items, expecting 3 or 4",
+                len
+            );
+            return Err(OcspError::Asn1LengthError("BasicResponse"));
+        }
+
+        let tbs = s.get(0).map_err(OcspError::Asn1DecodingError)?.raw();
+        let data = ResponseData::parse_with_mode(tbs, mode).map_err(asn1_field(
+            basic,
+            tbs,
+            "tbsResponseData",
+        ))?;
+        let algo = s.get(1).map_err(OcspError::Asn1DecodingError)?.raw();
+        let algo = AlgorithmIdentifier::parse(algo).map_err(asn1_field(
+            basic,
+            algo,
+            "signatureAlgorithm",
+        ))?;
+
+        let sign = s.get(2).map_err(OcspError::Asn1DecodingError)?;
+        // leading byte is the number of unused bits
+        if sign.tag() != ASN1_BIT_STRING || sign.value().first() != Some(&0x00) {
+            error!("Provided signature is not a BIT STRING of whole bytes");
+            let e = OcspError::Asn1MismatchError("BasicResponse signature");
+            return Err(asn1_field(basic, sign.raw(), "signature")(e));
+        }
+        let sign = sign.value()[1..].to_vec();
+
+        let mut certs = None;
+        if len == 4 {
+            let tagging = s.get(3).map_err(OcspError::Asn1DecodingError)?;
+            let field = asn1_field(basic, tagging.raw(), "certs");
+            if tagging.tag() != ASN1_EXPLICIT_0 {
+                return Err(field(OcspError::Asn1MismatchError("BasicResponse certs")));
+            }
+            let list = tagging.value();
+            let v = SequenceRef::decode(list)
+                .map(|l| l.iter().map(|c| c.raw().to_vec()).collect::<Vec<_>>())
+                .map_err(field)?;
+            trace!("Found {} certs in basic response", v.len());
+            certs = Some(v);
         }
 
-        let len = asn1_encode_length(v.len())?;
-        let mut tlv = vec![ASN1_SEQUENCE];
-        tlv.extend(len);
-        tlv.extend(v);
+        trace!("Basic response successfully decoded");
+        let mut r = BasicResponse::new(data, algo, sign, certs);
+        r.tbs_raw = Some(tbs.to_vec());
+        Ok(r)
+    }
+
+    /// response data
+    pub fn tbs_resp_data(&self) -> &ResponseData {
+        &self.tbs_resp_data
+    }
+
+    /// mutable response data  
+    /// bytes as received or signed are dropped, response data is encoded again from now on
+    pub fn tbs_resp_data_mut(&mut self) -> &mut ResponseData {
+        self.tbs_raw = None;
+        &mut self.tbs_resp_data
+    }
+
+    /// replace response data, see [BasicResponse::tbs_resp_data_mut]
+    pub fn set_tbs_resp_data(&mut self, data: ResponseData) {
+        self.tbs_raw = None;
+        self.tbs_resp_data = data;
+    }
+
+    /// take response data, dropping signature
+    pub fn into_tbs_resp_data(self) -> ResponseData {
+        self.tbs_resp_data
+    }
+
+    /// DER of response data as received or signed, signature is verified over it  
+    /// None if response data is not from parsing or signing, or changed since
+    pub fn tbs_raw(&self) -> Option<&[u8]> {
+        self.tbs_raw.as_deref()
+    }
+
+    /// encode to ASN.1 DER  
+    /// response data as received or signed is written as is, so signature still matches
+    pub fn to_der(&self) -> Result<Bytes> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+
+    /// verify signature with responder public key, see [PublicKey::verify]  
+    /// received response data is verified as is, not re-encoded
+    pub fn verify(&self, key: &PublicKey) -> Result<()> {
+        let encoded;
+        let tbs = match &self.tbs_raw {
+            Some(raw) => raw,
+            None => {
+                encoded = self.tbs_resp_data.to_der()?;
+                &encoded
+            }
+        };
+        key.verify(&self.signature_algo, tbs, &self.signature)
+    }
+}
+
+impl DerEncode for BasicResponse {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
+        trace!("Encoding basic response: {:?}", self);
+
+        w.write_sequence(|w| {
+            match &self.tbs_raw {
+                Some(raw) => w.write_raw(raw),
+                None => self.tbs_resp_data.write_der(w)?,
+            }
+            self.signature_algo.write_der(w)?;
+            // no unused bits
+            w.write_tagged(ASN1_BIT_STRING, |w| {
+                w.write_raw(&[0x00]);
+                w.write_raw(&self.signature);
+                Ok(())
+            })?;
+            if let Some(certs) = &self.certs {
+                w.write_tagged(ASN1_EXPLICIT_0, |w| {
+                    w.write_sequence(|w| {
+                        certs.iter().for_each(|c| w.write_raw(c));
+                        Ok(())
+                    })
+                })?;
+            }
+            Ok(())
+        })?;
 
         trace!("Basic response successfully encoded");
-        trace!("Basic response {}", hex::encode(&tlv));
-        Ok(tlv)
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for BasicResponse {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        BasicResponse::parse(raw)
     }
 }
 
@@ -422,9 +903,8 @@ impl ResponseBytes {
     /// return new ResponseBytes
     /// currently only support basic response
     pub fn new_basic(oid: Oid, data: BasicResponse) -> Result<Self> {
-        if oid.index != OCSP_RESPONSE_BASIC_ID {
-            let dot_name = OCSP_OID_DOT_LIST[oid.index];
-            error!("Response type {} is not supported", dot_name);
+        if oid.index() != Some(OCSP_RESPONSE_BASIC_ID) {
+            error!("Response type {} is not supported", oid);
             return Err(OcspError::OcspUnsupportedResponseType);
         }
 
@@ -434,38 +914,87 @@ impl ResponseBytes {
         })
     }
 
-    /// encode to ASN.1 DER  
+    /// get response bytes from raw bytes  
     /// - with explicit 0 tagging  
     /// - with octet header for basic response
-    pub fn to_der(&self) -> Result<Bytes> {
-        trace!("Encoding response bytes {:?}", self);
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        ResponseBytes::parse_with_mode(bytes, DecodeMode::Lenient)
+    }
 
-        let mut v = vec![];
-        v.extend(self.response_type.to_der_raw()?);
+    /// get response bytes from raw bytes, see [ResponseBytes::parse] and [DecodeMode]
+    pub(crate) fn parse_with_mode(bytes: &[u8], mode: DecodeMode) -> Result<Self> {
+        trace!("Parsing response bytes {}", hex::encode(bytes));
+        let tagging = DerObject::decode(bytes).map_err(OcspError::Asn1DecodingError)?;
+        if tagging.tag() != ASN1_EXPLICIT_0 {
+            error!(
+                "Provided response bytes tag is {}, expecting 0xa0",
+                tagging.tag()
+            );
+            return Err(OcspError::Asn1MismatchError("ResponseBytes"));
+        }
 
-        // octet header for basic response
-        let basic = self.response_data.to_der()?;
-        let mut octet = vec![ASN1_OCTET];
-        let len = asn1_encode_length(basic.len())?;
-        octet.extend(len);
-        octet.extend(basic);
-        v.extend(octet);
+        let seq = tagging.value();
+        let s = seq.try_into()?;
+        if s.len() != 2 {
+            error!(
+                "Provided response bytes contains {} items, expecting 2",
+                s.len()
+            );
+            return Err(OcspError::Asn1LengthError("ResponseBytes"));
+        }
+
+        let oid = s.get(0).map_err(OcspError::Asn1DecodingError)?.raw();
+        let field = asn1_field(bytes, oid, "responseType");
+        let oid = Oid::parse_raw(oid).map_err(field)?;
+        if oid.index() != Some(OCSP_RESPONSE_BASIC_ID) {
+            error!("Response type {} is not supported", oid);
+            return Err(field(OcspError::OcspUnsupportedResponseType));
+        }
+
+        let octet = s.get(1).map_err(OcspError::Asn1DecodingError)?;
+        let field = asn1_field(bytes, octet.value(), "response");
+        if octet.tag() != ASN1_OCTET {
+            let e = OcspError::Asn1MismatchError("ResponseBytes basic response");
+            return Err(asn1_field(bytes, octet.raw(), "response")(e));
+        }
+        if mode.is_strict() {
+            asn1_validate_der(octet.value()).map_err(field)?;
+        }
+        let basic = BasicResponse::parse_with_mode(octet.value(), mode).map_err(field)?;
+
+        trace!("Response bytes successfully decoded");
+        ResponseBytes::new_basic(oid, basic)
+    }
+
+    /// encode to ASN.1 DER  
+    /// - with explicit 0 tagging  
+    /// - with octet header for basic response
+    pub fn to_der(&self) -> Result<Bytes> {
+        DerWriter::encode(|w| self.write_der(w))
+    }
+}
 
-        // response sequence header
-        let len = asn1_encode_length(v.len())?;
-        let mut tlv = vec![ASN1_SEQUENCE];
-        tlv.extend(len);
-        tlv.extend(v);
+impl DerEncode for ResponseBytes {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
+        trace!("Encoding response bytes {:?}", self);
 
         // response byte exp 0 tagging
-        let len = asn1_encode_length(tlv.len())?;
-        let mut tagging = vec![ASN1_EXPLICIT_0];
-        tagging.extend(len);
-        tagging.extend(tlv);
+        w.write_tagged(ASN1_EXPLICIT_0, |w| {
+            w.write_sequence(|w| {
+                self.response_type.write_der(w)?;
+                // octet header for basic response
+                w.write_tagged(ASN1_OCTET, |w| self.response_data.write_der(w))
+            })
+        })?;
 
         trace!("Response bytes successfully encoded");
-        trace!("Response bytes {}", hex::encode(&tagging));
-        Ok(tagging)
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for ResponseBytes {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        ResponseBytes::parse(raw)
     }
 }
 
@@ -487,6 +1016,22 @@ pub enum OcspRespStatus {
     Unauthorized = 6u8,
 }
 
+impl TryFrom<u8> for OcspRespStatus {
+    type Error = OcspError;
+
+    fn try_from(status: u8) -> Result<Self> {
+        match status {
+            0 => Ok(OcspRespStatus::Successful),
+            1 => Ok(OcspRespStatus::MalformedReq),
+            2 => Ok(OcspRespStatus::InternalError),
+            3 => Ok(OcspRespStatus::TryLater),
+            5 => Ok(OcspRespStatus::SigRequired),
+            6 => Ok(OcspRespStatus::Unauthorized),
+            _ => Err(OcspError::OcspRespUndefinedStatus(status)),
+        }
+    }
+}
+
 /// RFC6960 Ocsp Response
 #[derive(Debug)]
 pub struct OcspResponse {
@@ -520,25 +1065,296 @@ impl OcspResponse {
         }
     }
 
+    /// parsing an ocsp response from raw bytes  
+    /// data after the response is ignored, see [OcspResponse::parse_with_mode]
+    pub fn parse(ocsp_resp: &[u8]) -> Result<Self> {
+        OcspResponse::parse_with_mode(ocsp_resp, DecodeMode::Lenient)
+    }
+
+    /// parsing an ocsp response from raw bytes  
+    /// in strict mode, only DER is accepted
+    pub fn parse_with_mode(ocsp_resp: &[u8], mode: DecodeMode) -> Result<Self> {
+        debug!("Parsing ocsp response in {:?} mode", mode);
+        trace!("Raw ocsp response: {}", hex::encode(ocsp_resp));
+        if mode.is_strict() {
+            asn1_validate_der(ocsp_resp)?;
+        }
+        let s = ocsp_resp.try_into()?;
+
+        let status = s.get(0).map_err(OcspError::Asn1DecodingError)?;
+        let field = asn1_field(ocsp_resp, status.raw(), "responseStatus");
+        if status.tag() != ASN1_ENUMERATED || status.value().len() != 1 {
+            return Err(field(OcspError::Asn1MismatchError("OcspResponse status")));
+        }
+        let status = OcspRespStatus::try_from(status.value()[0]).map_err(field)?;
+
+        let r = match (status, s.len()) {
+            (OcspRespStatus::Successful, 2) => {
+                let bytes = s.get(1).map_err(OcspError::Asn1DecodingError)?;
+                let bytes = ResponseBytes::parse_with_mode(bytes.raw(), mode)
+                    .map_err(asn1_field(ocsp_resp, bytes.raw(), "responseBytes"))?;
+                OcspResponse::new_success(bytes)
+            }
+            (OcspRespStatus::Successful, _) => {
+                error!("Successful ocsp response without response bytes");
+                return Err(OcspError::Asn1LengthError("OcspResponse"));
+            }
+            (_, 1) => OcspResponse::new_non_success(status)?,
+            (_, _) => {
+                error!("Non-successful ocsp response with response bytes");
+                return Err(OcspError::Asn1LengthError("OcspResponse"));
+            }
+        };
+
+        debug!("Ocsp response successfully decoded");
+        Ok(r)
+    }
+
     /// encode to ASN.1 DER
     pub fn to_der(&self) -> Result<Bytes> {
+        let tlv = DerWriter::encode(|w| self.write_der(w))?;
+        trace!("Ocsp response {}", hex::encode(&tlv));
+        Ok(tlv)
+    }
+}
+
+impl DerEncode for OcspResponse {
+    fn write_der(&self, w: &mut DerWriter<'_>) -> Result<()> {
         trace!("Ocsp Response: {:?}", self);
 
-        let mut v = vec![ASN1_ENUMERATED, 0x01];
-        v.push(self.resp_status as u8);
+        w.write_sequence(|w| {
+            w.write_raw(&[ASN1_ENUMERATED, 0x01, self.resp_status as u8]);
+            if let Some(r) = &self.resp_bytes {
+                r.write_der(w)?;
+            }
+            Ok(())
+        })?;
 
-        if let Some(r) = &self.resp_bytes {
-            v.extend(r.to_der()?);
+        trace!("Ocsp response successfully encoded");
+        Ok(())
+    }
+}
+
+impl<'a> DerDecode<'a> for OcspResponse {
+    fn from_der(raw: &'a [u8]) -> Result<Self> {
+        OcspResponse::parse(raw)
+    }
+}
+
+/// check RFC 6960 consistency of a single response
+fn validate_one_resp(one: &OneResp) -> Result<()> {
+    if let Some(next) = one.next_update {
+        if next < one.this_update {
+            error!(
+                "nextUpdate {:?} is earlier than thisUpdate {:?}",
+                next, one.this_update
+            );
+            return Err(OcspError::GenNextUpdateBeforeThisUpdate);
         }
+    }
+    Ok(())
+}
 
-        let len = asn1_encode_length(v.len())?;
-        let mut tlv = vec![ASN1_SEQUENCE];
-        tlv.extend(len);
-        tlv.extend(v);
+/// Builder of [OneResp], checked at [OneRespBuilder::build]
+#[derive(Debug, Clone)]
+pub struct OneRespBuilder {
+    cid: CertId,
+    cert_status: Option<CertStatus>,
+    this_update: Option<GeneralizedTime>,
+    next_update: Option<GeneralizedTime>,
+    one_resp_ext: Option<Vec<OcspExtI>>,
+}
 
-        trace!("Ocsp response successfully encoded");
-        trace!("Ocsp response {}", hex::encode(&tlv));
-        Ok(tlv)
+impl OneRespBuilder {
+    /// start a single response for cid
+    pub fn new(cid: CertId) -> Self {
+        OneRespBuilder {
+            cid,
+            cert_status: None,
+            this_update: None,
+            next_update: None,
+            one_resp_ext: None,
+        }
+    }
+
+    /// cert is good
+    pub fn good(self) -> Self {
+        self.status(CertStatus::Good)
+    }
+
+    /// cert is revoked at time
+    pub fn revoked(self, time: GeneralizedTime, reason: Option<CrlReason>) -> Self {
+        self.status(CertStatus::Revoked(RevokedInfo::new(time, reason)))
+    }
+
+    /// cert is unknown to the responder
+    pub fn unknown(self) -> Self {
+        self.status(CertStatus::Unknown)
+    }
+
+    /// set cert status
+    pub fn status(mut self, status: CertStatus) -> Self {
+        self.cert_status = Some(status);
+        self
+    }
+
+    /// time at which the status is known to be correct
+    pub fn this_update(mut self, time: GeneralizedTime) -> Self {
+        self.this_update = Some(time);
+        self
+    }
+
+    /// time at or before which newer status will be available  
+    /// must not be earlier than thisUpdate
+    pub fn next_update(mut self, time: GeneralizedTime) -> Self {
+        self.next_update = Some(time);
+        self
+    }
+
+    /// add a single response extension
+    pub fn extension(mut self, ext: OcspExtI) -> Self {
+        self.one_resp_ext.get_or_insert_with(Vec::new).push(ext);
+        self
+    }
+
+    /// return single response  
+    /// - status and thisUpdate are required  
+    /// - nextUpdate must not be earlier than thisUpdate
+    pub fn build(self) -> Result<OneResp> {
+        let one = OneResp {
+            cid: self.cid,
+            cert_status: self
+                .cert_status
+                .ok_or(OcspError::GenMissingField("certStatus"))?,
+            this_update: self
+                .this_update
+                .ok_or(OcspError::GenMissingField("thisUpdate"))?,
+            next_update: self.next_update,
+            one_resp_ext: self.one_resp_ext,
+        };
+        validate_one_resp(&one)?;
+        Ok(one)
+    }
+}
+
+/// Builder of successful [OcspResponse] with basic response type  
+/// checked at [OcspResponseBuilder::build]
+#[derive(Debug, Clone)]
+pub struct OcspResponseBuilder {
+    responder_id: ResponderId,
+    produced_at: Option<GeneralizedTime>,
+    responses: Vec<OneResp>,
+    resp_ext: Option<Vec<OcspExtI>>,
+    signature: Option<(AlgorithmIdentifier, Bytes)>,
+    certs: Option<Vec<Bytes>>,
+}
+
+impl OcspResponseBuilder {
+    /// start a response by responder id  
+    /// producedAt defaults to current time in whole seconds,
+    /// so repeated [OcspResponseBuilder::build_data] agree
+    pub fn new(id: ResponderId) -> Self {
+        OcspResponseBuilder {
+            responder_id: id,
+            produced_at: Some(GeneralizedTime::now()),
+            responses: vec![],
+            resp_ext: None,
+            signature: None,
+            certs: None,
+        }
+    }
+
+    /// time of signing the response  
+    /// must not be in the future
+    pub fn produced_at(mut self, time: GeneralizedTime) -> Self {
+        self.produced_at = Some(time);
+        self
+    }
+
+    /// add a single response
+    pub fn response(mut self, one: OneResp) -> Self {
+        self.responses.push(one);
+        self
+    }
+
+    /// add single responses
+    pub fn responses(mut self, list: impl IntoIterator<Item = OneResp>) -> Self {
+        self.responses.extend(list);
+        self
+    }
+
+    /// add a response extension, eg. nonce
+    pub fn extension(mut self, ext: OcspExtI) -> Self {
+        self.resp_ext.get_or_insert_with(Vec::new).push(ext);
+        self
+    }
+
+    /// signature over DER of [OcspResponseBuilder::build_data]  
+    /// algorithm can be an [Oid]
+    pub fn signature(mut self, algo: impl Into<AlgorithmIdentifier>, sign: Bytes) -> Self {
+        self.signature = Some((algo.into(), sign));
+        self
+    }
+
+    /// certificates helping the client verify the signature
+    pub fn certs(mut self, certs: Vec<Bytes>) -> Self {
+        self.certs = Some(certs);
+        self
+    }
+
+    /// return response data to be signed  
+    /// - at least one single response  
+    /// - every single response is consistent, see [OneRespBuilder::build]  
+    /// - producedAt is not in the future
+    pub fn build_data(&self) -> Result<ResponseData> {
+        if self.responses.is_empty() {
+            error!("Response data contains no single response");
+            return Err(OcspError::GenEmptyResponses);
+        }
+        for one in &self.responses {
+            validate_one_resp(one)?;
+        }
+
+        let produced_at = self
+            .produced_at
+            .ok_or(OcspError::GenMissingField("producedAt"))?;
+        if produced_at > GeneralizedTime::try_from(SystemTime::now())? {
+            error!("producedAt {:?} is in the future", produced_at);
+            return Err(OcspError::GenProducedAtInFuture);
+        }
+
+        Ok(ResponseData::new(
+            self.responder_id.clone(),
+            produced_at,
+            self.responses.clone(),
+            self.resp_ext.clone(),
+        ))
+    }
+
+    /// return successful response, see [OcspResponseBuilder::build_data]  
+    /// signature is required
+    pub fn build(self) -> Result<OcspResponse> {
+        let data = self.build_data()?;
+        let (algo, sign) = self
+            .signature
+            .ok_or(OcspError::GenMissingField("signature"))?;
+        let basic = BasicResponse::new(data, algo, sign, self.certs);
+        OcspResponseBuilder::wrap(basic)
+    }
+
+    /// return successful response signed by signer, see [OcspResponseBuilder::build_data]  
+    /// signature set by [OcspResponseBuilder::signature] is ignored
+    pub fn sign<S: Signer + ?Sized>(self, signer: &S) -> Result<OcspResponse> {
+        let mut basic = self.build_data()?.sign(signer)?;
+        basic.certs = self.certs;
+        OcspResponseBuilder::wrap(basic)
+    }
+
+    fn wrap(basic: BasicResponse) -> Result<OcspResponse> {
+        let resp_type = Oid::from_bytes(&OCSP_RESPONSE_BASIC_HEX)?;
+        Ok(OcspResponse::new_success(ResponseBytes::new_basic(
+            resp_type, basic,
+        )?))
     }
 }
 
@@ -569,13 +1385,13 @@ mod test {
         ];
         let sn = vec![0x41, 0x30, 0x09, 0x83, 0x33, 0x1f, 0x9d, 0x4f];
         let certid = CertId::new(oid.clone(), &name, &key, &sn);
-        let good = CertStatus::new(CertStatusCode::Good, None);
+        let good = CertStatus::Good;
         let gt = GeneralizedTime::new(2021, 1, 12, 3, 26, 43).unwrap();
 
         let one = OneResp {
             cid: certid.clone(),
             cert_status: good,
-            this_update: gt.clone(),
+            this_update: gt,
             next_update: None,
             one_resp_ext: None,
         };
@@ -584,9 +1400,9 @@ mod test {
         let certid2 = CertId::new(oid, &name, &key, &sn2);
         let rev_t = GeneralizedTime::new(2020, 11, 30, 1, 48, 25).unwrap();
         let rev_info = RevokedInfo::new(rev_t, Some(CrlReason::OcspRevokeUnspecified));
-        let revoke = CertStatus::new(CertStatusCode::Revoked, Some(rev_info));
+        let revoke = CertStatus::Revoked(rev_info);
         let two = OneResp {
-            cid: certid2,
+            cid: certid2.clone(),
             cert_status: revoke,
             this_update: gt,
             next_update: None,
@@ -594,7 +1410,7 @@ mod test {
         };
 
         let list = [one, two].to_vec();
-        let data = ResponseData::new(id, produce, list, None);
+        let data = ResponseData::new(id.clone(), produce, list, None);
 
         let oid = Oid::new_from_dot(ALGO_SHA1_WITH_RSA_ENCRYPTION_DOT).unwrap();
 
@@ -623,7 +1439,7 @@ mod test {
         };
         let sign = some_signing_machine().await;
 
-        let basic = BasicResponse::new(data, oid, sign, None);
+        let basic = BasicResponse::new(data, oid.clone(), sign.clone(), None);
         let resp_type = Oid::new_from_dot(OCSP_RESPONSE_BASIC_DOT).unwrap();
         let bytes = ResponseBytes::new_basic(resp_type, basic).unwrap();
         let ocsp = OcspResponse::new_success(bytes);
@@ -673,6 +1489,249 @@ mod test {
         ];
 
         assert_eq!(c, v);
+
+        // same response through builders
+        let one = OneRespBuilder::new(certid)
+            .good()
+            .this_update(gt)
+            .build()
+            .unwrap();
+        let two = OneRespBuilder::new(certid2)
+            .revoked(rev_t, Some(CrlReason::OcspRevokeUnspecified))
+            .this_update(gt)
+            .build()
+            .unwrap();
+        let ocsp = OcspResponseBuilder::new(id)
+            .produced_at(produce)
+            .response(one)
+            .response(two)
+            .signature(oid, sign)
+            .build()
+            .unwrap();
+        assert_eq!(c, ocsp.to_der().unwrap());
+    }
+
+    /// builders reject inconsistent responses
+    #[test]
+    fn response_builder_checks() {
+        let cid = CertId::new(
+            Oid::new_from_dot(ALGO_SHA1_DOT).unwrap(),
+            &[0; 20],
+            &[0; 20],
+            &[0x01],
+        );
+        let this = GeneralizedTime::new(2021, 1, 12, 3, 26, 43).unwrap();
+        let next = GeneralizedTime::new(2021, 1, 19, 3, 26, 43).unwrap();
+
+        let one = OneRespBuilder::new(cid.clone())
+            .good()
+            .this_update(this)
+            .next_update(next)
+            .build()
+            .unwrap();
+        assert_eq!(Some(next), one.next_update);
+
+        match OneRespBuilder::new(cid.clone())
+            .good()
+            .this_update(next)
+            .next_update(this)
+            .build()
+        {
+            Err(OcspError::GenNextUpdateBeforeThisUpdate) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+        match OneRespBuilder::new(cid.clone()).this_update(this).build() {
+            Err(OcspError::GenMissingField("certStatus")) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+        match OneRespBuilder::new(cid.clone()).unknown().build() {
+            Err(OcspError::GenMissingField("thisUpdate")) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+
+        let id = ResponderId::new_key_hash(&[0; 20]);
+        match OcspResponseBuilder::new(id.clone()).build_data() {
+            Err(OcspError::GenEmptyResponses) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+        let future = GeneralizedTime::new(2999, 1, 1, 0, 0, 0).unwrap();
+        match OcspResponseBuilder::new(id.clone())
+            .produced_at(future)
+            .response(one.clone())
+            .build_data()
+        {
+            Err(OcspError::GenProducedAtInFuture) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+        // responses added as struct literals are checked too
+        let mut bad = one.clone();
+        bad.next_update = Some(GeneralizedTime::new(2020, 1, 1, 0, 0, 0).unwrap());
+        match OcspResponseBuilder::new(id.clone())
+            .response(bad)
+            .build_data()
+        {
+            Err(OcspError::GenNextUpdateBeforeThisUpdate) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+
+        // producedAt defaults to creation time, data is stable for signing
+        let builder = OcspResponseBuilder::new(id).response(one);
+        let data = builder.build_data().unwrap();
+        assert_eq!(
+            data.to_der().unwrap(),
+            builder.build_data().unwrap().to_der().unwrap()
+        );
+        // whole seconds only, YYYYMMDDHHMMSSZ
+        let produced = data.produced_at.to_der().unwrap();
+        assert_eq!(15, produced[1]);
+        assert_eq!(17, produced.len());
+        match builder.build() {
+            Err(OcspError::GenMissingField("signature")) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+    }
+
+    /// signed response verifies over its response data
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn response_builder_sign() {
+        use crate::oid::ALGO_ED25519_DOT;
+        use crate::sign::{test::ED25519_KEY_PKCS8, Ed25519Signer};
+        use ed25519_dalek::pkcs8::DecodePrivateKey;
+        use signature::Verifier;
+
+        let cid = CertId::new(
+            Oid::new_from_dot(ALGO_SHA1_DOT).unwrap(),
+            &[0x01; 20],
+            &[0x02; 20],
+            &[0x03],
+        );
+        let one = OneRespBuilder::new(cid)
+            .good()
+            .this_update(GeneralizedTime::new(2021, 1, 12, 3, 26, 43).unwrap())
+            .build()
+            .unwrap();
+        let signer = Ed25519Signer::from_pem(ED25519_KEY_PKCS8).unwrap();
+        let ocsp = OcspResponseBuilder::new(ResponderId::new_key_hash(&[0; 20]))
+            .response(one)
+            .certs(vec![vec![0x30, 0x00]])
+            .sign(&signer)
+            .unwrap();
+
+        let v = ocsp.to_der().unwrap();
+        let basic = OcspResponse::parse(&v)
+            .unwrap()
+            .resp_bytes
+            .unwrap()
+            .response_data;
+        assert_eq!(basic.signature_algo.oid.to_dot(), ALGO_ED25519_DOT);
+        assert_eq!(basic.certs, Some(vec![vec![0x30, 0x00]]));
+
+        let key = ed25519_dalek::SigningKey::from_pkcs8_pem(ED25519_KEY_PKCS8).unwrap();
+        let sign = ed25519_dalek::Signature::from_slice(&basic.signature).unwrap();
+        let tbs = basic.tbs_resp_data().to_der().unwrap();
+        key.verifying_key().verify(&tbs, &sign).unwrap();
+    }
+
+    /// response by openssl ocsp verifies with responder certificate
+    #[cfg(feature = "ecdsa")]
+    #[test]
+    fn basic_resp_verify() {
+        use crate::verify::test::{key, CA_CERT, P256_SPKI, RSA_SPKI};
+
+        let v = hex::decode(
+            "308201010a0100a081fb3081f806092b06010505073001010481ea3081e73081\
+            8ea11430123110300e06035504030c0754657374204341180f32303236313031\
+            383037313335355a30653063303b300906052b0e03021a05000414bf7052c8b9\
+            c0f760c89123e099815eb2c039422604140c316bc36fbf60ce6108ebbdffd261\
+            4737ae0e0d020212348000180f32303236313031383037313335355aa011180f\
+            32303236313031393037313335355a300a06082a8648ce3d0403020348003045\
+            022038352234d3ffb690f7765b1426c8c280eeafd843a5d82f95c843ad869acf\
+            c2b40221008d87dbfd33086ba3c3eccbeb2099413477c3990f4e14d01b91bab9\
+            0b0d43830e",
+        )
+        .unwrap();
+        let basic = OcspResponse::parse(&v)
+            .unwrap()
+            .resp_bytes
+            .unwrap()
+            .response_data;
+        let ca = PublicKey::from_pem(CA_CERT).unwrap();
+        basic.verify(&ca).unwrap();
+        match basic.verify(&key(P256_SPKI)) {
+            Err(OcspError::OcspBadSignature) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+        match basic.verify(&key(RSA_SPKI)) {
+            Err(OcspError::OcspSignatureKeyMismatch(_)) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+    }
+
+    /// signature is verified over received response data, which may not be canonical
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn basic_resp_verify_received_bytes() {
+        use crate::sign::{test::ED25519_KEY_PKCS8, Ed25519Signer};
+        use crate::verify::test::{key, ED25519_SPKI};
+
+        let cid = CertId::new(
+            Oid::new_from_dot(ALGO_SHA1_DOT).unwrap(),
+            &[0x01; 20],
+            &[0x02; 20],
+            &[0x03],
+        );
+        let one = OneRespBuilder::new(cid)
+            .good()
+            .this_update(GeneralizedTime::new(2021, 1, 12, 3, 26, 43).unwrap())
+            .build()
+            .unwrap();
+        let data = OcspResponseBuilder::new(ResponderId::new_key_hash(&[0; 20]))
+            .response(one)
+            .build_data()
+            .unwrap()
+            .to_der()
+            .unwrap();
+        // DEFAULT v1 written explicitly, re-encoding omits it
+        let data = DerObject::decode(&data).unwrap();
+        let tbs = DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                w.write_raw(&[ASN1_EXPLICIT_0, 0x03, 0x02, 0x01, 0x00]);
+                w.write_raw(data.value());
+                Ok(())
+            })
+        })
+        .unwrap();
+        let signer = Ed25519Signer::from_pem(ED25519_KEY_PKCS8).unwrap();
+        let sign = signer.sign(&tbs).unwrap();
+        let v = DerWriter::encode(|w| {
+            w.write_sequence(|w| {
+                w.write_raw(&tbs);
+                signer.algorithm().write_der(w)?;
+                w.write_tagged(ASN1_BIT_STRING, |w| {
+                    w.write_raw(&[0x00]);
+                    w.write_raw(&sign);
+                    Ok(())
+                })
+            })
+        })
+        .unwrap();
+
+        let mut basic = BasicResponse::parse(&v).unwrap();
+        assert_eq!(basic.tbs_raw(), Some(&tbs[..]));
+        basic.verify(&key(ED25519_SPKI)).unwrap();
+        // stapled as received
+        assert_eq!(v, basic.to_der().unwrap());
+        assert_eq!(v.len(), basic.encoded_len().unwrap());
+
+        // changed response data is no longer what was signed
+        basic.tbs_resp_data_mut().produced_at = GeneralizedTime::new(2030, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(None, basic.tbs_raw());
+        assert_ne!(v, basic.to_der().unwrap());
+        match basic.verify(&key(ED25519_SPKI)) {
+            Err(OcspError::OcspBadSignature) => {}
+            r => panic!("unexpected {:?}", r),
+        }
     }
 
     /// basic resp to ASN.1 DER
@@ -695,13 +1754,13 @@ mod test {
         ];
         let sn = vec![0x41, 0x30, 0x09, 0x83, 0x33, 0x1f, 0x9d, 0x4f];
         let certid = CertId::new(oid.clone(), &name, &key, &sn);
-        let good = CertStatus::new(CertStatusCode::Good, None);
+        let good = CertStatus::Good;
         let gt = GeneralizedTime::new(2021, 1, 12, 3, 26, 43).unwrap();
 
         let one = OneResp {
             cid: certid.clone(),
             cert_status: good,
-            this_update: gt.clone(),
+            this_update: gt,
             next_update: None,
             one_resp_ext: None,
         };
@@ -710,7 +1769,7 @@ mod test {
         let certid2 = CertId::new(oid, &name, &key, &sn2);
         let rev_t = GeneralizedTime::new(2020, 11, 30, 1, 48, 25).unwrap();
         let rev_info = RevokedInfo::new(rev_t, Some(CrlReason::OcspRevokeUnspecified));
-        let revoke = CertStatus::new(CertStatusCode::Revoked, Some(rev_info));
+        let revoke = CertStatus::Revoked(rev_info);
         let two = OneResp {
             cid: certid2,
             cert_status: revoke,
@@ -815,13 +1874,13 @@ mod test {
         ];
         let sn = vec![0x41, 0x30, 0x09, 0x83, 0x33, 0x1f, 0x9d, 0x4f];
         let certid = CertId::new(oid.clone(), &name, &key, &sn);
-        let good = CertStatus::new(CertStatusCode::Good, None);
+        let good = CertStatus::Good;
         let gt = GeneralizedTime::new(2021, 1, 12, 3, 26, 43).unwrap();
 
         let one = OneResp {
             cid: certid.clone(),
             cert_status: good,
-            this_update: gt.clone(),
+            this_update: gt,
             next_update: None,
             one_resp_ext: None,
         };
@@ -830,7 +1889,7 @@ mod test {
         let certid2 = CertId::new(oid, &name, &key, &sn2);
         let rev_t = GeneralizedTime::new(2020, 11, 30, 1, 48, 25).unwrap();
         let rev_info = RevokedInfo::new(rev_t, Some(CrlReason::OcspRevokeUnspecified));
-        let revoke = CertStatus::new(CertStatusCode::Revoked, Some(rev_info));
+        let revoke = CertStatus::Revoked(rev_info);
         let two = OneResp {
             cid: certid2,
             cert_status: revoke,
@@ -898,13 +1957,13 @@ mod test {
         ];
         let sn = vec![0x41, 0x30, 0x09, 0x83, 0x33, 0x1f, 0x9d, 0x4f];
         let certid = CertId::new(oid.clone(), &name, &key, &sn);
-        let good = CertStatus::new(CertStatusCode::Good, None);
+        let good = CertStatus::Good;
         let gt = GeneralizedTime::new(2021, 1, 12, 3, 26, 43).unwrap();
 
         let one = OneResp {
             cid: certid.clone(),
             cert_status: good,
-            this_update: gt.clone(),
+            this_update: gt,
             next_update: None,
             one_resp_ext: None,
         };
@@ -913,7 +1972,7 @@ mod test {
         let certid2 = CertId::new(oid, &name, &key, &sn2);
         let rev_t = GeneralizedTime::new(2020, 11, 30, 1, 48, 25).unwrap();
         let rev_info = RevokedInfo::new(rev_t, Some(CrlReason::OcspRevokeUnspecified));
-        let revoke = CertStatus::new(CertStatusCode::Revoked, Some(rev_info));
+        let revoke = CertStatus::Revoked(rev_info);
         let two = OneResp {
             cid: certid2,
             cert_status: revoke,
@@ -922,8 +1981,8 @@ mod test {
             one_resp_ext: None,
         };
 
-        let resp = [one, two];
-        let v = OneResp::list_to_der(&resp).unwrap();
+        let resp = vec![one, two];
+        let v = resp.to_der().unwrap();
 
         let c = vec![
             0x30, 0x81, 0xc6, 0x30, 0x56, 0x30, 0x41, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03,
@@ -960,13 +2019,13 @@ mod test {
         ];
         let sn = vec![0x41, 0x30, 0x09, 0x83, 0x33, 0x1f, 0x9d, 0x4f];
         let certid = CertId::new(oid, &name, &key, &sn);
-        let good = CertStatus::new(CertStatusCode::Good, None);
+        let good = CertStatus::Good;
         let gt = GeneralizedTime::new(2021, 1, 13, 3, 9, 25).unwrap();
 
         let one = OneResp {
             cid: certid,
             cert_status: good,
-            this_update: gt.clone(),
+            this_update: gt,
             next_update: Some(gt),
             one_resp_ext: None,
         };
@@ -1001,13 +2060,13 @@ mod test {
         ];
         let sn = vec![0x41, 0x30, 0x09, 0x83, 0x33, 0x1f, 0x9d, 0x4f];
         let certid = CertId::new(oid, &name, &key, &sn);
-        let good = CertStatus::new(CertStatusCode::Good, None);
+        let good = CertStatus::Good;
         let gt = GeneralizedTime::new(2021, 1, 13, 3, 9, 25).unwrap();
 
         let one = OneResp {
             cid: certid,
             cert_status: good,
-            this_update: gt.clone(),
+            this_update: gt,
             next_update: None,
             one_resp_ext: None,
         };
@@ -1027,30 +2086,37 @@ mod test {
         assert_eq!(c, v);
     }
 
-    // test good & unknown status don't deal with revoke info
+    // test status conversions and accessors
     #[test]
-    fn cert_good_with_rev_info() {
+    fn cert_status_conversion() {
         let rev_info = RevokedInfo::new(
             GeneralizedTime::new(2021, 1, 1, 1, 1, 1).unwrap(),
             Some(CrlReason::OcspRevokeUnspecified),
         );
 
-        let good_rev = CertStatus::new(CertStatusCode::Good, Some(rev_info));
-        assert!(good_rev.revoke_info.is_none());
+        let good = CertStatus::try_from(CertStatusCode::Good).unwrap();
+        assert_eq!(CertStatus::Good, good);
+        assert!(good.revoke_info().is_none());
+        let unknown = CertStatus::try_from(CertStatusCode::Unknown).unwrap();
+        assert_eq!(CertStatusCode::Unknown, CertStatusCode::from(&unknown));
+        match CertStatus::try_from(CertStatusCode::Revoked) {
+            Err(OcspError::GenRevokeInfoNotFound) => {}
+            r => panic!("unexpected {:?}", r),
+        }
 
-        let rev_info = RevokedInfo::new(
-            GeneralizedTime::new(2021, 1, 1, 1, 1, 1).unwrap(),
-            Some(CrlReason::OcspRevokeUnspecified),
+        let revoke = CertStatus::from(rev_info.clone());
+        assert_eq!(CertStatusCode::Revoked, revoke.code());
+        assert_eq!(Some(&rev_info), revoke.revoke_info());
+        assert_eq!(
+            revoke,
+            CertStatus::parse(&revoke.to_der().unwrap()).unwrap()
         );
-
-        let unknown_rev = CertStatus::new(CertStatusCode::Unknown, Some(rev_info));
-        assert!(unknown_rev.revoke_info.is_none());
     }
 
     // test unknown cert status
     #[test]
     fn cert_unknown() {
-        let unknown = CertStatus::new(CertStatusCode::Unknown, None);
+        let unknown = CertStatus::Unknown;
         let v = unknown.to_der().unwrap();
         assert_eq!(vec![0x82, 0x00], v);
     }
@@ -1062,7 +2128,7 @@ mod test {
             GeneralizedTime::new(2021, 1, 1, 1, 1, 1).unwrap(),
             Some(CrlReason::OcspRevokeUnspecified),
         );
-        let revoke = CertStatus::new(CertStatusCode::Revoked, Some(rev_info));
+        let revoke = CertStatus::Revoked(rev_info);
         let v = revoke.to_der().unwrap();
 
         assert_eq!(
@@ -1077,7 +2143,7 @@ mod test {
     // return good cert status
     #[test]
     fn cert_good() {
-        let good = CertStatus::new(CertStatusCode::Good, None);
+        let good = CertStatus::Good;
         let v = good.to_der().unwrap();
         assert_eq!(vec![0x80, 0x00], v);
     }
@@ -1118,6 +2184,284 @@ mod test {
         );
     }
 
+    /// ocsp response from ASN.1 DER
+    #[test]
+    fn ocsp_resp_success_parse() {
+        let resp_hex = "308202270a0100a08202203082021c06\
+    092b06010505073001010482020d3082\
+    02093081f2a2160414366f35fbef16c6\
+    ba8a3183426d97ba894d556e91180f32\
+    303231303131323033323634335a3081\
+    c630563041300906052b0e03021a0500\
+    0414694d18a9be42f7802614d4844f23\
+    601478b788200414397be002a2f571fd\
+    80dceb52a17a7f8b632be75502084130\
+    0983331f9d4f8000180f323032313031\
+    31323033323634335a306c3041300906\
+    052b0e03021a05000414694d18a9be42\
+    f7802614d4844f23601478b788200414\
+    397be002a2f571fd80dceb52a17a7f8b\
+    632be75502086378e51d448ff46da116\
+    180f3230323031313330303134383235\
+    5aa0030a0100180f3230323130313132\
+    3033323634335a300d06092a864886f7\
+    0d010105050003820101001e022d5ba2\
+    5aa6ee97c5d910c61ebeb73db75a767d\
+    eb43af88c2a56377d9e5aeaa54843008\
+    7b5429d9b90b30569f9444676ad3a988\
+    5fb6d29cd46489ea1a82c369790d2a49\
+    43f4ca93c97706c929707fb6e5b49d43\
+    3b84003bd9aa24a395278ab63e7a2622\
+    d2ec7d3579453e7960bbcfca6d0d3db0\
+    fe460f7c2bbaf72e8c6fb85c7c6537ea\
+    0cb3c36811a8950f7396987598a5b3c8\
+    9fc1466cb17c559589d85d8af954d607\
+    c43ff708ddf5d6672faa14fbc717b553\
+    52c2110450f220e8a0be9e6a8664a6ac\
+    dca63ae3a706b72cc19da227ce5e1a8f\
+    69adce38f45f8dd3874885898d7cebd6\
+    057fd8e5f327694198edd90fe6e82161\
+    3be71e3ba24f4db85f10a7";
+        let resp_v8 = hex::decode(resp_hex).unwrap();
+        let resp = OcspResponse::parse(&resp_v8[..]).unwrap();
+        let bytes = resp.resp_bytes.as_ref().unwrap();
+        assert_eq!(bytes.response_data.tbs_resp_data().responses.len(), 2);
+        assert_eq!(resp_v8, resp.to_der().unwrap());
+
+        // appending to a caller supplied buffer
+        let mut buf = vec![0xff];
+        resp.to_der_into(&mut buf).unwrap();
+        assert_eq!(0xff, buf[0]);
+        assert_eq!(resp_v8, buf[1..]);
+        assert_eq!(resp_v8.len(), resp.encoded_len().unwrap());
+
+        // writing to io::Write
+        let mut out = vec![];
+        assert_eq!(resp_v8.len(), resp.to_writer(&mut out).unwrap());
+        assert_eq!(resp_v8, out);
+
+        // same through DerDecode, as generic code would
+        let resp = <OcspResponse as DerDecode<'_>>::from_der(&resp_v8[..]).unwrap();
+        assert_eq!(resp_v8, DerEncode::to_der(&resp).unwrap());
+
+        // DER response is accepted in strict mode, trailing data is not
+        let _ = OcspResponse::parse_with_mode(&resp_v8[..], DecodeMode::Strict).unwrap();
+        let mut trailing = resp_v8.clone();
+        trailing.push(0x00);
+        let _ = OcspResponse::parse(&trailing[..]).unwrap();
+        match OcspResponse::parse_with_mode(&trailing[..], DecodeMode::Strict) {
+            Err(OcspError::Asn1TrailingData(1)) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+    }
+
+    /// non-successful ocsp response from ASN.1 DER
+    #[test]
+    fn ocsp_resp_non_success_parse() {
+        let resp_v8 = vec![0x30, 0x03, 0x0a, 0x01, 0x01];
+        let resp = OcspResponse::parse(&resp_v8[..]).unwrap();
+        assert!(resp.resp_bytes.is_none());
+        assert_eq!(resp_v8, resp.to_der().unwrap());
+    }
+
+    /// successful status requires response bytes
+    #[test]
+    fn ocsp_resp_success_without_bytes() {
+        let resp_v8 = [0x30, 0x03, 0x0a, 0x01, 0x00];
+        assert!(OcspResponse::parse(&resp_v8[..]).is_err());
+    }
+
+    /// basic response carrying certs from ASN.1 DER
+    #[test]
+    fn basic_resp_with_certs_parse() {
+        let resp_hex = "308202270a0100a08202203082021c06\
+    092b06010505073001010482020d3082\
+    02093081f2a2160414366f35fbef16c6\
+    ba8a3183426d97ba894d556e91180f32\
+    303231303131323033323634335a3081\
+    c630563041300906052b0e03021a0500\
+    0414694d18a9be42f7802614d4844f23\
+    601478b788200414397be002a2f571fd\
+    80dceb52a17a7f8b632be75502084130\
+    0983331f9d4f8000180f323032313031\
+    31323033323634335a306c3041300906\
+    052b0e03021a05000414694d18a9be42\
+    f7802614d4844f23601478b788200414\
+    397be002a2f571fd80dceb52a17a7f8b\
+    632be75502086378e51d448ff46da116\
+    180f3230323031313330303134383235\
+    5aa0030a0100180f3230323130313132\
+    3033323634335a300d06092a864886f7\
+    0d010105050003820101001e022d5ba2\
+    5aa6ee97c5d910c61ebeb73db75a767d\
+    eb43af88c2a56377d9e5aeaa54843008\
+    7b5429d9b90b30569f9444676ad3a988\
+    5fb6d29cd46489ea1a82c369790d2a49\
+    43f4ca93c97706c929707fb6e5b49d43\
+    3b84003bd9aa24a395278ab63e7a2622\
+    d2ec7d3579453e7960bbcfca6d0d3db0\
+    fe460f7c2bbaf72e8c6fb85c7c6537ea\
+    0cb3c36811a8950f7396987598a5b3c8\
+    9fc1466cb17c559589d85d8af954d607\
+    c43ff708ddf5d6672faa14fbc717b553\
+    52c2110450f220e8a0be9e6a8664a6ac\
+    dca63ae3a706b72cc19da227ce5e1a8f\
+    69adce38f45f8dd3874885898d7cebd6\
+    057fd8e5f327694198edd90fe6e82161\
+    3be71e3ba24f4db85f10a7";
+        let resp_v8 = hex::decode(resp_hex).unwrap();
+        let resp = OcspResponse::parse(&resp_v8[..]).unwrap();
+        let mut basic = resp.resp_bytes.unwrap().response_data;
+        let cert = vec![0x30, 0x03, 0x02, 0x01, 0x01];
+        basic.certs = Some(vec![cert.clone(), cert]);
+        let v = basic.to_der().unwrap();
+
+        let parsed = BasicResponse::parse(&v[..]).unwrap();
+        assert_eq!(parsed.certs.as_ref().unwrap().len(), 2);
+        assert_eq!(v, parsed.to_der().unwrap());
+    }
+
+    /// good one resp with next update from ASN.1 DER
+    #[test]
+    fn one_resp_next_update_parse() {
+        let one_hex = "30693041300906052b0e03021a050004\
+    14694d18a9be42f7802614d4844f2360\
+    1478b788200414397be002a2f571fd80\
+    dceb52a17a7f8b632be7550208413009\
+    83331f9d4f8000180f32303231303131\
+    333033303932355aa011180f32303231\
+    303131333033303932355a";
+        let one_v8 = hex::decode(one_hex).unwrap();
+        let one = OneResp::parse(&one_v8[..]).unwrap();
+        assert!(one.next_update.is_some());
+        assert_eq!(one_v8, one.to_der().unwrap());
+
+        // duplicate nextUpdate is reported by position
+        let next = one_v8[one_v8.len() - 19..].to_vec();
+        assert_eq!(0xa0, next[0]);
+        let mut dup = one_v8.clone();
+        dup.extend_from_slice(&next);
+        dup[1] += 19;
+        let err = OneResp::parse(&dup).unwrap_err();
+        assert_eq!(Some("[4]"), err.path());
+        assert_eq!(Some(one_v8.len()), err.offset());
+        assert!(matches!(
+            err.root(),
+            OcspError::Asn1MismatchError("OneResp")
+        ));
+    }
+
+    /// explicit v1 in response data is accepted
+    #[test]
+    fn response_data_explicit_version_parse() {
+        let data = vec![
+            0x30, 0x30, 0xa0, 0x03, 0x02, 0x01, 0x00, 0xa2, 0x16, 0x04, 0x14, 0x36, 0x6f, 0x35,
+            0xfb, 0xef, 0x16, 0xc6, 0xba, 0x8a, 0x31, 0x83, 0x42, 0x6d, 0x97, 0xba, 0x89, 0x4d,
+            0x55, 0x6e, 0x91, 0x18, 0x0f, 0x32, 0x30, 0x32, 0x31, 0x30, 0x31, 0x31, 0x32, 0x30,
+            0x33, 0x32, 0x36, 0x34, 0x33, 0x5a, 0x30, 0x00,
+        ];
+        let rd = ResponseData::parse(&data[..]).unwrap();
+        assert_eq!(rd.version, Version::V1);
+        assert!(rd.responses.is_empty());
+        let e = ResponseData::parse_with_mode(&data[..], DecodeMode::Strict).unwrap_err();
+        match e.root() {
+            OcspError::Asn1ExplicitDefault("version") => {}
+            r => panic!("unexpected {:?}", r),
+        }
+        assert_eq!(Some("version"), e.path());
+        assert_eq!(Some(2), e.offset());
+
+        // unknown version
+        let mut data = data;
+        data[6] = 0x01;
+        assert!(ResponseData::parse(&data[..]).is_err());
+    }
+
+    /// items past responseExtensions are not dropped
+    #[test]
+    fn response_data_extra_items() {
+        let mut data = vec![
+            0x30, 0x00, 0xa2, 0x16, 0x04, 0x14, 0x36, 0x6f, 0x35, 0xfb, 0xef, 0x16, 0xc6, 0xba,
+            0x8a, 0x31, 0x83, 0x42, 0x6d, 0x97, 0xba, 0x89, 0x4d, 0x55, 0x6e, 0x91, 0x18, 0x0f,
+            0x32, 0x30, 0x32, 0x31, 0x30, 0x31, 0x31, 0x32, 0x30, 0x33, 0x32, 0x36, 0x34, 0x33,
+            0x5a, 0x30, 0x00,
+        ];
+        // critical nonce extension, then junk [5]
+        data.extend_from_slice(&[
+            0xa1, 0x14, 0x30, 0x12, 0x30, 0x10, 0x06, 0x09, 0x2b, 0x06, 0x01, 0x05, 0x05, 0x07,
+            0x30, 0x01, 0x02, 0x01, 0x01, 0xff, 0x04, 0x00,
+        ]);
+        data[1] = (data.len() - 2) as u8;
+        let rd = ResponseData::parse(&data).unwrap();
+        assert_eq!(1, rd.resp_ext.as_ref().unwrap().len());
+        assert_eq!(data, rd.to_der().unwrap());
+
+        data.extend_from_slice(&[0xa5, 0x00]);
+        data[1] = (data.len() - 2) as u8;
+        for mode in &[DecodeMode::Lenient, DecodeMode::Strict] {
+            let e = ResponseData::parse_with_mode(&data, *mode).unwrap_err();
+            assert!(matches!(e, OcspError::Asn1LengthError("ResponseData")));
+        }
+    }
+
+    /// responder by name from string
+    #[test]
+    fn responder_by_name_to_der() {
+        let name: DistinguishedName = "CN=OCSP,O=Internet Widgits Pty Ltd,ST=Some-State,C=AU"
+            .parse()
+            .unwrap();
+        let id = ResponderId::new_by_name(&name).unwrap();
+        let v = id.to_der().unwrap();
+        let c = vec![
+            0xa1, 0x56, 0x30, 0x54, 0x31, 0x0b, 0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x06, 0x13,
+            0x02, 0x41, 0x55, 0x31, 0x13, 0x30, 0x11, 0x06, 0x03, 0x55, 0x04, 0x08, 0x0c, 0x0a,
+            0x53, 0x6f, 0x6d, 0x65, 0x2d, 0x53, 0x74, 0x61, 0x74, 0x65, 0x31, 0x21, 0x30, 0x1f,
+            0x06, 0x03, 0x55, 0x04, 0x0a, 0x0c, 0x18, 0x49, 0x6e, 0x74, 0x65, 0x72, 0x6e, 0x65,
+            0x74, 0x20, 0x57, 0x69, 0x64, 0x67, 0x69, 0x74, 0x73, 0x20, 0x50, 0x74, 0x79, 0x20,
+            0x4c, 0x74, 0x64, 0x31, 0x0d, 0x30, 0x0b, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x04,
+            0x4f, 0x43, 0x53, 0x50,
+        ];
+
+        assert_eq!(c, v);
+        assert_eq!(name, ResponderId::parse(&v).unwrap().name().unwrap());
+    }
+
+    /// responder by name, example from OpenSSL
+    #[test]
+    fn responder_by_name_parse() {
+        let id_hex = "a1563054310b30090603550406130241\
+    553113301106035504080c0a536f6d65\
+    2d53746174653121301f060355040a0c\
+    18496e7465726e657420576964676974\
+    7320507479204c7464310d300b060355\
+    04030c044f435350";
+        let id_v8 = hex::decode(id_hex).unwrap();
+        let id = ResponderId::parse(&id_v8[..]).unwrap();
+        assert_eq!(id_v8, id.to_der().unwrap());
+    }
+
+    /// revoke info with reason from ASN.1 DER
+    #[test]
+    fn revoke_info_parse() {
+        let ri = RevokedInfo::new(
+            GeneralizedTime::new(2020, 11, 30, 1, 48, 25).unwrap(),
+            Some(CrlReason::OcspRevokeKeyCompromise),
+        );
+        let v = ri.to_der().unwrap();
+        let status = CertStatus::parse(&v[..]).unwrap();
+        assert_eq!(v, status.to_der().unwrap());
+    }
+
+    /// undefined crl reason 7
+    #[test]
+    fn revoke_info_undefined_reason() {
+        let v = vec![
+            0xa1, 0x16, 0x18, 0x0f, 0x32, 0x30, 0x32, 0x30, 0x31, 0x31, 0x33, 0x30, 0x30, 0x31,
+            0x34, 0x38, 0x32, 0x35, 0x5a, 0xa0, 0x03, 0x0a, 0x01, 0x07,
+        ];
+        assert!(RevokedInfo::parse(&v[..]).is_err());
+    }
+
     // init log
     #[allow(dead_code)]
     fn init() {
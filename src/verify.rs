@@ -0,0 +1,533 @@
+//! Verifying signatures of OCSP messages  
+//! supported algorithms depend on crate features `rsa`, `ecdsa` and `ed25519`, as in [crate::sign]
+
+#[cfg(any(feature = "rsa", feature = "ed25519"))]
+use std::convert::TryFrom;
+
+use tracing::{error, trace};
+
+use crate::common::asn1::{
+    asn1_field, asn1_tbs_certificate, AlgorithmIdentifier, AlgorithmParameters, Bytes, Oid,
+    SequenceRef, ASN1_BIT_STRING,
+};
+use crate::err::{OcspError, Result};
+use crate::oid::{
+    ALGO_EC_PUBLIC_KEY_ID, ALGO_ED25519_ID, ALGO_RSASSA_PSS_ID, ALGO_RSA_ENCRYPTION_ID,
+    ALGO_SHA1_ID, ALGO_SHA1_WITH_RSA_ENCRYPTION_ID, ALGO_SHA256_ID,
+    ALGO_SHA256_WITH_ECDSA_ENCRYPTION_ID, ALGO_SHA256_WITH_RSA_ENCRYPTION_ID, ALGO_SHA384_ID,
+    ALGO_SHA384_WITH_ECDSA_ENCRYPTION_ID, ALGO_SHA384_WITH_RSA_ENCRYPTION_ID, ALGO_SHA512_ID,
+    ALGO_SHA512_WITH_ECDSA_ENCRYPTION_ID, ALGO_SHA512_WITH_RSA_ENCRYPTION_ID,
+};
+
+/// digest of a signature algorithm
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Digest {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl Digest {
+    fn from_oid(oid: &Oid) -> Option<Self> {
+        match oid.index() {
+            Some(ALGO_SHA1_ID) => Some(Digest::Sha1),
+            Some(ALGO_SHA256_ID) => Some(Digest::Sha256),
+            Some(ALGO_SHA384_ID) => Some(Digest::Sha384),
+            Some(ALGO_SHA512_ID) => Some(Digest::Sha512),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "ecdsa")]
+    fn hash(&self, data: &[u8]) -> Bytes {
+        use sha2::Digest as _;
+        match self {
+            Digest::Sha1 => sha1::Sha1::digest(data).to_vec(),
+            Digest::Sha256 => sha2::Sha256::digest(data).to_vec(),
+            Digest::Sha384 => sha2::Sha384::digest(data).to_vec(),
+            Digest::Sha512 => sha2::Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+/// signature scheme of an algorithm identifier
+#[cfg_attr(
+    not(all(feature = "rsa", feature = "ecdsa", feature = "ed25519")),
+    allow(dead_code)
+)]
+#[derive(Debug, Copy, Clone)]
+enum Scheme {
+    Pkcs1(Digest),
+    /// digest and salt length
+    Pss(Digest, usize),
+    Ecdsa(Digest),
+    Ed25519,
+}
+
+/// key type of a subject public key info
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum KeyType {
+    Rsa,
+    Ec,
+    Ed25519,
+}
+
+impl Scheme {
+    fn from_algorithm(algo: &AlgorithmIdentifier) -> Result<Self> {
+        let unsupported = || {
+            error!("Unsupported signature algorithm {}", algo.oid);
+            OcspError::OcspUnsupportedSignatureAlgo(algo.oid.to_dot())
+        };
+        let scheme = match algo.oid.index() {
+            Some(ALGO_SHA1_WITH_RSA_ENCRYPTION_ID) => Scheme::Pkcs1(Digest::Sha1),
+            Some(ALGO_SHA256_WITH_RSA_ENCRYPTION_ID) => Scheme::Pkcs1(Digest::Sha256),
+            Some(ALGO_SHA384_WITH_RSA_ENCRYPTION_ID) => Scheme::Pkcs1(Digest::Sha384),
+            Some(ALGO_SHA512_WITH_RSA_ENCRYPTION_ID) => Scheme::Pkcs1(Digest::Sha512),
+            Some(ALGO_SHA256_WITH_ECDSA_ENCRYPTION_ID) => Scheme::Ecdsa(Digest::Sha256),
+            Some(ALGO_SHA384_WITH_ECDSA_ENCRYPTION_ID) => Scheme::Ecdsa(Digest::Sha384),
+            Some(ALGO_SHA512_WITH_ECDSA_ENCRYPTION_ID) => Scheme::Ecdsa(Digest::Sha512),
+            Some(ALGO_ED25519_ID) => Scheme::Ed25519,
+            Some(ALGO_RSASSA_PSS_ID) => match &algo.parameters {
+                // MGF1 uses the message digest, trailer field is always 0xbc
+                AlgorithmParameters::RsaPss(p)
+                    if p.mgf_hash_algo.oid == p.hash_algo.oid && p.trailer_field == 1 =>
+                {
+                    let digest = Digest::from_oid(&p.hash_algo.oid).ok_or_else(unsupported)?;
+                    Scheme::Pss(digest, p.salt_len as usize)
+                }
+                _ => return Err(unsupported()),
+            },
+            _ => return Err(unsupported()),
+        };
+        Ok(scheme)
+    }
+
+    fn key_type(&self) -> KeyType {
+        match self {
+            Scheme::Pkcs1(_) | Scheme::Pss(_, _) => KeyType::Rsa,
+            Scheme::Ecdsa(_) => KeyType::Ec,
+            Scheme::Ed25519 => KeyType::Ed25519,
+        }
+    }
+}
+
+/// Subject public key of a signer, eg. OCSP responder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    algo: AlgorithmIdentifier,
+    // subjectPublicKey BIT STRING content, without unused bits
+    key: Bytes,
+}
+
+impl PublicKey {
+    /// get public key from DER SubjectPublicKeyInfo
+    pub fn from_spki_der(spki: &[u8]) -> Result<Self> {
+        trace!("Parsing subject public key info {}", hex::encode(spki));
+        let s = SequenceRef::decode(spki)?;
+        if s.len() != 2 {
+            error!(
+                "Provided subject public key info contains {} items, expecting 2",
+                s.len()
+            );
+            return Err(OcspError::Asn1LengthError("SubjectPublicKeyInfo"));
+        }
+        let mut items = s.iter();
+        let algo = items.next().ok_or(OcspError::Asn1Truncated)?.raw();
+        let algo = AlgorithmIdentifier::parse(algo).map_err(asn1_field(spki, algo, "algorithm"))?;
+        let bits = items.next().ok_or(OcspError::Asn1Truncated)?;
+        // leading byte is the number of unused bits
+        if bits.tag() != ASN1_BIT_STRING || bits.value().first() != Some(&0x00) {
+            error!("Provided subject public key is not a BIT STRING of whole bytes");
+            let e = OcspError::Asn1MismatchError("SubjectPublicKeyInfo subjectPublicKey");
+            return Err(asn1_field(spki, bits.raw(), "subjectPublicKey")(e));
+        }
+
+        trace!("Subject public key info {} successfully decoded", algo.oid);
+        Ok(PublicKey {
+            algo,
+            key: bits.value()[1..].to_vec(),
+        })
+    }
+
+    /// get public key of a DER X.509 certificate
+    pub fn from_certificate_der(cert: &[u8]) -> Result<Self> {
+        trace!("Extracting public key from certificate");
+        let spki = asn1_tbs_certificate(cert)?[5].raw();
+        PublicKey::from_spki_der(spki).map_err(asn1_field(
+            cert,
+            spki,
+            "tbsCertificate.subjectPublicKeyInfo",
+        ))
+    }
+
+    /// get public key from PEM  
+    /// - `PUBLIC KEY`, SubjectPublicKeyInfo  
+    /// - `CERTIFICATE`, X.509 certificate
+    pub fn from_pem(pem: &str) -> Result<Self> {
+        let (label, der) = pem_rfc7468::decode_vec(pem.as_bytes()).map_err(|e| {
+            error!("Unable to decode PEM: {}", e);
+            OcspError::Asn1MismatchError("PEM")
+        })?;
+        match label {
+            "PUBLIC KEY" => PublicKey::from_spki_der(&der),
+            "CERTIFICATE" => PublicKey::from_certificate_der(&der),
+            _ => {
+                error!("Provided PEM label is {}, expecting public key", label);
+                Err(OcspError::Asn1MismatchError("PEM label"))
+            }
+        }
+    }
+
+    /// public key algorithm, eg. rsaEncryption
+    pub fn algorithm(&self) -> &AlgorithmIdentifier {
+        &self.algo
+    }
+
+    /// subjectPublicKey, without BIT STRING unused bits byte
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    fn key_type(&self) -> Option<KeyType> {
+        match self.algo.oid.index() {
+            Some(ALGO_RSA_ENCRYPTION_ID) | Some(ALGO_RSASSA_PSS_ID) => Some(KeyType::Rsa),
+            Some(ALGO_EC_PUBLIC_KEY_ID) => Some(KeyType::Ec),
+            Some(ALGO_ED25519_ID) => Some(KeyType::Ed25519),
+            _ => None,
+        }
+    }
+
+    /// verify signature of data with algorithm  
+    /// - [OcspError::OcspBadSignature] if signature does not match  
+    /// - [OcspError::OcspUnsupportedSignatureAlgo] if algorithm is unknown or its feature is disabled  
+    /// - [OcspError::OcspSignatureKeyMismatch] if key cannot verify algorithm
+    pub fn verify(&self, algo: &AlgorithmIdentifier, data: &[u8], sign: &[u8]) -> Result<()> {
+        let scheme = Scheme::from_algorithm(algo)?;
+        if self.key_type() != Some(scheme.key_type()) {
+            error!("Public key {} cannot verify {}", self.algo.oid, algo.oid);
+            return Err(OcspError::OcspSignatureKeyMismatch(algo.oid.to_dot()));
+        }
+
+        trace!("Verifying {} bytes with {:?}", data.len(), scheme);
+        let r = match scheme {
+            #[cfg(feature = "rsa")]
+            Scheme::Pkcs1(digest) => verify_pkcs1(&self.key, digest, data, sign),
+            #[cfg(feature = "rsa")]
+            Scheme::Pss(digest, salt) => verify_pss(&self.key, digest, salt, data, sign),
+            #[cfg(feature = "ecdsa")]
+            Scheme::Ecdsa(digest) => verify_ecdsa(&self.key, digest, data, sign),
+            #[cfg(feature = "ed25519")]
+            Scheme::Ed25519 => verify_ed25519(&self.key, data, sign),
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = (data, sign);
+                error!("Verifying {} requires a disabled crate feature", algo.oid);
+                Err(OcspError::OcspUnsupportedSignatureAlgo(algo.oid.to_dot()))
+            }
+        };
+        if let Err(OcspError::OcspBadSignature) = r {
+            error!("Signature of {} does not verify", algo.oid);
+        }
+        r
+    }
+}
+
+#[cfg(any(feature = "rsa", feature = "ecdsa", feature = "ed25519"))]
+fn bad_signature<E>(_: E) -> OcspError {
+    OcspError::OcspBadSignature
+}
+
+#[cfg(any(feature = "rsa", feature = "ecdsa", feature = "ed25519"))]
+fn invalid_key<E: std::fmt::Display>(e: E) -> OcspError {
+    error!("Unable to load public key: {}", e);
+    OcspError::Asn1MismatchError("SubjectPublicKeyInfo subjectPublicKey")
+}
+
+/// verify by RustCrypto key of signature decoded from bytes
+#[cfg(any(feature = "rsa", feature = "ed25519"))]
+fn verify_with<V, S>(key: &V, data: &[u8], sign: &[u8]) -> Result<()>
+where
+    V: signature::Verifier<S>,
+    S: for<'a> TryFrom<&'a [u8], Error = signature::Error>,
+{
+    let sign = S::try_from(sign).map_err(bad_signature)?;
+    key.verify(data, &sign).map_err(bad_signature)
+}
+
+#[cfg(feature = "rsa")]
+fn verify_pkcs1(key: &[u8], digest: Digest, data: &[u8], sign: &[u8]) -> Result<()> {
+    use rsa::pkcs1::DecodeRsaPublicKey;
+    use rsa::pkcs1v15::{Signature, VerifyingKey};
+    let key = rsa::RsaPublicKey::from_pkcs1_der(key).map_err(invalid_key)?;
+    match digest {
+        Digest::Sha1 => {
+            verify_with::<_, Signature>(&VerifyingKey::<sha1::Sha1>::new(key), data, sign)
+        }
+        Digest::Sha256 => {
+            verify_with::<_, Signature>(&VerifyingKey::<sha2::Sha256>::new(key), data, sign)
+        }
+        Digest::Sha384 => {
+            verify_with::<_, Signature>(&VerifyingKey::<sha2::Sha384>::new(key), data, sign)
+        }
+        Digest::Sha512 => {
+            verify_with::<_, Signature>(&VerifyingKey::<sha2::Sha512>::new(key), data, sign)
+        }
+    }
+}
+
+#[cfg(feature = "rsa")]
+fn verify_pss(key: &[u8], digest: Digest, salt: usize, data: &[u8], sign: &[u8]) -> Result<()> {
+    use rsa::pkcs1::DecodeRsaPublicKey;
+    use rsa::pss::{Signature, VerifyingKey};
+    let key = rsa::RsaPublicKey::from_pkcs1_der(key).map_err(invalid_key)?;
+    match digest {
+        Digest::Sha1 => verify_with::<_, Signature>(
+            &VerifyingKey::<sha1::Sha1>::new_with_salt_len(key, salt),
+            data,
+            sign,
+        ),
+        Digest::Sha256 => verify_with::<_, Signature>(
+            &VerifyingKey::<sha2::Sha256>::new_with_salt_len(key, salt),
+            data,
+            sign,
+        ),
+        Digest::Sha384 => verify_with::<_, Signature>(
+            &VerifyingKey::<sha2::Sha384>::new_with_salt_len(key, salt),
+            data,
+            sign,
+        ),
+        Digest::Sha512 => verify_with::<_, Signature>(
+            &VerifyingKey::<sha2::Sha512>::new_with_salt_len(key, salt),
+            data,
+            sign,
+        ),
+    }
+}
+
+/// curve is told by point length
+#[cfg(feature = "ecdsa")]
+fn verify_ecdsa(key: &[u8], digest: Digest, data: &[u8], sign: &[u8]) -> Result<()> {
+    use signature::hazmat::PrehashVerifier;
+    let hash = digest.hash(data);
+    if let Ok(key) = p256::ecdsa::VerifyingKey::from_sec1_bytes(key) {
+        let sign = p256::ecdsa::Signature::from_der(sign).map_err(bad_signature)?;
+        return key.verify_prehash(&hash, &sign).map_err(bad_signature);
+    }
+    let key = p384::ecdsa::VerifyingKey::from_sec1_bytes(key).map_err(invalid_key)?;
+    let sign = p384::ecdsa::Signature::from_der(sign).map_err(bad_signature)?;
+    key.verify_prehash(&hash, &sign).map_err(bad_signature)
+}
+
+#[cfg(feature = "ed25519")]
+fn verify_ed25519(key: &[u8], data: &[u8], sign: &[u8]) -> Result<()> {
+    let key = <[u8; 32]>::try_from(key).map_err(invalid_key)?;
+    let key = ed25519_dalek::VerifyingKey::from_bytes(&key).map_err(invalid_key)?;
+    verify_with::<_, ed25519_dalek::Signature>(&key, data, sign)
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    // fixtures are shared, not every feature uses all of them
+    #![allow(dead_code)]
+    use super::*;
+    use crate::common::asn1::RsaPssParameters;
+    use crate::oid::*;
+
+    // public keys of crate::sign::test keys, by openssl pkey -pubout
+    pub(crate) const RSA_SPKI: &str =
+        "30819f300d06092a864886f70d010101050003818d0030818902818100d59f12b8\
+        aec818085af6fe1035550735e6e6270d993c52f868e71b78940d764f87de1738\
+        3209ee93bea9963a13e10f97f9df920b5982ee3d40723de713b26b8839aeb984\
+        08cee9798aeaa75a0ed2df3e0c1efdc6735d286b135336a5b9b5056ff4bc1961\
+        37060090cc4dc57c3ddd33a5d0572c60ac91a5de5afc6783b3a03e710203010001";
+    pub(crate) const P256_SPKI: &str =
+        "3059301306072a8648ce3d020106082a8648ce3d030107034200044bfd991b4afe\
+        e2d809f89aa624d4eeab21742697a35bbff1c06b40739a1acdd5762fb7629475\
+        09f376dcb357e0a90a9b07e76a9dac4806604c86d5b9d3f1b7d6";
+    pub(crate) const P384_SPKI: &str =
+        "3076301006072a8648ce3d020106052b8104002203620004f88ee0d13de08e8178\
+        1c3dcbb17816a1f8e3b5cde5a2c854800228d1192f98ae787c5aeb8ea17f3563\
+        0b69d7230770f93a3bd6812995a266822e76af355beb014168feb3ec9cfdd2ec\
+        b83d4fa328da57b19ae95daa7f1426179e2f9326aa5857";
+    pub(crate) const ED25519_SPKI: &str =
+        "302a300506032b65700321005c1c3f74ad131fc66e2fa04c74b9478b1d2d8e24f9810b31ad784a0509c0dfdb";
+    // self-signed P-256 CA by openssl req -x509
+    pub(crate) const CA_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIBejCCASGgAwIBAgIUNvTarlE6gFhK5nOZEM3QqKTxW2YwCgYIKoZIzj0EAwIw
+EjEQMA4GA1UEAwwHVGVzdCBDQTAgFw0yNjEwMTgwNzEzNTVaGA8yMTI2MDkyNDA3
+MTM1NVowEjEQMA4GA1UEAwwHVGVzdCBDQTBZMBMGByqGSM49AgEGCCqGSM49AwEH
+A0IABDwi0Aj7le03ij25Syq5Dx3+cx0pyy3YNVNR360giTD2rbAtF6u665hBpAJS
+R0jM0Zzr+hExxH0jYk1mOgz8hE2jUzBRMB0GA1UdDgQWBBQMMWvDb79gzmEI673/
+0mFHN64ODTAfBgNVHSMEGDAWgBQMMWvDb79gzmEI673/0mFHN64ODTAPBgNVHRMB
+Af8EBTADAQH/MAoGCCqGSM49BAMCA0cAMEQCIBqko6EcmIDhoo1F/wDWpZyjwuTX
+m2KNn4CLpN8kutZuAiBhVvXm4481P9C6KNV9R9sNyqkVmkK98J0N+uQe/fq6CQ==
+-----END CERTIFICATE-----";
+    /// issued by [CA_CERT] with serial 0x1234
+    pub(crate) const LEAF_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIBUzCB+6ADAgECAgISNDAKBggqhkjOPQQDAjASMRAwDgYDVQQDDAdUZXN0IENB
+MCAXDTI2MTAxODA3MTM1NVoYDzIxMjYwOTI0MDcxMzU1WjAPMQ0wCwYDVQQDDARs
+ZWFmMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEnN2SpPmIid4Bcq+ocGeDD7PU
+YnUGYEy8fD6BVb2vcSDzeCUGCtPSnEuhvvHfX6ST9m+Lc+rBJW/pUC6d2G/WpKNC
+MEAwHQYDVR0OBBYEFBFyFHvcnBdUzMzRgIZTF+rleNOtMB8GA1UdIwQYMBaAFAwx
+a8Nvv2DOYQjrvf/SYUc3rg4NMAoGCCqGSM49BAMCA0cAMEQCIB1QDQCiWOk8WIun
+sFUvEeLI8hscDv7J5kQSvzRqxyXGAiAMGAyOD1OLxjfoRDU0VVpAcYoC4QoYKfkV
+Rgg2LSba3w==
+-----END CERTIFICATE-----";
+    const CA_SPKI: &str = "3059301306072a8648ce3d020106082a8648ce3d030107034200043c22d008fb95\
+        ed378a3db94b2ab90f1dfe731d29cb2dd8355351dfad208930f6adb02d17abba\
+        eb9841a402524748ccd19cebfa1131c47d23624d663a0cfc844d";
+    const MSG: &[u8] = b"ocsp-rs";
+
+    pub(crate) fn key(spki: &str) -> PublicKey {
+        PublicKey::from_spki_der(&hex::decode(spki).unwrap()).unwrap()
+    }
+
+    fn algo(dot: &str) -> AlgorithmIdentifier {
+        AlgorithmIdentifier::new(Oid::new_from_dot(dot).unwrap())
+    }
+
+    // signatures by openssl dgst -sign
+    #[cfg(all(feature = "rsa", feature = "ecdsa", feature = "ed25519"))]
+    #[test]
+    fn verify_openssl_signatures() {
+        let pss = RsaPssParameters::new(Oid::new_from_dot(ALGO_SHA256_DOT).unwrap(), 32);
+        let vectors = [
+            (
+                RSA_SPKI,
+                algo(ALGO_SHA256_WITH_RSA_ENCRYPTION_DOT),
+                "35eaeb94d2edde2fe022556a22ed367cb66d1c8b5b029275556051643f5abf18\
+                0f4e481be7422b58ba89748b2c84c008d551d328cfd68773344609e4b201b267\
+                7ffcb7f8a03c9bb4ad51690040b5aaf71f434416a7ba0a37c4d7b4cfebb3c5aa\
+                e4d5f2b54615bcc67511b3d14c0bdb172cf597be9c861e2feb7352e202e0b77f",
+            ),
+            (
+                RSA_SPKI,
+                AlgorithmIdentifier::new_rsa_pss(pss).unwrap(),
+                "0b446d560698b241a7ea5b6f4405bec6ecfb5af177096623f86f87fe9b8ff35a\
+                236026f805f705e10013cce12d41a2d310dfa13a6f02a8ba7d6fbcf9ddc78adf\
+                cd517c03c9145a1f2f6259858f55a8065a14992a4485c97fd5f1e5d802dc6fa6\
+                8f62f73b82a77767f4455536174d4d04208e4ff94abb2229f7e3f1c6fc590196",
+            ),
+            (
+                P256_SPKI,
+                algo(ALGO_SHA256_WITH_ECDSA_ENCRYPTION_DOT),
+                "3045022100c41468d4267f3df92aaa81a3491524d0ba096c76bdf3c0096740d0\
+                48d671d45e02203c542f76405a7bd56f7d37ab7ddd5b678b353ab08293aff95c\
+                23b66a946325d0",
+            ),
+            (
+                P384_SPKI,
+                algo(ALGO_SHA384_WITH_ECDSA_ENCRYPTION_DOT),
+                "3064023014d8a75ad63f575b0b606c3c011ea351614fc1cb75ad120255b11777\
+                30b0e5b7b67473c42c2747f683370a01c093f00502301c81930c68b3c869fd71\
+                93152650bdf1e74a7e980ed91e37b96e82b9225d8363090432930e64b25a9bc4\
+                9c0d54efa7ef",
+            ),
+            (
+                ED25519_SPKI,
+                algo(ALGO_ED25519_DOT),
+                "0db72977a957f09e8031df86dd870b34a27dc1d59669d88400ea84b88b61f38e\
+                67b5bd77d43ce134bfa8fca9eb282db43dbf8d505eb0f91a6a7043f600012608",
+            ),
+        ];
+        for (spki, algo, sign) in vectors.iter() {
+            let key = key(spki);
+            let mut sign = hex::decode(sign).unwrap();
+            key.verify(algo, MSG, &sign).unwrap();
+            assert!(key.verify(algo, b"ocsp-rt", &sign).is_err());
+            let last = sign.len() - 1;
+            sign[last] ^= 0x01;
+            match key.verify(algo, MSG, &sign) {
+                Err(OcspError::OcspBadSignature) => {}
+                r => panic!("unexpected {:?} for {}", r, algo.oid),
+            }
+        }
+    }
+
+    // signer and verifier agree on every algorithm
+    #[cfg(all(feature = "rsa", feature = "ecdsa", feature = "ed25519"))]
+    #[test]
+    fn verify_signer_round_trip() {
+        use crate::sign::{test::*, *};
+        let signers: Vec<(Box<dyn Signer>, &str)> = vec![
+            (
+                Box::new(RsaSigner::from_pem(RSA_KEY_PKCS1, SignatureHash::Sha384).unwrap()),
+                RSA_SPKI,
+            ),
+            (
+                Box::new(RsaSigner::from_pem(RSA_KEY_PKCS1, SignatureHash::Sha512).unwrap()),
+                RSA_SPKI,
+            ),
+            (
+                Box::new(EcdsaSigner::from_pem(P256_KEY_PKCS8).unwrap()),
+                P256_SPKI,
+            ),
+            (
+                Box::new(EcdsaSigner::from_pem(P384_KEY_SEC1).unwrap()),
+                P384_SPKI,
+            ),
+            (
+                Box::new(Ed25519Signer::from_pem(ED25519_KEY_PKCS8).unwrap()),
+                ED25519_SPKI,
+            ),
+        ];
+        for (signer, spki) in signers.iter() {
+            let sign = signer.sign(MSG).unwrap();
+            key(spki).verify(&signer.algorithm(), MSG, &sign).unwrap();
+        }
+    }
+
+    // unsupported algorithm and key mismatch are told apart from bad signature
+    #[test]
+    fn verify_errors() {
+        let rsa = key(RSA_SPKI);
+        match rsa.verify(&algo(ALGO_SHA256_WITH_ECDSA_ENCRYPTION_DOT), MSG, &[0; 8]) {
+            Err(OcspError::OcspSignatureKeyMismatch(dot)) => {
+                assert_eq!(dot, ALGO_SHA256_WITH_ECDSA_ENCRYPTION_DOT)
+            }
+            r => panic!("unexpected {:?}", r),
+        }
+        match key(ED25519_SPKI).verify(&algo(ALGO_SHA1_WITH_RSA_ENCRYPTION_DOT), MSG, &[0; 8]) {
+            Err(OcspError::OcspSignatureKeyMismatch(_)) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+        match rsa.verify(&algo(ALGO_SHA256_DOT), MSG, &[0; 8]) {
+            Err(OcspError::OcspUnsupportedSignatureAlgo(dot)) => assert_eq!(dot, ALGO_SHA256_DOT),
+            r => panic!("unexpected {:?}", r),
+        }
+        // MGF1 with another digest
+        let mut pss = RsaPssParameters::new(Oid::new_from_dot(ALGO_SHA256_DOT).unwrap(), 32);
+        pss.mgf_hash_algo = algo(ALGO_SHA1_DOT);
+        let pss = AlgorithmIdentifier::new_rsa_pss(pss).unwrap();
+        match rsa.verify(&pss, MSG, &[0; 8]) {
+            Err(OcspError::OcspUnsupportedSignatureAlgo(dot)) => {
+                assert_eq!(dot, ALGO_RSASSA_PSS_DOT)
+            }
+            r => panic!("unexpected {:?}", r),
+        }
+    }
+
+    // public key from spki and certificate
+    #[test]
+    fn public_key_parse() {
+        let ca = PublicKey::from_pem(CA_CERT).unwrap();
+        assert_eq!(ca, key(CA_SPKI));
+        assert_eq!(ca.algorithm().oid.to_dot(), ALGO_EC_PUBLIC_KEY_DOT);
+
+        let (_, der) = pem_rfc7468::decode_vec(CA_CERT.as_bytes()).unwrap();
+        assert_eq!(ca, PublicKey::from_certificate_der(&der).unwrap());
+        let spki = hex::decode(CA_SPKI).unwrap();
+        let pem = pem_rfc7468::encode_string("PUBLIC KEY", Default::default(), &spki).unwrap();
+        assert_eq!(ca, PublicKey::from_pem(&pem).unwrap());
+
+        let pem = pem_rfc7468::encode_string("PRIVATE KEY", Default::default(), &spki).unwrap();
+        assert!(PublicKey::from_pem(&pem).is_err());
+        // subjectPublicKey with unused bits
+        let mut spki = spki;
+        spki[25] = 0x01;
+        let e = PublicKey::from_spki_der(&spki).unwrap_err();
+        assert_eq!(e.path(), Some("subjectPublicKey"));
+        assert_eq!(e.offset(), Some(23));
+    }
+}